@@ -0,0 +1,49 @@
+use std::process::Command;
+
+// gRPC 接口的 proto 文件，相对于 proto/ 目录
+const PROTOS: &[&str] = &["alpha/v1/tags.proto", "alpha/v1/tickets.proto"];
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    embed_git_sha();
+    compile_protos()
+}
+
+// 将 git 提交哈希嵌入二进制（供 GET /version 使用）
+// 优先读取 GIT_SHA 环境变量（CI / Docker 构建时没有 .git 目录），否则调用 git，都失败时为 "unknown"
+fn embed_git_sha() {
+    println!("cargo:rerun-if-env-changed=GIT_SHA");
+
+    let sha = std::env::var("GIT_SHA")
+        .ok()
+        .filter(|sha| !sha.trim().is_empty())
+        .or_else(git_sha)
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=GIT_SHA={}", sha.trim());
+
+    // 提交变化时重新生成
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+        println!("cargo:rerun-if-changed={}/refs", git_dir);
+    }
+}
+
+// 生成 gRPC 服务端与客户端代码；用 protox 解析 proto 文件，构建时无需安装 protoc
+fn compile_protos() -> Result<(), Box<dyn std::error::Error>> {
+    println!("cargo:rerun-if-changed=proto");
+    let descriptors = protox::compile(PROTOS, ["proto"])?;
+    tonic_prost_build::configure().compile_fds(descriptors)?;
+    Ok(())
+}
+
+fn git_sha() -> Option<String> {
+    git(&["rev-parse", "--short=12", "HEAD"])
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let text = String::from_utf8(output.stdout).ok()?;
+    Some(text.trim().to_string()).filter(|text| !text.is_empty())
+}
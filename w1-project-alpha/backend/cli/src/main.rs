@@ -0,0 +1,259 @@
+use clap::{Args, Parser, Subcommand};
+use std::error::Error;
+use uuid::Uuid;
+
+use project_alpha_client::models::{
+    CreateTagRequest, CreateTicketRequest, TicketFilter, TicketWithTags, UpdateTicketRequest,
+};
+use project_alpha_client::Client;
+
+mod output;
+mod resolve;
+mod settings;
+mod tui;
+
+use output::OutputFormat;
+use settings::{Settings, DEFAULT_SERVER};
+
+/// Project Alpha 命令行客户端
+///
+/// 服务端地址依次取自 `--server`、`ALPHA_SERVER` 环境变量、配置文件 `~/.config/alpha/config.toml`（可用 `ALPHA_CONFIG` 指定其他路径）中的 `server`，默认为 http://localhost:3000。
+/// ID 可以只写开头几位，只要能唯一确定一条记录。
+#[derive(Parser)]
+#[command(name = "alpha", version)]
+struct Cli {
+    /// 服务端地址
+    #[arg(long, global = true, env = "ALPHA_SERVER", value_name = "URL")]
+    server: Option<String>,
+
+    /// 输出格式（默认取配置文件中的 output，否则为 table）
+    #[arg(short, long, global = true, value_enum)]
+    output: Option<OutputFormat>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 工单
+    #[command(subcommand)]
+    Ticket(TicketCommand),
+    /// 标签
+    #[command(subcommand)]
+    Tag(TagCommand),
+    /// 全屏终端界面：工单列表、筛选、标签侧栏与详情，随服务端变更实时刷新（按 ? 查看快捷键）
+    Tui {
+        /// 在协作连接中显示的用户名
+        #[arg(long, env = "USER", default_value = "alpha")]
+        user: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum TicketCommand {
+    /// 列出工单，新的在前
+    Ls(ListArgs),
+    /// 查看单个工单
+    Show { id: String },
+    /// 创建工单
+    New {
+        title: String,
+        /// 描述
+        #[arg(short, long)]
+        description: Option<String>,
+        /// 标签名，可重复
+        #[arg(short, long = "tag", value_name = "NAME")]
+        tags: Vec<String>,
+    },
+    /// 标记为已完成
+    Done {
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// 重新打开
+    Reopen {
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// 删除工单
+    Rm { id: String },
+}
+
+#[derive(Args)]
+struct ListArgs {
+    /// 只列出带有该标签的工单
+    #[arg(short, long, value_name = "NAME")]
+    tag: Option<String>,
+    /// 只列出未完成的工单
+    #[arg(long, conflicts_with = "done")]
+    open: bool,
+    /// 只列出已完成的工单
+    #[arg(long)]
+    done: bool,
+    /// 标题包含该文本（不区分大小写）
+    #[arg(short, long)]
+    search: Option<String>,
+}
+
+#[derive(Subcommand)]
+enum TagCommand {
+    /// 列出所有标签
+    Ls,
+    /// 创建标签
+    New {
+        name: String,
+        /// 颜色，#RRGGBB 或 #RGB
+        #[arg(short, long)]
+        color: Option<String>,
+    },
+    /// 删除标签（按名称），并从所有工单上移除
+    Rm { name: String },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    if let Err(err) = run(cli).await {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    let settings = Settings::load()?;
+    let server = cli
+        .server
+        .or(settings.server)
+        .unwrap_or_else(|| DEFAULT_SERVER.to_string());
+    let format = cli.output.or(settings.output).unwrap_or_default();
+    let client = Client::new(server)?;
+
+    let text = match cli.command {
+        Command::Ticket(command) => ticket(&client, command, format).await?,
+        Command::Tag(command) => tag(&client, command, format).await?,
+        Command::Tui { user } => {
+            tui::run(&client, &user).await?;
+            String::new()
+        }
+    };
+    print!("{}", text);
+    Ok(())
+}
+
+async fn ticket(
+    client: &Client,
+    command: TicketCommand,
+    format: OutputFormat,
+) -> Result<String, Box<dyn Error>> {
+    let text = match command {
+        TicketCommand::Ls(args) => {
+            let tag = match args.tag {
+                Some(name) => Some(resolve::tag_ids(&client.list_tags().await?, &[name])?[0]),
+                None => None,
+            };
+            let filter = TicketFilter {
+                tag,
+                search: args.search,
+                completed: (args.open || args.done).then_some(args.done),
+            };
+            output::tickets(format, &client.list_tickets(&filter).await?)?
+        }
+        TicketCommand::Show { id } => {
+            let id = ticket_ids(client, std::slice::from_ref(&id)).await?[0];
+            output::ticket(format, &client.get_ticket(id).await?)?
+        }
+        TicketCommand::New {
+            title,
+            description,
+            tags,
+        } => {
+            let tag_ids = if tags.is_empty() {
+                None
+            } else {
+                Some(resolve::tag_ids(&client.list_tags().await?, &tags)?)
+            };
+            let created = client
+                .create_ticket(&CreateTicketRequest {
+                    title,
+                    description,
+                    tag_ids,
+                })
+                .await?;
+            output::ticket(format, &client.get_ticket(created.id).await?)?
+        }
+        TicketCommand::Done { ids } => set_completed(client, &ids, true, format).await?,
+        TicketCommand::Reopen { ids } => set_completed(client, &ids, false, format).await?,
+        TicketCommand::Rm { id } => {
+            let id = ticket_ids(client, std::slice::from_ref(&id)).await?[0];
+            client.delete_ticket(id).await?;
+            format!("Deleted ticket {}\n", id)
+        }
+    };
+    Ok(text)
+}
+
+async fn set_completed(
+    client: &Client,
+    ids: &[String],
+    completed: bool,
+    format: OutputFormat,
+) -> Result<String, Box<dyn Error>> {
+    // 先解析全部 ID，任何一个无效时不做修改
+    let resolved = ticket_ids(client, ids).await?;
+
+    let mut tickets: Vec<TicketWithTags> = Vec::with_capacity(resolved.len());
+    for id in resolved {
+        client
+            .update_ticket(
+                id,
+                &UpdateTicketRequest {
+                    title: None,
+                    description: None,
+                    completed: Some(completed),
+                },
+            )
+            .await?;
+        tickets.push(client.get_ticket(id).await?);
+    }
+    Ok(output::tickets(format, &tickets)?)
+}
+
+/// 完整的 UUID 直接使用，其余按前缀在工单列表中查找；列表最多获取一次
+async fn ticket_ids(client: &Client, ids: &[String]) -> Result<Vec<Uuid>, Box<dyn Error>> {
+    let mut tickets = None;
+    let mut resolved = Vec::with_capacity(ids.len());
+    for id in ids {
+        if let Ok(id) = Uuid::parse_str(id) {
+            resolved.push(id);
+            continue;
+        }
+        let tickets = match &mut tickets {
+            Some(tickets) => tickets,
+            None => tickets.insert(client.list_tickets(&TicketFilter::default()).await?),
+        };
+        let ticket = resolve::by_id_prefix(tickets, id, "ticket", |ticket| ticket.ticket.id)?;
+        resolved.push(ticket.ticket.id);
+    }
+    Ok(resolved)
+}
+
+async fn tag(
+    client: &Client,
+    command: TagCommand,
+    format: OutputFormat,
+) -> Result<String, Box<dyn Error>> {
+    let text = match command {
+        TagCommand::Ls => output::tags(format, &client.list_tags().await?)?,
+        TagCommand::New { name, color } => {
+            let tag = client.create_tag(&CreateTagRequest { name, color }).await?;
+            output::tags(format, &[tag])?
+        }
+        TagCommand::Rm { name } => {
+            let id = resolve::tag_ids(&client.list_tags().await?, std::slice::from_ref(&name))?[0];
+            client.delete_tag(id).await?;
+            format!("Deleted tag {}\n", name)
+        }
+    };
+    Ok(text)
+}
@@ -0,0 +1,224 @@
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+use project_alpha_client::models::{Tag, TicketWithTags};
+
+/// Characters of an id shown in tables; enough to pass back as a prefix.
+pub const SHORT_ID_LEN: usize = 8;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Aligned columns with shortened ids.
+    #[default]
+    Table,
+    /// The API's JSON, pretty-printed.
+    Json,
+    /// The columns of the server's CSV export, so output can be imported.
+    Csv,
+}
+
+pub fn tickets(format: OutputFormat, tickets: &[TicketWithTags]) -> Result<String, String> {
+    match format {
+        OutputFormat::Table => Ok(table(
+            &["ID", "STATUS", "TITLE", "TAGS", "UPDATED"],
+            tickets.iter().map(|ticket| {
+                vec![
+                    short_id(&ticket.ticket.id.to_string()),
+                    status(ticket.ticket.completed).to_string(),
+                    ticket.ticket.title.clone(),
+                    tag_names(ticket, ", "),
+                    ticket
+                        .ticket
+                        .updated_at
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string(),
+                ]
+            }),
+        )),
+        OutputFormat::Json => json(tickets),
+        OutputFormat::Csv => csv(
+            &[
+                "id",
+                "title",
+                "description",
+                "completed",
+                "created_at",
+                "updated_at",
+                "tags",
+            ],
+            tickets.iter().map(|ticket| {
+                vec![
+                    ticket.ticket.id.to_string(),
+                    ticket.ticket.title.clone(),
+                    ticket.ticket.description.clone().unwrap_or_default(),
+                    ticket.ticket.completed.to_string(),
+                    ticket.ticket.created_at.to_rfc3339(),
+                    ticket.ticket.updated_at.to_rfc3339(),
+                    tag_names(ticket, ","),
+                ]
+            }),
+        ),
+    }
+}
+
+/// One ticket with its description, for `show` and after changes.
+pub fn ticket(format: OutputFormat, ticket: &TicketWithTags) -> Result<String, String> {
+    match format {
+        OutputFormat::Table => {
+            let mut text = table(
+                &["ID", "STATUS", "TITLE", "TAGS", "CREATED", "UPDATED"],
+                [vec![
+                    ticket.ticket.id.to_string(),
+                    status(ticket.ticket.completed).to_string(),
+                    ticket.ticket.title.clone(),
+                    tag_names(ticket, ", "),
+                    ticket
+                        .ticket
+                        .created_at
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string(),
+                    ticket
+                        .ticket
+                        .updated_at
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string(),
+                ]],
+            );
+            if let Some(description) = &ticket.ticket.description {
+                text.push('\n');
+                text.push_str(description);
+                text.push('\n');
+            }
+            Ok(text)
+        }
+        OutputFormat::Json => json(ticket),
+        OutputFormat::Csv => tickets(format, std::slice::from_ref(ticket)),
+    }
+}
+
+pub fn tags(format: OutputFormat, tags: &[Tag]) -> Result<String, String> {
+    let rows = |full_id: bool| {
+        tags.iter().map(move |tag| {
+            let id = tag.id.to_string();
+            vec![
+                if full_id { id } else { short_id(&id) },
+                tag.name.clone(),
+                tag.color.clone().unwrap_or_default(),
+            ]
+        })
+    };
+    match format {
+        OutputFormat::Table => Ok(table(&["ID", "NAME", "COLOR"], rows(false))),
+        OutputFormat::Json => json(tags),
+        OutputFormat::Csv => csv(&["id", "name", "color"], rows(true)),
+    }
+}
+
+fn status(completed: bool) -> &'static str {
+    if completed {
+        "done"
+    } else {
+        "open"
+    }
+}
+
+fn short_id(id: &str) -> String {
+    id.chars().take(SHORT_ID_LEN).collect()
+}
+
+fn tag_names(ticket: &TicketWithTags, separator: &str) -> String {
+    ticket
+        .tags
+        .iter()
+        .map(|tag| tag.name.as_str())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// Columns padded to their widest cell; the last one is not padded.
+fn table(headers: &[&str], rows: impl IntoIterator<Item = Vec<String>>) -> String {
+    let rows: Vec<Vec<String>> = rows.into_iter().collect();
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|header| header.chars().count())
+        .collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut text = String::new();
+    let header_row: Vec<String> = headers.iter().map(|header| header.to_string()).collect();
+    for row in std::iter::once(&header_row).chain(&rows) {
+        let last = row.len() - 1;
+        for (i, (cell, width)) in row.iter().zip(&widths).enumerate() {
+            if i == last {
+                text.push_str(cell);
+            } else {
+                let padding = width - cell.chars().count();
+                text.push_str(cell);
+                text.push_str(&" ".repeat(padding + 2));
+            }
+        }
+        text.truncate(text.trim_end().len());
+        text.push('\n');
+    }
+    text
+}
+
+fn csv(headers: &[&str], rows: impl IntoIterator<Item = Vec<String>>) -> Result<String, String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let error = |err: csv::Error| format!("Failed to write CSV: {}", err);
+    writer.write_record(headers).map_err(error)?;
+    for row in rows {
+        writer.write_record(&row).map_err(error)?;
+    }
+    let bytes = writer
+        .into_inner()
+        .map_err(|err| format!("Failed to write CSV: {}", err.error()))?;
+    String::from_utf8(bytes).map_err(|err| err.to_string())
+}
+
+fn json<T: Serialize + ?Sized>(value: &T) -> Result<String, String> {
+    let mut text = serde_json::to_string_pretty(value).map_err(|err| err.to_string())?;
+    text.push('\n');
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table_aligns_columns_and_trims_trailing_space() {
+        let text = table(
+            &["ID", "TITLE", "TAGS"],
+            [
+                vec!["3f2a0c1e".to_string(), "Crash".to_string(), String::new()],
+                vec![
+                    "a1000000".to_string(),
+                    "Slow search".to_string(),
+                    "bug".to_string(),
+                ],
+            ],
+        );
+        assert_eq!(
+            text,
+            "ID        TITLE        TAGS\n\
+             3f2a0c1e  Crash\n\
+             a1000000  Slow search  bug\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields() {
+        let text = csv(
+            &["id", "title"],
+            [vec!["1".to_string(), "Say \"hi\", then leave".to_string()]],
+        )
+        .unwrap();
+        assert_eq!(text, "id,title\n1,\"Say \"\"hi\"\", then leave\"\n");
+    }
+}
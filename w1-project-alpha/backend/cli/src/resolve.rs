@@ -0,0 +1,75 @@
+use uuid::Uuid;
+
+/// Find the item whose id is `id` or starts with it, as shown in the
+/// shortened ids of table output. `kind` names the item in errors.
+pub fn by_id_prefix<'a, T>(
+    items: &'a [T],
+    id: &str,
+    kind: &str,
+    id_of: impl Fn(&T) -> Uuid,
+) -> Result<&'a T, String> {
+    let prefix = id.trim().to_ascii_lowercase();
+    if prefix.is_empty() {
+        return Err(format!("Missing {} id", kind));
+    }
+    let matches: Vec<&T> = items
+        .iter()
+        .filter(|item| id_of(item).to_string().starts_with(&prefix))
+        .collect();
+    match matches.as_slice() {
+        [item] => Ok(item),
+        [] => Err(format!("No {} with id {}", kind, id)),
+        _ => Err(format!(
+            "{} id {} is ambiguous: {}",
+            kind,
+            id,
+            matches
+                .iter()
+                .map(|item| id_of(item).to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// The ids of the tags called `names`, ignoring case.
+pub fn tag_ids(
+    tags: &[project_alpha_client::models::Tag],
+    names: &[String],
+) -> Result<Vec<Uuid>, String> {
+    names
+        .iter()
+        .map(|name| {
+            tags.iter()
+                .find(|tag| tag.name.eq_ignore_ascii_case(name.trim()))
+                .map(|tag| tag.id)
+                .ok_or_else(|| format!("No tag named '{}'", name))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(text: &str) -> Uuid {
+        Uuid::parse_str(text).unwrap()
+    }
+
+    #[test]
+    fn prefixes_must_match_exactly_one_id() {
+        let ids = [
+            id("3f2a0c1e-0000-4000-8000-000000000001"),
+            id("3f2b9d44-0000-4000-8000-000000000002"),
+            id("a1000000-0000-4000-8000-000000000003"),
+        ];
+        let find = |prefix: &str| by_id_prefix(&ids, prefix, "ticket", |id| *id);
+
+        assert_eq!(find("3F2A").unwrap(), &ids[0]);
+        assert_eq!(find("a1").unwrap(), &ids[2]);
+        assert_eq!(find(&ids[1].to_string()).unwrap(), &ids[1]);
+        assert!(find("3f2").unwrap_err().contains("ambiguous"));
+        assert_eq!(find("ffff").unwrap_err(), "No ticket with id ffff");
+        assert!(find(" ").is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+use crate::output::OutputFormat;
+
+/// Server used when neither the flag, the environment nor the config file
+/// names one.
+pub const DEFAULT_SERVER: &str = "http://localhost:3000";
+
+/// Overrides the config file location.
+pub const CONFIG_ENV: &str = "ALPHA_CONFIG";
+
+/// `~/.config/alpha/config.toml`; every key is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    /// Base URL of the server, e.g. `http://localhost:3000`.
+    pub server: Option<String>,
+    /// Output format when `--output` is not given.
+    pub output: Option<OutputFormat>,
+}
+
+impl Settings {
+    /// The config file at `$ALPHA_CONFIG`, else under `$XDG_CONFIG_HOME` or
+    /// `~/.config`. A missing file means defaults.
+    pub fn load() -> Result<Self, String> {
+        match config_path() {
+            Some(path) if path.exists() => Self::read(&path),
+            _ => Ok(Self::default()),
+        }
+    }
+
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?;
+        toml::from_str(&text).map_err(|err| format!("Invalid config {}: {}", path.display(), err))
+    }
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(base.join("alpha").join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_reject_unknown_keys() {
+        let settings: Settings =
+            toml::from_str("server = \"http://alpha:3000\"\noutput = \"json\"").unwrap();
+        assert_eq!(settings.server.as_deref(), Some("http://alpha:3000"));
+        assert_eq!(settings.output, Some(OutputFormat::Json));
+
+        assert!(toml::from_str::<Settings>("").is_ok());
+        assert!(toml::from_str::<Settings>("sever = \"typo\"").is_err());
+    }
+}
@@ -0,0 +1,455 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use uuid::Uuid;
+
+use project_alpha_client::models::{Tag, TicketFilter, TicketWithTags};
+
+/// The pane that arrow keys move in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Focus {
+    Tags,
+    #[default]
+    Tickets,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Mode {
+    #[default]
+    Normal,
+    /// Typing a search; Esc brings back the previous one.
+    Search {
+        previous: String,
+    },
+    NewTicket {
+        title: String,
+    },
+    /// Adding and removing tags of the selected ticket; `cursor` indexes
+    /// [`App::tags`].
+    EditTags {
+        cursor: usize,
+    },
+    ConfirmDelete,
+    Help,
+}
+
+/// What a key press asks of the server. The event loop performs it and
+/// reloads; the app state itself only changes locally.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    /// Fetch tags and the tickets matching the current filters.
+    Reload,
+    Toggle(Uuid),
+    Create(String),
+    Delete(Uuid),
+    AddTag {
+        ticket: Uuid,
+        tag: Uuid,
+    },
+    RemoveTag {
+        ticket: Uuid,
+        tag: Uuid,
+    },
+}
+
+/// A message for the footer until the next key press.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Status {
+    pub text: String,
+    pub error: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct App {
+    pub tickets: Vec<TicketWithTags>,
+    pub tags: Vec<Tag>,
+    pub search: String,
+    pub tag_filter: Option<Uuid>,
+    /// `None` shows all tickets, like the "all" button of the web filter panel.
+    pub completed: Option<bool>,
+    pub focus: Focus,
+    /// Index into `tickets`.
+    pub selected: usize,
+    /// Row of the tag sidebar; row 0 is "all tags".
+    pub tag_cursor: usize,
+    pub mode: Mode,
+    pub status: Option<Status>,
+    /// Whether change events are arriving from the server.
+    pub live: bool,
+}
+
+impl App {
+    pub fn query(&self) -> TicketFilter {
+        let search = self.search.trim();
+        TicketFilter {
+            tag: self.tag_filter,
+            search: (!search.is_empty()).then(|| search.to_string()),
+            completed: self.completed,
+        }
+    }
+
+    pub fn selected_ticket(&self) -> Option<&TicketWithTags> {
+        self.tickets.get(self.selected)
+    }
+
+    /// Replace the list, keeping the selected ticket selected if it is
+    /// still there.
+    pub fn set_tickets(&mut self, tickets: Vec<TicketWithTags>) {
+        let selected_id = self.selected_ticket().map(|ticket| ticket.ticket.id);
+        self.tickets = tickets;
+        self.selected = selected_id
+            .and_then(|id| self.tickets.iter().position(|t| t.ticket.id == id))
+            .unwrap_or(self.selected)
+            .min(self.tickets.len().saturating_sub(1));
+    }
+
+    /// Replace the tags. Returns `false` if the tag being filtered on is
+    /// gone, in which case the filter is cleared.
+    pub fn set_tags(&mut self, tags: Vec<Tag>) -> bool {
+        self.tags = tags;
+        self.tag_cursor = self.tag_cursor.min(self.tags.len());
+        if let Mode::EditTags { cursor } = &mut self.mode {
+            *cursor = (*cursor).min(self.tags.len().saturating_sub(1));
+        }
+        match self.tag_filter {
+            Some(id) if !self.tags.iter().any(|tag| tag.id == id) => {
+                self.tag_filter = None;
+                false
+            }
+            _ => true,
+        }
+    }
+
+    pub fn info(&mut self, text: impl Into<String>) {
+        self.status = Some(Status {
+            text: text.into(),
+            error: false,
+        });
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.status = Some(Status {
+            text: text.into(),
+            error: true,
+        });
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Some(Action::Quit);
+        }
+        self.status = None;
+        match &self.mode {
+            Mode::Normal => self.normal_key(key),
+            Mode::Search { .. } => self.search_key(key),
+            Mode::NewTicket { .. } => self.new_ticket_key(key),
+            Mode::EditTags { .. } => self.edit_tags_key(key),
+            Mode::ConfirmDelete => {
+                self.mode = Mode::Normal;
+                match key.code {
+                    KeyCode::Char('y') => self.selected_id().map(Action::Delete),
+                    _ => None,
+                }
+            }
+            Mode::Help => {
+                self.mode = Mode::Normal;
+                None
+            }
+        }
+    }
+
+    fn normal_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Char('q') => return Some(Action::Quit),
+            KeyCode::Char('?') => self.mode = Mode::Help,
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Left | KeyCode::Right => self.switch_focus(),
+            KeyCode::Char('h') | KeyCode::Char('l') => self.switch_focus(),
+            KeyCode::Char('j') | KeyCode::Down => self.move_cursor(1),
+            KeyCode::Char('k') | KeyCode::Up => self.move_cursor(-1),
+            KeyCode::Char('g') | KeyCode::Home => self.move_cursor(isize::MIN),
+            KeyCode::Char('G') | KeyCode::End => self.move_cursor(isize::MAX),
+            KeyCode::Enter if self.focus == Focus::Tags => {
+                let tag = self.tag_cursor.checked_sub(1).map(|i| self.tags[i].id);
+                return self.set_filter(|app| app.tag_filter = tag);
+            }
+            KeyCode::Char('/') => {
+                self.mode = Mode::Search {
+                    previous: self.search.clone(),
+                }
+            }
+            KeyCode::Char('f') => {
+                let next = match self.completed {
+                    None => Some(false),
+                    Some(false) => Some(true),
+                    Some(true) => None,
+                };
+                return self.set_filter(|app| app.completed = next);
+            }
+            KeyCode::Char('c') => {
+                return self.set_filter(|app| {
+                    app.search.clear();
+                    app.tag_filter = None;
+                    app.completed = None;
+                });
+            }
+            KeyCode::Char(' ') | KeyCode::Char('x') => {
+                return self.selected_id().map(Action::Toggle)
+            }
+            KeyCode::Char('t') if self.selected_ticket().is_some() => {
+                if self.tags.is_empty() {
+                    self.info("No tags yet; create one with `alpha tag new`");
+                } else {
+                    self.mode = Mode::EditTags { cursor: 0 };
+                }
+            }
+            KeyCode::Char('n') => {
+                self.mode = Mode::NewTicket {
+                    title: String::new(),
+                }
+            }
+            KeyCode::Char('d') if self.selected_ticket().is_some() => {
+                self.mode = Mode::ConfirmDelete
+            }
+            KeyCode::Char('r') => return Some(Action::Reload),
+            _ => {}
+        }
+        None
+    }
+
+    fn search_key(&mut self, key: KeyEvent) -> Option<Action> {
+        match key.code {
+            KeyCode::Enter => {
+                let Mode::Search { previous } = std::mem::take(&mut self.mode) else {
+                    return None;
+                };
+                self.selected = 0;
+                return (previous != self.search).then_some(Action::Reload);
+            }
+            KeyCode::Esc => {
+                if let Mode::Search { previous } = std::mem::take(&mut self.mode) {
+                    self.search = previous;
+                }
+            }
+            KeyCode::Backspace => {
+                self.search.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.search.push(c)
+            }
+            _ => {}
+        }
+        None
+    }
+
+    fn new_ticket_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let Mode::NewTicket { title } = &mut self.mode else {
+            return None;
+        };
+        match key.code {
+            KeyCode::Enter => {
+                let title = title.trim().to_string();
+                if title.is_empty() {
+                    return None;
+                }
+                self.mode = Mode::Normal;
+                return Some(Action::Create(title));
+            }
+            KeyCode::Esc => self.mode = Mode::Normal,
+            KeyCode::Backspace => {
+                title.pop();
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => title.push(c),
+            _ => {}
+        }
+        None
+    }
+
+    fn edit_tags_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let Mode::EditTags { cursor } = &mut self.mode else {
+            return None;
+        };
+        let last = self.tags.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => *cursor = (*cursor + 1).min(last),
+            KeyCode::Char('k') | KeyCode::Up => *cursor = cursor.saturating_sub(1),
+            KeyCode::Char(' ') | KeyCode::Enter => {
+                let tag = self.tags.get(*cursor)?.id;
+                let ticket = self.tickets.get(self.selected)?;
+                let id = ticket.ticket.id;
+                return Some(if ticket.tags.iter().any(|t| t.id == tag) {
+                    Action::RemoveTag { ticket: id, tag }
+                } else {
+                    Action::AddTag { ticket: id, tag }
+                });
+            }
+            KeyCode::Esc | KeyCode::Char('t') | KeyCode::Char('q') => self.mode = Mode::Normal,
+            _ => {}
+        }
+        None
+    }
+
+    fn selected_id(&self) -> Option<Uuid> {
+        self.selected_ticket().map(|ticket| ticket.ticket.id)
+    }
+
+    fn switch_focus(&mut self) {
+        self.focus = match self.focus {
+            Focus::Tags => Focus::Tickets,
+            Focus::Tickets => Focus::Tags,
+        };
+    }
+
+    fn move_cursor(&mut self, by: isize) {
+        let (cursor, len) = match self.focus {
+            Focus::Tags => (&mut self.tag_cursor, self.tags.len() + 1),
+            Focus::Tickets => (&mut self.selected, self.tickets.len()),
+        };
+        let last = len.saturating_sub(1) as isize;
+        *cursor = (*cursor as isize).saturating_add(by).clamp(0, last) as usize;
+    }
+
+    /// Change the filters and ask for a reload if they changed.
+    fn set_filter(&mut self, change: impl FnOnce(&mut Self)) -> Option<Action> {
+        let before = self.query();
+        change(self);
+        let after = self.query();
+        let changed = (before.tag, before.search, before.completed)
+            != (after.tag, after.search, after.completed);
+        if changed {
+            self.selected = 0;
+        }
+        changed.then_some(Action::Reload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use project_alpha_client::models::Ticket;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn tag(name: &str) -> Tag {
+        Tag {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            color: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn ticket(title: &str, tags: Vec<Tag>) -> TicketWithTags {
+        TicketWithTags {
+            ticket: Ticket {
+                id: Uuid::new_v4(),
+                title: title.to_string(),
+                description: None,
+                completed: false,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            },
+            tags,
+        }
+    }
+
+    #[test]
+    fn filters_cycle_and_ask_for_a_reload() {
+        let mut app = App::default();
+        let bug = tag("bug");
+        app.set_tags(vec![bug.clone()]);
+
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char('f'))),
+            Some(Action::Reload)
+        );
+        assert_eq!(app.query().completed, Some(false));
+        app.handle_key(key(KeyCode::Char('f')));
+        app.handle_key(key(KeyCode::Char('f')));
+        assert_eq!(app.query().completed, None);
+
+        // 侧边栏第 0 行是“全部标签”
+        app.handle_key(key(KeyCode::Tab));
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), Some(Action::Reload));
+        assert_eq!(app.query().tag, Some(bug.id));
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), None);
+
+        // 搜索按 Enter 生效，Esc 恢复原来的搜索
+        app.handle_key(key(KeyCode::Char('/')));
+        for c in "crash".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), Some(Action::Reload));
+        assert_eq!(app.query().search.as_deref(), Some("crash"));
+        app.handle_key(key(KeyCode::Char('/')));
+        app.handle_key(key(KeyCode::Backspace));
+        app.handle_key(key(KeyCode::Esc));
+        assert_eq!(app.search, "crash");
+
+        // 标签被删除后清除该筛选
+        assert!(!app.set_tags(Vec::new()));
+        assert_eq!(app.query().tag, None);
+    }
+
+    #[test]
+    fn ticket_keys_act_on_the_selected_ticket() {
+        let mut app = App::default();
+        let bug = tag("bug");
+        let docs = tag("docs");
+        app.set_tags(vec![bug.clone(), docs.clone()]);
+        let first = ticket("First", vec![bug.clone()]);
+        let second = ticket("Second", Vec::new());
+        let second_id = second.ticket.id;
+        app.set_tickets(vec![first.clone(), second.clone()]);
+
+        app.handle_key(key(KeyCode::Char('j')));
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char(' '))),
+            Some(Action::Toggle(second_id))
+        );
+
+        // 重新加载后仍选中同一工单
+        app.set_tickets(vec![ticket("New", Vec::new()), first.clone(), second]);
+        assert_eq!(app.selected, 2);
+
+        app.handle_key(key(KeyCode::Char('k')));
+        app.handle_key(key(KeyCode::Char('t')));
+        assert_eq!(
+            app.handle_key(key(KeyCode::Enter)),
+            Some(Action::RemoveTag {
+                ticket: first.ticket.id,
+                tag: bug.id
+            })
+        );
+        app.handle_key(key(KeyCode::Down));
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char(' '))),
+            Some(Action::AddTag {
+                ticket: first.ticket.id,
+                tag: docs.id
+            })
+        );
+        app.handle_key(key(KeyCode::Esc));
+
+        app.handle_key(key(KeyCode::Char('d')));
+        assert_eq!(app.handle_key(key(KeyCode::Char('n'))), None);
+        app.handle_key(key(KeyCode::Char('d')));
+        assert_eq!(
+            app.handle_key(key(KeyCode::Char('y'))),
+            Some(Action::Delete(first.ticket.id))
+        );
+
+        app.handle_key(key(KeyCode::Char('n')));
+        assert_eq!(app.handle_key(key(KeyCode::Enter)), None);
+        for c in "q ".chars() {
+            app.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(
+            app.handle_key(key(KeyCode::Enter)),
+            Some(Action::Create("q".to_string()))
+        );
+        assert_eq!(app.handle_key(key(KeyCode::Char('q'))), Some(Action::Quit));
+    }
+}
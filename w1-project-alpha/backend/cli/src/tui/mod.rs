@@ -0,0 +1,159 @@
+//! `alpha tui`: a full-screen view of the tickets, the counterpart of the
+//! web `TicketsPage`, kept current by the server's change events.
+
+use crossterm::event::{Event, EventStream, KeyEventKind};
+use futures_util::stream::{BoxStream, StreamExt};
+use ratatui::DefaultTerminal;
+use std::error::Error;
+use std::time::Duration;
+use tokio::time::{sleep_until, Instant};
+
+use project_alpha_client::models::{CreateTicketRequest, ServerMessage};
+use project_alpha_client::{Client, Result as ClientResult};
+
+mod app;
+mod ui;
+
+use app::{Action, App};
+
+/// Wait this long after a change event for more before reloading, so a
+/// burst such as an import causes one reload.
+const RELOAD_DELAY: Duration = Duration::from_millis(150);
+/// Wait between attempts to reopen a lost event socket.
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+type Events = BoxStream<'static, ClientResult<ServerMessage>>;
+
+pub async fn run(client: &Client, user: &str) -> Result<(), Box<dyn Error>> {
+    // 先加载一次，连不上服务端时直接报错而不进入全屏
+    let mut app = App::default();
+    reload(client, &mut app).await?;
+    let events = client.subscribe(user).await.ok();
+    app.live = events.is_some();
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, client, user, app, events).await;
+    ratatui::restore();
+    result
+}
+
+async fn event_loop(
+    terminal: &mut DefaultTerminal,
+    client: &Client,
+    user: &str,
+    mut app: App,
+    mut events: Option<Events>,
+) -> Result<(), Box<dyn Error>> {
+    let mut keys = EventStream::new();
+    let mut reload_at: Option<Instant> = None;
+    let mut reconnect_at = Instant::now() + RECONNECT_DELAY;
+
+    loop {
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+
+        tokio::select! {
+            event = keys.next() => match event {
+                Some(Ok(Event::Key(key))) if key.kind == KeyEventKind::Press => {
+                    match app.handle_key(key) {
+                        Some(Action::Quit) => return Ok(()),
+                        Some(action) => perform(client, &mut app, action).await,
+                        None => {}
+                    }
+                }
+                Some(Ok(_)) => {}
+                Some(Err(err)) => return Err(err.into()),
+                None => return Ok(()),
+            },
+            message = next_event(&mut events) => match message {
+                Some(Ok(ServerMessage::Change(_))) => {
+                    reload_at.get_or_insert_with(|| Instant::now() + RELOAD_DELAY);
+                }
+                Some(Ok(_)) => {}
+                Some(Err(_)) | None => {
+                    events = None;
+                    app.live = false;
+                    reconnect_at = Instant::now() + RECONNECT_DELAY;
+                }
+            },
+            _ = sleep_until(reconnect_at), if events.is_none() => {
+                match client.subscribe(user).await {
+                    Ok(stream) => {
+                        events = Some(stream);
+                        app.live = true;
+                        // 断开期间的变更不会补发
+                        reload_at = Some(Instant::now());
+                    }
+                    Err(_) => reconnect_at = Instant::now() + RECONNECT_DELAY,
+                }
+            }
+            _ = sleep_until(reload_at.unwrap_or_else(Instant::now)), if reload_at.is_some() => {
+                reload_at = None;
+                if let Err(err) = reload(client, &mut app).await {
+                    app.error(err.to_string());
+                }
+            }
+        }
+    }
+}
+
+async fn next_event(events: &mut Option<Events>) -> Option<ClientResult<ServerMessage>> {
+    match events {
+        Some(events) => events.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Carry out an action against the server and reload, reporting the
+/// outcome in the footer.
+async fn perform(client: &Client, app: &mut App, action: Action) {
+    let result = match action {
+        Action::Quit | Action::Reload => Ok(None),
+        Action::Toggle(id) => client.toggle_ticket_completed(id).await.map(|ticket| {
+            Some(if ticket.completed {
+                "Marked done"
+            } else {
+                "Reopened"
+            })
+        }),
+        Action::Create(title) => client
+            .create_ticket(&CreateTicketRequest {
+                title,
+                description: None,
+                tag_ids: None,
+            })
+            .await
+            .map(|_| Some("Ticket created")),
+        Action::Delete(id) => client
+            .delete_ticket(id)
+            .await
+            .map(|_| Some("Ticket deleted")),
+        Action::AddTag { ticket, tag } => client
+            .add_tag_to_ticket(ticket, tag)
+            .await
+            .map(|_| Some("Tag added")),
+        Action::RemoveTag { ticket, tag } => client
+            .remove_tag_from_ticket(ticket, tag)
+            .await
+            .map(|_| Some("Tag removed")),
+    };
+    match result {
+        Ok(message) => match reload(client, app).await {
+            Ok(()) => {
+                if let Some(message) = message {
+                    app.info(message);
+                }
+            }
+            Err(err) => app.error(err.to_string()),
+        },
+        Err(err) => app.error(err.to_string()),
+    }
+}
+
+/// Fetch the tags and the tickets matching the filters.
+async fn reload(client: &Client, app: &mut App) -> ClientResult<()> {
+    if !app.set_tags(client.list_tags().await?) {
+        app.info("The tag filter was removed because the tag was deleted");
+    }
+    app.set_tickets(client.list_tickets(&app.query()).await?);
+    Ok(())
+}
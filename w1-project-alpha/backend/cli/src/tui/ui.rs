@@ -0,0 +1,417 @@
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Clear, List, ListItem, ListState, Paragraph, Wrap},
+    Frame,
+};
+
+use project_alpha_client::models::{Tag, TicketWithTags};
+
+use super::app::{App, Focus, Mode};
+
+const SIDEBAR_WIDTH: u16 = 24;
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Tag sidebar on the left; filters, the ticket list and the selected
+/// ticket on the right; key hints or the last status in the footer.
+pub fn draw(frame: &mut Frame, app: &App) {
+    let [body, footer] =
+        Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+    let [sidebar, main] =
+        Layout::horizontal([Constraint::Length(SIDEBAR_WIDTH), Constraint::Min(0)]).areas(body);
+    let [filters, content] =
+        Layout::vertical([Constraint::Length(3), Constraint::Min(0)]).areas(main);
+    let [list, detail] =
+        Layout::horizontal([Constraint::Percentage(55), Constraint::Percentage(45)]).areas(content);
+
+    draw_tags(frame, app, sidebar);
+    draw_filters(frame, app, filters);
+    draw_tickets(frame, app, list);
+    draw_detail(frame, app, detail);
+    draw_footer(frame, app, footer);
+
+    match &app.mode {
+        Mode::NewTicket { title } => {
+            let area = popup(frame.area(), 60, 3);
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(format!("{}▏", title))
+                    .block(Block::bordered().title(" New ticket ")),
+                area,
+            );
+        }
+        Mode::EditTags { cursor } => draw_tag_editor(frame, app, *cursor),
+        Mode::ConfirmDelete => {
+            let title = app
+                .selected_ticket()
+                .map(|ticket| ticket.ticket.title.as_str())
+                .unwrap_or_default();
+            let area = popup(frame.area(), 60, 4);
+            frame.render_widget(Clear, area);
+            frame.render_widget(
+                Paragraph::new(vec![
+                    Line::from(title.to_string()),
+                    Line::from("y: delete, any other key: keep"),
+                ])
+                .block(Block::bordered().title(" Delete ticket? ").red()),
+                area,
+            );
+        }
+        Mode::Help => draw_help(frame),
+        Mode::Normal | Mode::Search { .. } => {}
+    }
+}
+
+fn draw_tags(frame: &mut Frame, app: &App, area: Rect) {
+    let mut items = vec![ListItem::new(filter_marker(
+        app.tag_filter.is_none(),
+        "All tags",
+    ))];
+    items.extend(app.tags.iter().map(|tag| {
+        let selected = app.tag_filter == Some(tag.id);
+        ListItem::new(Line::from(vec![
+            Span::raw(if selected { "● " } else { "  " }),
+            Span::styled(tag.name.clone(), Style::new().fg(tag_color(tag))),
+        ]))
+    }));
+    let list = List::new(items)
+        .block(pane(" Tags ", app.focus == Focus::Tags))
+        .highlight_style(highlight(app.focus == Focus::Tags));
+    let mut state = ListState::default().with_selected(Some(app.tag_cursor));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_filters(frame: &mut Frame, app: &App, area: Rect) {
+    let searching = matches!(app.mode, Mode::Search { .. });
+    let search = if searching {
+        Span::raw(format!("{}▏", app.search)).yellow()
+    } else if app.search.is_empty() {
+        Span::raw("-").dark_gray()
+    } else {
+        Span::raw(app.search.clone())
+    };
+    let status = |label: &'static str, value: Option<bool>| {
+        if app.completed == value {
+            Span::raw(format!("[{}]", label)).bold()
+        } else {
+            Span::raw(format!(" {} ", label)).dark_gray()
+        }
+    };
+    let tag = app
+        .tag_filter
+        .and_then(|id| app.tags.iter().find(|tag| tag.id == id))
+        .map(|tag| Span::styled(tag.name.clone(), Style::new().fg(tag_color(tag))))
+        .unwrap_or_else(|| Span::raw("all").dark_gray());
+
+    let line = Line::from(vec![
+        Span::raw("Search: "),
+        search,
+        Span::raw("   Status: "),
+        status("all", None),
+        status("open", Some(false)),
+        status("done", Some(true)),
+        Span::raw("   Tag: "),
+        tag,
+    ]);
+    frame.render_widget(
+        Paragraph::new(line).block(pane(" Filters ", searching)),
+        area,
+    );
+}
+
+fn draw_tickets(frame: &mut Frame, app: &App, area: Rect) {
+    let title = format!(" Tickets ({}) ", app.tickets.len());
+    let focused = app.focus == Focus::Tickets;
+    if app.tickets.is_empty() {
+        frame.render_widget(
+            Paragraph::new("No tickets. Press n to create one.")
+                .dark_gray()
+                .block(pane(&title, focused)),
+            area,
+        );
+        return;
+    }
+
+    let items =
+        app.tickets.iter().map(|ticket| {
+            let mut spans = vec![Span::raw(if ticket.ticket.completed {
+                "[x] "
+            } else {
+                "[ ] "
+            })];
+            let title = Span::raw(ticket.ticket.title.clone());
+            spans.push(if ticket.ticket.completed {
+                title.dark_gray().crossed_out()
+            } else {
+                title
+            });
+            spans.extend(ticket.tags.iter().map(|tag| {
+                Span::styled(format!(" #{}", tag.name), Style::new().fg(tag_color(tag)))
+            }));
+            ListItem::new(Line::from(spans))
+        });
+    let list = List::new(items)
+        .block(pane(&title, focused))
+        .highlight_style(highlight(focused));
+    let mut state = ListState::default().with_selected(Some(app.selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_detail(frame: &mut Frame, app: &App, area: Rect) {
+    let block = pane(" Details ", false);
+    let Some(ticket) = app.selected_ticket() else {
+        frame.render_widget(block, area);
+        return;
+    };
+    frame.render_widget(
+        Paragraph::new(detail_lines(ticket))
+            .wrap(Wrap { trim: false })
+            .block(block),
+        area,
+    );
+}
+
+fn detail_lines(ticket: &TicketWithTags) -> Vec<Line<'static>> {
+    let field = |name: &'static str, value: String| {
+        Line::from(vec![Span::raw(name).dark_gray(), Span::raw(value)])
+    };
+    let mut tags = vec![Span::raw("Tags     ").dark_gray()];
+    if ticket.tags.is_empty() {
+        tags.push(Span::raw("-"));
+    }
+    for tag in &ticket.tags {
+        tags.push(Span::styled(
+            format!("{} ", tag.name),
+            Style::new().fg(tag_color(tag)),
+        ));
+    }
+
+    let mut lines = vec![
+        Line::from(ticket.ticket.title.clone()).bold(),
+        Line::default(),
+        field("Id       ", ticket.ticket.id.to_string()),
+        field(
+            "Status   ",
+            if ticket.ticket.completed {
+                "done"
+            } else {
+                "open"
+            }
+            .to_string(),
+        ),
+        Line::from(tags),
+        field(
+            "Created  ",
+            ticket.ticket.created_at.format(DATE_FORMAT).to_string(),
+        ),
+        field(
+            "Updated  ",
+            ticket.ticket.updated_at.format(DATE_FORMAT).to_string(),
+        ),
+        Line::default(),
+    ];
+    match &ticket.ticket.description {
+        Some(description) => {
+            lines.extend(description.lines().map(|line| Line::from(line.to_string())))
+        }
+        None => lines.push(Line::from("No description").dark_gray()),
+    }
+    lines
+}
+
+fn draw_footer(frame: &mut Frame, app: &App, area: Rect) {
+    let [message, live] =
+        Layout::horizontal([Constraint::Min(0), Constraint::Length(10)]).areas(area);
+
+    let text = match (&app.status, &app.mode) {
+        (Some(status), _) if status.error => Line::from(status.text.clone()).red(),
+        (Some(status), _) => Line::from(status.text.clone()).green(),
+        (None, Mode::Search { .. }) => Line::from("enter: apply  esc: cancel").dark_gray(),
+        (None, Mode::NewTicket { .. }) => Line::from("enter: create  esc: cancel").dark_gray(),
+        (None, Mode::EditTags { .. }) => {
+            Line::from("space: add/remove  j/k: move  esc: done").dark_gray()
+        }
+        (None, _) => Line::from(
+            "space: toggle  t: tags  /: search  f: status  n: new  d: delete  ?: help  q: quit",
+        )
+        .dark_gray(),
+    };
+    frame.render_widget(Paragraph::new(text), message);
+
+    let indicator = if app.live {
+        Span::raw("● live").green()
+    } else {
+        Span::raw("○ offline").red()
+    };
+    frame.render_widget(Paragraph::new(indicator).right_aligned(), live);
+}
+
+fn draw_tag_editor(frame: &mut Frame, app: &App, cursor: usize) {
+    let Some(ticket) = app.selected_ticket() else {
+        return;
+    };
+    let items = app.tags.iter().map(|tag| {
+        let on = ticket.tags.iter().any(|t| t.id == tag.id);
+        ListItem::new(Line::from(vec![
+            Span::raw(if on { "[x] " } else { "[ ] " }),
+            Span::styled(tag.name.clone(), Style::new().fg(tag_color(tag))),
+        ]))
+    });
+    let height = (app.tags.len() as u16)
+        .saturating_add(2)
+        .min(frame.area().height);
+    let area = popup(frame.area(), 40, height);
+    frame.render_widget(Clear, area);
+    let list = List::new(items)
+        .block(Block::bordered().title(format!(" Tags of {} ", ticket.ticket.title)))
+        .highlight_style(highlight(true));
+    let mut state = ListState::default().with_selected(Some(cursor));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_help(frame: &mut Frame) {
+    const KEYS: [(&str, &str); 15] = [
+        ("tab h l", "switch between tags and tickets"),
+        ("j k ↑ ↓", "move"),
+        ("g G", "first, last"),
+        ("enter", "filter by the tag under the cursor"),
+        ("/", "search titles"),
+        ("f", "cycle status: all, open, done"),
+        ("c", "clear all filters"),
+        ("space x", "toggle completed"),
+        ("t", "add or remove tags"),
+        ("n", "new ticket"),
+        ("d", "delete ticket"),
+        ("r", "reload"),
+        ("?", "this help"),
+        ("q ctrl-c", "quit"),
+        ("", "changes by others show up live"),
+    ];
+    let lines: Vec<Line> = KEYS
+        .iter()
+        .map(|(keys, what)| {
+            Line::from(vec![
+                Span::raw(format!("{:<10}", keys)).bold(),
+                Span::raw(*what),
+            ])
+        })
+        .collect();
+    let area = popup(frame.area(), 52, lines.len() as u16 + 2);
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(" Keys ")),
+        area,
+    );
+}
+
+fn pane(title: &str, focused: bool) -> Block<'static> {
+    let block = Block::bordered().title(title.to_string());
+    if focused {
+        block.border_style(Style::new().cyan())
+    } else {
+        block
+    }
+}
+
+fn highlight(focused: bool) -> Style {
+    if focused {
+        Style::new().add_modifier(Modifier::REVERSED)
+    } else {
+        Style::new().add_modifier(Modifier::BOLD)
+    }
+}
+
+fn filter_marker(active: bool, label: &str) -> String {
+    format!("{}{}", if active { "● " } else { "  " }, label)
+}
+
+/// The tag's `#RRGGBB` or `#RGB` color, or the terminal's default.
+fn tag_color(tag: &Tag) -> Color {
+    let Some(hex) = tag
+        .color
+        .as_deref()
+        .and_then(|color| color.strip_prefix('#'))
+    else {
+        return Color::Reset;
+    };
+    let channel = |digits: &str| u8::from_str_radix(digits, 16).ok();
+    let rgb = match hex.len() {
+        6 => (
+            channel(&hex[0..2]),
+            channel(&hex[2..4]),
+            channel(&hex[4..6]),
+        ),
+        3 => (
+            channel(&hex[0..1]).map(|v| v * 17),
+            channel(&hex[1..2]).map(|v| v * 17),
+            channel(&hex[2..3]).map(|v| v * 17),
+        ),
+        _ => return Color::Reset,
+    };
+    match rgb {
+        (Some(r), Some(g), Some(b)) => Color::Rgb(r, g, b),
+        _ => Color::Reset,
+    }
+}
+
+/// A `width` × `height` area in the middle of `area`, shrunk to fit.
+fn popup(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + (area.height - height) / 2,
+        width,
+        height,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use project_alpha_client::models::Ticket;
+    use ratatui::{backend::TestBackend, Terminal};
+    use uuid::Uuid;
+
+    #[test]
+    fn renders_list_detail_and_tag_colors() {
+        let bug = Tag {
+            id: Uuid::new_v4(),
+            name: "bug".to_string(),
+            color: Some("#f00".to_string()),
+            created_at: Utc::now(),
+        };
+        assert_eq!(tag_color(&bug), Color::Rgb(255, 0, 0));
+
+        let mut app = App::default();
+        app.set_tags(vec![bug.clone()]);
+        app.set_tickets(vec![TicketWithTags {
+            ticket: Ticket {
+                id: Uuid::new_v4(),
+                title: "Crash on save".to_string(),
+                description: Some("Steps to reproduce".to_string()),
+                completed: false,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            },
+            tags: vec![bug],
+        }]);
+        app.live = true;
+
+        let mut terminal = Terminal::new(TestBackend::new(120, 20)).unwrap();
+        terminal.draw(|frame| draw(frame, &app)).unwrap();
+        let screen: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(screen.contains("[ ] Crash on save #bug"));
+        assert!(screen.contains("Steps to reproduce"));
+        assert!(screen.contains("Tickets (1)"));
+        assert!(screen.contains("● live"));
+    }
+}
@@ -0,0 +1,217 @@
+//! 命令行测试：对使用内存 SQLite 的真实服务端运行编译好的 alpha 二进制
+use axum::{body::Body, extract::Request, http::Method, middleware::Next};
+use project_alpha_backend::config::{Config, DatabaseConfig};
+use project_alpha_backend::{create_app, AppState, Database};
+use serde_json::Value;
+use std::process::Output;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio::process::Command;
+
+/// 启动使用独立内存数据库的服务端，返回其地址
+async fn start_server() -> String {
+    start_counting_server().await.0
+}
+
+/// 同 start_server，另外统计获取工单列表的请求数
+async fn start_counting_server() -> (String, Arc<AtomicUsize>) {
+    let database = Database::connect(&DatabaseConfig::new("sqlite::memory:"))
+        .await
+        .expect("Failed to open sqlite database");
+    database.migrate().await.expect("Failed to run migrations");
+    let listings = Arc::new(AtomicUsize::new(0));
+    let counter = listings.clone();
+    let app = create_app(AppState::new(database), &Config::default()).layer(
+        axum::middleware::from_fn(move |request: Request<Body>, next: Next| {
+            let counter = counter.clone();
+            async move {
+                if request.method() == Method::GET && request.uri().path() == "/api/v1/tickets" {
+                    counter.fetch_add(1, Ordering::SeqCst);
+                }
+                next.run(request).await
+            }
+        }),
+    );
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{}", addr), listings)
+}
+
+/// 测试结束后自动删除的临时目录
+struct TempDir(std::path::PathBuf);
+
+impl TempDir {
+    fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("alpha-cli-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// 在不读取开发者本地配置的环境中运行 alpha
+async fn alpha(home: &TempDir, server: Option<&str>, args: &[&str]) -> Output {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_alpha"));
+    command
+        .args(args)
+        .env_clear()
+        .env("HOME", &home.0)
+        .env("PATH", std::env::var_os("PATH").unwrap_or_default());
+    if let Some(server) = server {
+        command.env("ALPHA_SERVER", server);
+    }
+    command.output().await.expect("Failed to run alpha")
+}
+
+/// 运行 alpha 并断言成功，返回标准输出
+async fn ok(home: &TempDir, server: &str, args: &[&str]) -> String {
+    let output = alpha(home, Some(server), args).await;
+    assert!(output.status.success(), "alpha {:?}: {:?}", args, output);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+async fn json(home: &TempDir, server: &str, args: &[&str]) -> Value {
+    let mut args = args.to_vec();
+    args.extend(["--output", "json"]);
+    serde_json::from_str(&ok(home, server, &args).await).unwrap()
+}
+
+#[tokio::test]
+async fn test_ticket_workflow() {
+    let server = start_server().await;
+    let home = TempDir::new();
+
+    ok(&home, &server, &["tag", "new", "bug", "--color", "#f00"]).await;
+    ok(&home, &server, &["tag", "new", "docs"]).await;
+    let tags = ok(&home, &server, &["tag", "ls"]).await;
+    assert!(tags.starts_with("ID        NAME  COLOR\n"), "{}", tags);
+    assert!(tags.contains("bug   #f00"));
+
+    let created = json(
+        &home,
+        &server,
+        &["ticket", "new", "Crash on save", "-d", "Steps", "-t", "bug"],
+    )
+    .await;
+    assert_eq!(created["title"], "Crash on save");
+    assert_eq!(created["tags"][0]["name"], "bug");
+    let id = created["id"].as_str().unwrap().to_string();
+    ok(
+        &home,
+        &server,
+        &["ticket", "new", "Write guide", "--tag", "docs"],
+    )
+    .await;
+
+    // 按标签与状态过滤
+    let listed = json(&home, &server, &["ticket", "ls", "--tag", "bug", "--open"]).await;
+    assert_eq!(listed.as_array().unwrap().len(), 1);
+    assert_eq!(listed[0]["id"], id.as_str());
+
+    // 用 ID 前缀完成工单
+    let done = ok(&home, &server, &["ticket", "done", &id[..8]]).await;
+    assert!(done.contains("done    Crash on save"), "{}", done);
+    assert!(
+        json(&home, &server, &["ticket", "ls", "--tag", "bug", "--open"])
+            .await
+            .as_array()
+            .unwrap()
+            .is_empty()
+    );
+    let finished = json(&home, &server, &["ticket", "ls", "--done"]).await;
+    assert_eq!(finished[0]["id"], id.as_str());
+
+    let csv = ok(&home, &server, &["ticket", "ls", "-o", "csv"]).await;
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next(),
+        Some("id,title,description,completed,created_at,updated_at,tags")
+    );
+    assert_eq!(lines.count(), 2);
+
+    let shown = ok(&home, &server, &["ticket", "show", &id[..8]]).await;
+    assert!(shown.ends_with("\nSteps\n"), "{}", shown);
+
+    ok(&home, &server, &["ticket", "rm", &id]).await;
+    let table = ok(&home, &server, &["ticket", "ls"]).await;
+    assert!(table.starts_with("ID        STATUS  TITLE        TAGS  UPDATED\n"));
+    assert!(!table.contains("Crash on save"));
+}
+
+#[tokio::test]
+async fn test_errors_and_config_file() {
+    let server = start_server().await;
+    let home = TempDir::new();
+
+    let output = alpha(&home, Some(&server), &["ticket", "done", "ffff"]).await;
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: No ticket with id ffff\n"
+    );
+
+    let output = alpha(&home, Some(&server), &["ticket", "new", " "]).await;
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("title must not be empty"), "{}", stderr);
+
+    let output = alpha(&home, Some(&server), &["ticket", "ls", "--tag", "nope"]).await;
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "error: No tag named 'nope'\n"
+    );
+
+    // 未设置 ALPHA_SERVER 时读取 ~/.config/alpha/config.toml
+    let dir = home.0.join(".config").join("alpha");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("config.toml"),
+        format!("server = \"{}\"\noutput = \"json\"\n", server),
+    )
+    .unwrap();
+    let output = alpha(&home, None, &["tag", "ls"]).await;
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "[]\n");
+
+    std::fs::write(dir.join("config.toml"), "sever = \"typo\"\n").unwrap();
+    let output = alpha(&home, None, &["tag", "ls"]).await;
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("unknown field `sever`"));
+}
+
+#[tokio::test]
+async fn test_id_prefixes_share_one_ticket_listing() {
+    let (server, listings) = start_counting_server().await;
+    let home = TempDir::new();
+
+    let mut ids = Vec::new();
+    for title in ["First", "Second", "Third"] {
+        let created = json(&home, &server, &["ticket", "new", title]).await;
+        ids.push(created["id"].as_str().unwrap().to_string());
+    }
+
+    // 多个前缀只获取一次工单列表，完整的 UUID 不需要列表
+    let before = listings.load(Ordering::SeqCst);
+    let done = json(
+        &home,
+        &server,
+        &["ticket", "done", &ids[0][..8], &ids[1][..8], &ids[2]],
+    )
+    .await;
+    assert_eq!(done.as_array().unwrap().len(), 3);
+    assert_eq!(listings.load(Ordering::SeqCst) - before, 1);
+
+    let before = listings.load(Ordering::SeqCst);
+    ok(&home, &server, &["ticket", "reopen", &ids[0], &ids[1]]).await;
+    assert_eq!(listings.load(Ordering::SeqCst), before);
+}
@@ -0,0 +1,111 @@
+use reqwest::{Response, StatusCode};
+use thiserror::Error;
+use tokio_tungstenite::tungstenite;
+
+use project_alpha_models::ErrorResponse;
+
+/// Why a request failed. Error responses from the server are decoded into
+/// the variant matching the server's own error, with its message and the
+/// request ID to quote when reporting it.
+#[derive(Error, Debug)]
+pub enum ClientError {
+    #[error("Not found: {message}")]
+    NotFound {
+        message: String,
+        request_id: Option<String>,
+    },
+
+    #[error("Validation error: {message}")]
+    Validation {
+        message: String,
+        request_id: Option<String>,
+    },
+
+    /// Any other error status, including server errors.
+    #[error("Server responded {status}: {message}")]
+    Server {
+        status: StatusCode,
+        message: String,
+        request_id: Option<String>,
+    },
+
+    /// The server could not be reached, or its response could not be read.
+    #[error("Request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The collaboration socket could not be opened or failed while open.
+    #[error("WebSocket failed: {0}")]
+    WebSocket(Box<tungstenite::Error>),
+
+    #[error("Invalid server URL '{0}'")]
+    InvalidUrl(String),
+}
+
+impl From<tungstenite::Error> for ClientError {
+    fn from(err: tungstenite::Error) -> Self {
+        ClientError::WebSocket(Box::new(err))
+    }
+}
+
+impl ClientError {
+    /// Decode an error response. Bodies that are not an [`ErrorResponse`],
+    /// such as those of proxies, keep their text as the message.
+    pub(crate) async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        let (message, request_id) = match serde_json::from_str::<ErrorResponse>(&body) {
+            Ok(error) => (error.error, error.request_id),
+            Err(_) if body.trim().is_empty() => (
+                status.canonical_reason().unwrap_or("no body").to_string(),
+                None,
+            ),
+            Err(_) => (body, None),
+        };
+        match status {
+            StatusCode::NOT_FOUND => ClientError::NotFound {
+                message,
+                request_id,
+            },
+            StatusCode::BAD_REQUEST => ClientError::Validation {
+                message,
+                request_id,
+            },
+            status => ClientError::Server {
+                status,
+                message,
+                request_id,
+            },
+        }
+    }
+
+    /// The HTTP status of an error response.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ClientError::NotFound { .. } => Some(StatusCode::NOT_FOUND),
+            ClientError::Validation { .. } => Some(StatusCode::BAD_REQUEST),
+            ClientError::Server { status, .. } => Some(*status),
+            ClientError::Http(err) => err.status(),
+            ClientError::WebSocket(err) => match err.as_ref() {
+                tungstenite::Error::Http(response) => Some(response.status()),
+                _ => None,
+            },
+            ClientError::InvalidUrl(_) => None,
+        }
+    }
+
+    /// The `X-Request-Id` the server logged the failed request under.
+    pub fn request_id(&self) -> Option<&str> {
+        match self {
+            ClientError::NotFound { request_id, .. }
+            | ClientError::Validation { request_id, .. }
+            | ClientError::Server { request_id, .. } => request_id.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ClientError::NotFound { .. })
+    }
+}
+
+pub type Result<T> = std::result::Result<T, ClientError>;
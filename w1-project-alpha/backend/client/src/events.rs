@@ -0,0 +1,35 @@
+use futures_util::stream::{BoxStream, StreamExt};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::models::ServerMessage;
+use crate::{Client, ClientError, Result};
+
+/// The collaboration socket under `/api/v1/ws`.
+impl Client {
+    /// Open the socket as `user` and stream what the server pushes: every
+    /// change event, plus presence and typing hints for all tickets.
+    ///
+    /// The stream ends when the server closes the socket, e.g. on shutdown;
+    /// events published while disconnected are not replayed, so reload
+    /// after reconnecting. Messages this client does not know are skipped.
+    pub async fn subscribe(&self, user: &str) -> Result<BoxStream<'static, Result<ServerMessage>>> {
+        let mut url = reqwest::Url::parse(&format!("{}/api/v1/ws", self.base_url()))
+            .map_err(|_| ClientError::InvalidUrl(self.base_url().to_string()))?;
+        let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+        url.set_scheme(scheme)
+            .map_err(|_| ClientError::InvalidUrl(self.base_url().to_string()))?;
+        url.query_pairs_mut().append_pair("user", user);
+
+        let (socket, _) = connect_async(url.as_str()).await?;
+        let messages = socket
+            .take_while(|message| std::future::ready(!matches!(message, Ok(Message::Close(_)))))
+            .filter_map(|message| {
+                std::future::ready(match message {
+                    Ok(Message::Text(text)) => serde_json::from_str(&text).ok().map(Ok),
+                    Ok(_) => None,
+                    Err(err) => Some(Err(err.into())),
+                })
+            });
+        Ok(messages.boxed())
+    }
+}
@@ -0,0 +1,212 @@
+//! Async client for the Project Alpha REST API.
+//!
+//! Requests and responses use the model types the server is built with,
+//! from the shared `project-alpha-models` crate and re-exported in
+//! [`models`], so a field added on the server is picked up here at compile
+//! time.
+//!
+//! ```no_run
+//! # async fn run() -> project_alpha_client::Result<()> {
+//! use project_alpha_client::{models::TicketFilter, Client};
+//!
+//! let client = Client::new("http://localhost:3000")?;
+//! let open = TicketFilter {
+//!     completed: Some(false),
+//!     ..Default::default()
+//! };
+//! for ticket in client.list_tickets(&open).await? {
+//!     println!("{}", ticket.ticket.title);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use reqwest::{Method, RequestBuilder, Response, StatusCode};
+use serde::de::DeserializeOwned;
+use std::time::Duration;
+
+pub mod error;
+pub mod events;
+pub mod probes;
+pub mod tags;
+pub mod tickets;
+pub mod v2;
+pub mod webhooks;
+
+pub use error::{ClientError, Result};
+pub use v2::V2;
+
+/// The request and response types of the API.
+pub use project_alpha_models as models;
+
+/// Timeouts and the retry policy of a [`Client`].
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Limit on a whole request, from connecting to reading the body.
+    pub timeout: Duration,
+    /// Extra attempts for idempotent requests (`GET`, `PUT`, `DELETE`) that
+    /// failed to connect, timed out or got a 429, 502, 503 or 504.
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled on each further attempt.
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(30),
+            max_retries: 3,
+            base_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A client for one server. Cloning is cheap and shares the connection pool.
+#[derive(Debug, Clone)]
+pub struct Client {
+    http: reqwest::Client,
+    base_url: String,
+    config: ClientConfig,
+}
+
+impl Client {
+    /// A client for the server at `base_url`, e.g. `http://localhost:3000`.
+    pub fn new(base_url: impl Into<String>) -> Result<Self> {
+        Self::with_config(base_url, ClientConfig::default())
+    }
+
+    pub fn with_config(base_url: impl Into<String>, config: ClientConfig) -> Result<Self> {
+        let base_url = base_url.into().trim_end_matches('/').to_string();
+        let valid = reqwest::Url::parse(&base_url)
+            .is_ok_and(|url| ["http", "https"].contains(&url.scheme()));
+        if !valid {
+            return Err(ClientError::InvalidUrl(base_url));
+        }
+        let http = reqwest::Client::builder().timeout(config.timeout).build()?;
+        Ok(Self {
+            http,
+            base_url,
+            config,
+        })
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Send a request to `path` on the server, built by `build` for every
+    /// attempt, and return the response if its status is a success.
+    ///
+    /// Idempotent methods are retried according to the [`ClientConfig`]; the
+    /// others are sent once, as a retry could apply them twice.
+    pub(crate) async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        build: impl Fn(RequestBuilder) -> RequestBuilder,
+    ) -> Result<Response> {
+        let url = format!("{}{}", self.base_url, path);
+        let retries = if is_idempotent(&method) {
+            self.config.max_retries
+        } else {
+            0
+        };
+
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let result = build(self.http.request(method.clone(), &url)).send().await;
+            let retryable = match &result {
+                Ok(response) => is_retryable_status(response.status()),
+                Err(err) => err.is_connect() || err.is_timeout(),
+            };
+            if !retryable || attempt > retries {
+                return match result {
+                    Ok(response) if response.status().is_success() => Ok(response),
+                    Ok(response) => Err(ClientError::from_response(response).await),
+                    Err(err) => Err(err.into()),
+                };
+            }
+            let delay = backoff_delay(attempt, self.config.base_backoff, self.config.max_backoff);
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    pub(crate) async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let response = self.send(Method::GET, path, |request| request).await?;
+        Ok(response.json().await?)
+    }
+
+    /// A request whose success response has no body worth reading.
+    pub(crate) async fn send_empty(&self, method: Method, path: &str) -> Result<()> {
+        self.send(method, path, |request| request).await?;
+        Ok(())
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    [
+        Method::GET,
+        Method::HEAD,
+        Method::PUT,
+        Method::DELETE,
+        Method::OPTIONS,
+    ]
+    .contains(method)
+}
+
+/// Delay before the next attempt, after `attempts` failed attempts.
+fn backoff_delay(attempts: u32, base: Duration, max: Duration) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(31);
+    base.saturating_mul(2u32.saturating_pow(exponent)).min(max)
+}
+
+/// Statuses that say "try again later" rather than "this request is wrong".
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_idempotent_methods_are_retried() {
+        assert!(is_idempotent(&Method::GET));
+        assert!(is_idempotent(&Method::PUT));
+        assert!(is_idempotent(&Method::DELETE));
+        assert!(!is_idempotent(&Method::POST));
+        assert!(!is_idempotent(&Method::PATCH));
+
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable_status(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let base = Duration::from_millis(200);
+        let max = Duration::from_secs(5);
+        assert_eq!(backoff_delay(1, base, max), base);
+        assert_eq!(backoff_delay(3, base, max), Duration::from_millis(800));
+        assert_eq!(backoff_delay(100, base, max), max);
+    }
+
+    #[test]
+    fn base_url_is_checked_and_normalized() {
+        let client = Client::new("http://localhost:3000/").unwrap();
+        assert_eq!(client.base_url(), "http://localhost:3000");
+        assert!(matches!(
+            Client::new("localhost:3000"),
+            Err(ClientError::InvalidUrl(_))
+        ));
+    }
+}
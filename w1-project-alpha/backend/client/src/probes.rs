@@ -0,0 +1,37 @@
+use reqwest::{Method, StatusCode};
+
+use crate::models::{HealthResponse, ReadinessResponse, VersionResponse};
+use crate::{Client, ClientError, Result};
+
+/// Liveness, readiness, build information and metrics.
+impl Client {
+    pub async fn health(&self) -> Result<HealthResponse> {
+        self.get_json("/healthz").await
+    }
+
+    /// The readiness report, also when the server is not ready: that is
+    /// reported in the body with a 503, which is not retried here.
+    pub async fn readiness(&self) -> Result<ReadinessResponse> {
+        let response = self
+            .http
+            .get(format!("{}/readyz", self.base_url))
+            .send()
+            .await?;
+        match response.status() {
+            StatusCode::OK | StatusCode::SERVICE_UNAVAILABLE => Ok(response.json().await?),
+            _ => Err(ClientError::from_response(response).await),
+        }
+    }
+
+    pub async fn version(&self) -> Result<VersionResponse> {
+        self.get_json("/version").await
+    }
+
+    /// The Prometheus metrics in text exposition format.
+    pub async fn metrics(&self) -> Result<String> {
+        let response = self
+            .send(Method::GET, "/metrics", |request| request)
+            .await?;
+        Ok(response.text().await?)
+    }
+}
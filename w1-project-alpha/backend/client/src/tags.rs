@@ -0,0 +1,41 @@
+use reqwest::Method;
+use uuid::Uuid;
+
+use crate::models::{CreateTagRequest, Tag, UpdateTagRequest};
+use crate::{Client, Result};
+
+/// Tags under `/api/v1`.
+impl Client {
+    /// Every tag, sorted by name.
+    pub async fn list_tags(&self) -> Result<Vec<Tag>> {
+        self.get_json("/api/v1/tags").await
+    }
+
+    pub async fn get_tag(&self, id: Uuid) -> Result<Tag> {
+        self.get_json(&format!("/api/v1/tags/{}", id)).await
+    }
+
+    pub async fn create_tag(&self, request: &CreateTagRequest) -> Result<Tag> {
+        let response = self
+            .send(Method::POST, "/api/v1/tags", |builder| {
+                builder.json(request)
+            })
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn update_tag(&self, id: Uuid, request: &UpdateTagRequest) -> Result<Tag> {
+        let response = self
+            .send(Method::PUT, &format!("/api/v1/tags/{}", id), |builder| {
+                builder.json(request)
+            })
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    /// Deletes the tag and unlinks it from every ticket.
+    pub async fn delete_tag(&self, id: Uuid) -> Result<()> {
+        self.send_empty(Method::DELETE, &format!("/api/v1/tags/{}", id))
+            .await
+    }
+}
@@ -0,0 +1,127 @@
+use bytes::Bytes;
+use futures_util::stream::{BoxStream, StreamExt};
+use reqwest::{header, Method};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::{
+    CreateTicketRequest, ExportFormat, ImportFormat, ImportReport, PresenceEntry, Ticket,
+    TicketFilter, TicketWithTags, UpdateTicketRequest,
+};
+use crate::{Client, Result};
+
+/// Tickets under `/api/v1`. See [`Client::v2`] for paginated listing.
+impl Client {
+    /// Every ticket matching `filter`, newest first.
+    pub async fn list_tickets(&self, filter: &TicketFilter) -> Result<Vec<TicketWithTags>> {
+        let response = self
+            .send(Method::GET, "/api/v1/tickets", |request| {
+                request.query(filter)
+            })
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn get_ticket(&self, id: Uuid) -> Result<TicketWithTags> {
+        self.get_json(&format!("/api/v1/tickets/{}", id)).await
+    }
+
+    pub async fn create_ticket(&self, request: &CreateTicketRequest) -> Result<Ticket> {
+        let response = self
+            .send(Method::POST, "/api/v1/tickets", |builder| {
+                builder.json(request)
+            })
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn update_ticket(&self, id: Uuid, request: &UpdateTicketRequest) -> Result<Ticket> {
+        let response = self
+            .send(Method::PUT, &format!("/api/v1/tickets/{}", id), |builder| {
+                builder.json(request)
+            })
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn delete_ticket(&self, id: Uuid) -> Result<()> {
+        self.send_empty(Method::DELETE, &format!("/api/v1/tickets/{}", id))
+            .await
+    }
+
+    pub async fn toggle_ticket_completed(&self, id: Uuid) -> Result<Ticket> {
+        let response = self
+            .send(
+                Method::PATCH,
+                &format!("/api/v1/tickets/{}/toggle", id),
+                |request| request,
+            )
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn add_tag_to_ticket(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()> {
+        self.send_empty(
+            Method::POST,
+            &format!("/api/v1/tickets/{}/tags/{}", ticket_id, tag_id),
+        )
+        .await
+    }
+
+    pub async fn remove_tag_from_ticket(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()> {
+        self.send_empty(
+            Method::DELETE,
+            &format!("/api/v1/tickets/{}/tags/{}", ticket_id, tag_id),
+        )
+        .await
+    }
+
+    /// Users currently viewing or editing the ticket.
+    pub async fn ticket_presence(&self, id: Uuid) -> Result<Vec<PresenceEntry>> {
+        self.get_json(&format!("/api/v1/tickets/{}/presence", id))
+            .await
+    }
+
+    /// Tickets matching `filter` in `format`, streamed as the server sends
+    /// them.
+    pub async fn export_tickets(
+        &self,
+        filter: &TicketFilter,
+        format: ExportFormat,
+    ) -> Result<BoxStream<'static, Result<Bytes>>> {
+        #[derive(Serialize)]
+        struct ExportQuery {
+            format: ExportFormat,
+        }
+
+        let response = self
+            .send(Method::GET, "/api/v1/tickets/export", |request| {
+                request.query(filter).query(&ExportQuery { format })
+            })
+            .await?;
+        Ok(response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(Into::into))
+            .boxed())
+    }
+
+    /// Import tickets from `body`; with `dry_run` the server only reports
+    /// what it would do.
+    pub async fn import_tickets(
+        &self,
+        format: ImportFormat,
+        body: impl Into<Bytes>,
+        dry_run: bool,
+    ) -> Result<ImportReport> {
+        let body = body.into();
+        let response = self
+            .send(Method::POST, "/api/v1/tickets/import", |request| {
+                request
+                    .query(&[("dry_run", dry_run)])
+                    .header(header::CONTENT_TYPE, format.content_type())
+                    .body(body.clone())
+            })
+            .await?;
+        Ok(response.json().await?)
+    }
+}
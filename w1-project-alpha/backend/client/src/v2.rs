@@ -0,0 +1,130 @@
+use futures_util::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use reqwest::Method;
+use uuid::Uuid;
+
+use crate::models::{
+    CreateTicketRequest, Page, PageParams, TicketFilter, TicketV2, UpdateTicketRequestV2,
+};
+use crate::{Client, Result};
+
+/// Tickets under `/api/v2`: a `status` instead of `completed`, tags always
+/// included and paginated listing.
+#[derive(Debug, Clone, Copy)]
+pub struct V2<'a> {
+    client: &'a Client,
+}
+
+impl Client {
+    pub fn v2(&self) -> V2<'_> {
+        V2 { client: self }
+    }
+}
+
+impl V2<'_> {
+    /// One page of the tickets matching `filter`, newest first.
+    pub async fn list_tickets(
+        &self,
+        filter: &TicketFilter,
+        params: PageParams,
+    ) -> Result<Page<TicketV2>> {
+        list_page(self.client, filter, params).await
+    }
+
+    /// Every page of the tickets matching `filter`, fetched as the stream is
+    /// polled. Pages are numbered, so tickets created while paging shift
+    /// later ones onto the next page and may be seen twice.
+    pub fn pages(
+        &self,
+        filter: TicketFilter,
+        per_page: u32,
+    ) -> BoxStream<'static, Result<Page<TicketV2>>> {
+        let client = self.client.clone();
+        stream::try_unfold(Some(1), move |next| {
+            let client = client.clone();
+            let filter = filter.clone();
+            async move {
+                let Some(page) = next else {
+                    return Ok(None);
+                };
+                let params = PageParams { page, per_page };
+                let items = list_page(&client, &filter, params).await?;
+                let next = (i64::from(page) < items.total_pages).then_some(page + 1);
+                Ok(Some((items, next)))
+            }
+        })
+        .boxed()
+    }
+
+    /// Every ticket matching `filter`, read a page at a time.
+    pub fn tickets(
+        &self,
+        filter: TicketFilter,
+        per_page: u32,
+    ) -> BoxStream<'static, Result<TicketV2>> {
+        self.pages(filter, per_page)
+            .map_ok(|page| stream::iter(page.items.into_iter().map(Ok)))
+            .try_flatten()
+            .boxed()
+    }
+
+    pub async fn get_ticket(&self, id: Uuid) -> Result<TicketV2> {
+        self.client
+            .get_json(&format!("/api/v2/tickets/{}", id))
+            .await
+    }
+
+    pub async fn create_ticket(&self, request: &CreateTicketRequest) -> Result<TicketV2> {
+        let response = self
+            .client
+            .send(Method::POST, "/api/v2/tickets", |builder| {
+                builder.json(request)
+            })
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn update_ticket(
+        &self,
+        id: Uuid,
+        request: &UpdateTicketRequestV2,
+    ) -> Result<TicketV2> {
+        let response = self
+            .client
+            .send(Method::PUT, &format!("/api/v2/tickets/{}", id), |builder| {
+                builder.json(request)
+            })
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn toggle_ticket_completed(&self, id: Uuid) -> Result<TicketV2> {
+        let response = self
+            .client
+            .send(
+                Method::PATCH,
+                &format!("/api/v2/tickets/{}/toggle", id),
+                |request| request,
+            )
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn delete_ticket(&self, id: Uuid) -> Result<()> {
+        self.client
+            .send_empty(Method::DELETE, &format!("/api/v2/tickets/{}", id))
+            .await
+    }
+}
+
+async fn list_page(
+    client: &Client,
+    filter: &TicketFilter,
+    params: PageParams,
+) -> Result<Page<TicketV2>> {
+    let response = client
+        .send(Method::GET, "/api/v2/tickets", |request| {
+            request.query(filter).query(&params)
+        })
+        .await?;
+    Ok(response.json().await?)
+}
@@ -0,0 +1,65 @@
+use reqwest::Method;
+use uuid::Uuid;
+
+use crate::models::{CreateWebhookRequest, UpdateWebhookRequest, Webhook, WebhookDelivery};
+use crate::{Client, Result};
+
+/// Webhook subscriptions under `/api/v1`.
+impl Client {
+    pub async fn list_webhooks(&self) -> Result<Vec<Webhook>> {
+        self.get_json("/api/v1/webhooks").await
+    }
+
+    pub async fn get_webhook(&self, id: Uuid) -> Result<Webhook> {
+        self.get_json(&format!("/api/v1/webhooks/{}", id)).await
+    }
+
+    pub async fn create_webhook(&self, request: &CreateWebhookRequest) -> Result<Webhook> {
+        let response = self
+            .send(Method::POST, "/api/v1/webhooks", |builder| {
+                builder.json(request)
+            })
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn update_webhook(
+        &self,
+        id: Uuid,
+        request: &UpdateWebhookRequest,
+    ) -> Result<Webhook> {
+        let response = self
+            .send(
+                Method::PUT,
+                &format!("/api/v1/webhooks/{}", id),
+                |builder| builder.json(request),
+            )
+            .await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn delete_webhook(&self, id: Uuid) -> Result<()> {
+        self.send_empty(Method::DELETE, &format!("/api/v1/webhooks/{}", id))
+            .await
+    }
+
+    /// Recent deliveries to the webhook, newest first; the server's default
+    /// of 50 when `limit` is `None`.
+    pub async fn webhook_deliveries(
+        &self,
+        id: Uuid,
+        limit: Option<i64>,
+    ) -> Result<Vec<WebhookDelivery>> {
+        let response = self
+            .send(
+                Method::GET,
+                &format!("/api/v1/webhooks/{}/deliveries", id),
+                |request| match limit {
+                    Some(limit) => request.query(&[("limit", limit)]),
+                    None => request,
+                },
+            )
+            .await?;
+        Ok(response.json().await?)
+    }
+}
@@ -0,0 +1,332 @@
+//! 客户端测试：对使用内存 SQLite 的真实服务端调用各个接口
+use futures_util::{StreamExt, TryStreamExt};
+use project_alpha_backend::config::{Config, DatabaseConfig};
+use project_alpha_backend::{create_app, AppState, Database};
+use project_alpha_client::models::{
+    ChangeEvent, CreateTagRequest, CreateTicketRequest, CreateWebhookRequest, ExportFormat,
+    ImportFormat, PageParams, ServerMessage, TicketFilter, TicketStatus, UpdateTagRequest,
+    UpdateTicketRequest, UpdateTicketRequestV2,
+};
+use project_alpha_client::{Client, ClientConfig, ClientError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+
+/// 启动使用独立内存数据库的服务端，返回其地址
+async fn start_server() -> String {
+    let database = Database::connect(&DatabaseConfig::new("sqlite::memory:"))
+        .await
+        .expect("Failed to open sqlite database");
+    database.migrate().await.expect("Failed to run migrations");
+    let app = create_app(AppState::new(database), &Config::default());
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{}", addr)
+}
+
+fn new_ticket(title: &str, tag_ids: Option<Vec<uuid::Uuid>>) -> CreateTicketRequest {
+    CreateTicketRequest {
+        title: title.to_string(),
+        description: None,
+        tag_ids,
+    }
+}
+
+#[tokio::test]
+async fn test_tickets_and_tags() {
+    let client = Client::new(start_server().await).unwrap();
+
+    let bug = client
+        .create_tag(&CreateTagRequest {
+            name: "bug".to_string(),
+            color: Some("#ff0000".to_string()),
+        })
+        .await
+        .unwrap();
+    let ticket = client
+        .create_ticket(&new_ticket("  Crash on save ", Some(vec![bug.id])))
+        .await
+        .unwrap();
+    assert_eq!(ticket.title, "Crash on save");
+
+    let fetched = client.get_ticket(ticket.id).await.unwrap();
+    assert_eq!(fetched.tags.len(), 1);
+
+    let updated = client
+        .update_ticket(
+            ticket.id,
+            &UpdateTicketRequest {
+                title: None,
+                description: Some("Steps attached".to_string()),
+                completed: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.description.as_deref(), Some("Steps attached"));
+    assert!(
+        client
+            .toggle_ticket_completed(ticket.id)
+            .await
+            .unwrap()
+            .completed
+    );
+
+    client
+        .remove_tag_from_ticket(ticket.id, bug.id)
+        .await
+        .unwrap();
+    let filter = TicketFilter {
+        tag: Some(bug.id),
+        ..Default::default()
+    };
+    assert!(client.list_tickets(&filter).await.unwrap().is_empty());
+    client.add_tag_to_ticket(ticket.id, bug.id).await.unwrap();
+    assert_eq!(client.list_tickets(&filter).await.unwrap().len(), 1);
+
+    let renamed = client
+        .update_tag(
+            bug.id,
+            &UpdateTagRequest {
+                name: Some("defect".to_string()),
+                color: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(renamed.name, "defect");
+    assert_eq!(client.list_tags().await.unwrap().len(), 1);
+    assert!(client.ticket_presence(ticket.id).await.unwrap().is_empty());
+
+    client.delete_tag(bug.id).await.unwrap();
+    client.delete_ticket(ticket.id).await.unwrap();
+    assert!(client
+        .get_ticket(ticket.id)
+        .await
+        .unwrap_err()
+        .is_not_found());
+}
+
+#[tokio::test]
+async fn test_errors_are_decoded() {
+    let client = Client::new(start_server().await).unwrap();
+
+    let err = client
+        .create_ticket(&new_ticket(" ", None))
+        .await
+        .unwrap_err();
+    let ClientError::Validation {
+        message,
+        request_id,
+    } = &err
+    else {
+        panic!("expected a validation error, got {:?}", err);
+    };
+    assert_eq!(message, "title must not be empty");
+    assert!(request_id.is_some());
+    assert_eq!(err.status(), Some(reqwest::StatusCode::BAD_REQUEST));
+
+    let err = client.get_tag(uuid::Uuid::new_v4()).await.unwrap_err();
+    assert!(err.is_not_found(), "{:?}", err);
+    assert!(err.to_string().starts_with("Not found: Tag with id"));
+
+    let err = client
+        .v2()
+        .list_tickets(
+            &TicketFilter::default(),
+            PageParams {
+                page: 1,
+                per_page: 500,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ClientError::Validation { .. }), "{:?}", err);
+}
+
+#[tokio::test]
+async fn test_v2_pagination() {
+    let client = Client::new(start_server().await).unwrap();
+    for i in 1..=5 {
+        client
+            .create_ticket(&new_ticket(&format!("Ticket {}", i), None))
+            .await
+            .unwrap();
+    }
+
+    let pages: Vec<_> = client
+        .v2()
+        .pages(TicketFilter::default(), 2)
+        .try_collect()
+        .await
+        .unwrap();
+    let sizes: Vec<usize> = pages.iter().map(|page| page.items.len()).collect();
+    assert_eq!(sizes, [2, 2, 1]);
+
+    let titles: Vec<String> = client
+        .v2()
+        .tickets(TicketFilter::default(), 2)
+        .map_ok(|ticket| ticket.title)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(titles.len(), 5);
+    assert_eq!(titles[0], "Ticket 5");
+    assert_eq!(titles[4], "Ticket 1");
+
+    // 没有匹配时只有一页空结果
+    let search = TicketFilter {
+        search: Some("nothing".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(client.v2().tickets(search, 2).count().await, 0);
+
+    let first = &pages[0].items[0];
+    let done = client
+        .v2()
+        .update_ticket(
+            first.id,
+            &UpdateTicketRequestV2 {
+                title: None,
+                description: None,
+                status: Some(TicketStatus::Completed),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(done.status, TicketStatus::Completed);
+}
+
+#[tokio::test]
+async fn test_import_export_webhooks_and_probes() {
+    let client = Client::new(start_server().await).unwrap();
+
+    let csv = "title,completed,tags\nFirst,false,ops\nSecond,true,\n";
+    let report = client
+        .import_tickets(ImportFormat::Csv, csv, false)
+        .await
+        .unwrap();
+    assert_eq!(report.imported, 2);
+    assert_eq!(report.created_tags, ["ops"]);
+
+    let chunks: Vec<_> = client
+        .export_tickets(&TicketFilter::default(), ExportFormat::Ndjson)
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    let body = String::from_utf8(chunks.concat()).unwrap();
+    assert_eq!(body.lines().count(), 2);
+
+    let webhook = client
+        .create_webhook(&CreateWebhookRequest {
+            url: "http://127.0.0.1:9/hook".to_string(),
+            secret: "s3cret-s3cret".to_string(),
+            events: None,
+            active: Some(false),
+        })
+        .await
+        .unwrap();
+    assert_eq!(client.list_webhooks().await.unwrap().len(), 1);
+    assert!(client
+        .webhook_deliveries(webhook.id, Some(10))
+        .await
+        .unwrap()
+        .is_empty());
+    client.delete_webhook(webhook.id).await.unwrap();
+
+    assert_eq!(client.health().await.unwrap().status, "ok");
+    assert_eq!(client.readiness().await.unwrap().status, "ready");
+    assert!(!client.version().await.unwrap().migrations.is_empty());
+    assert!(client
+        .metrics()
+        .await
+        .unwrap()
+        .contains("http_requests_total"));
+}
+
+/// 前两次请求返回 503 的服务端，记录收到的请求数
+#[tokio::test]
+async fn test_subscribe_streams_change_events() {
+    let client = Client::new(start_server().await).unwrap();
+    let mut events = client.subscribe("watcher").await.unwrap();
+
+    let ticket = client
+        .create_ticket(&new_ticket("Live", None))
+        .await
+        .unwrap();
+    client.delete_ticket(ticket.id).await.unwrap();
+
+    // 在线状态等其他消息与变更事件共用一个流，这里只看变更事件
+    let mut changes = Vec::new();
+    while changes.len() < 2 {
+        let message = tokio::time::timeout(Duration::from_secs(5), events.next())
+            .await
+            .expect("No event within 5s")
+            .expect("Stream ended")
+            .unwrap();
+        if let ServerMessage::Change(event) = message {
+            changes.push(event);
+        }
+    }
+    assert!(matches!(&changes[0], ChangeEvent::TicketCreated { ticket: t } if t.id == ticket.id));
+    assert!(matches!(changes[1], ChangeEvent::TicketDeleted { id } if id == ticket.id));
+
+    let err = match client.subscribe(" ").await {
+        Ok(_) => panic!("an empty user name must be rejected"),
+        Err(err) => err,
+    };
+    assert_eq!(err.status(), Some(reqwest::StatusCode::BAD_REQUEST));
+}
+
+async fn flaky_server() -> (String, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let counter = hits.clone();
+    let handler = move || {
+        let counter = counter.clone();
+        async move {
+            if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                (axum::http::StatusCode::SERVICE_UNAVAILABLE, "[]")
+            } else {
+                (axum::http::StatusCode::OK, "[]")
+            }
+        }
+    };
+    let app = axum::Router::new().route(
+        "/api/v1/tags",
+        axum::routing::get(handler.clone()).post(handler),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    (format!("http://{}", addr), hits)
+}
+
+#[tokio::test]
+async fn test_only_idempotent_requests_are_retried() {
+    let config = ClientConfig {
+        base_backoff: Duration::from_millis(10),
+        ..ClientConfig::default()
+    };
+
+    let (url, hits) = flaky_server().await;
+    let client = Client::with_config(url, config.clone()).unwrap();
+    assert!(client.list_tags().await.unwrap().is_empty());
+    assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+    let (url, hits) = flaky_server().await;
+    let client = Client::with_config(url, config).unwrap();
+    let err = client
+        .create_tag(&CreateTagRequest {
+            name: "ops".to_string(),
+            color: None,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.status(), Some(reqwest::StatusCode::SERVICE_UNAVAILABLE));
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
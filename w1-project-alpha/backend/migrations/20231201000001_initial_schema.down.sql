@@ -0,0 +1,6 @@
+-- 撤销初始表结构
+-- 扩展 uuid-ossp、pg_trgm 可能被同库的其他应用使用，保留不删
+
+DROP TABLE ticket_tags;
+DROP TABLE tags;
+DROP TABLE tickets;
@@ -0,0 +1,6 @@
+-- 撤销 SQLite 初始表结构；tickets 上的全文索引触发器随表一起删除
+
+DROP TABLE tickets_fts;
+DROP TABLE ticket_tags;
+DROP TABLE tags;
+DROP TABLE tickets;
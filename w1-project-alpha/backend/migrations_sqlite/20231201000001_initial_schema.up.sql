@@ -0,0 +1,55 @@
+-- SQLite 版本的初始表结构，与 migrations/ 中的 Postgres 表结构保持一致
+-- UUID 以 16 字节 BLOB 存储，时间戳以 RFC 3339 文本存储
+
+-- 创建 tickets 表
+-- seq 作为稳定的 rowid，供全文索引引用（VACUUM 不会改变它）
+CREATE TABLE tickets (
+    seq INTEGER PRIMARY KEY,
+    id BLOB NOT NULL UNIQUE,
+    title TEXT NOT NULL CHECK (length(title) <= 255),
+    description TEXT,
+    completed BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- 创建 tags 表
+CREATE TABLE tags (
+    id BLOB PRIMARY KEY NOT NULL,
+    name TEXT NOT NULL UNIQUE CHECK (length(name) <= 50),
+    color TEXT CHECK (length(color) <= 7),
+    created_at TEXT NOT NULL
+);
+
+-- 创建 ticket_tags 关联表
+CREATE TABLE ticket_tags (
+    ticket_id BLOB NOT NULL REFERENCES tickets(id) ON DELETE CASCADE,
+    tag_id BLOB NOT NULL REFERENCES tags(id) ON DELETE CASCADE,
+    PRIMARY KEY (ticket_id, tag_id)
+);
+
+-- 创建索引
+CREATE INDEX idx_tickets_created_at ON tickets(created_at);
+CREATE INDEX idx_ticket_tags_tag_id ON ticket_tags(tag_id);
+
+-- Ticket 标题全文索引（FTS5 trigram 分词，替代 pg_trgm 支持子串搜索）
+CREATE VIRTUAL TABLE tickets_fts USING fts5(
+    title,
+    content = 'tickets',
+    content_rowid = 'seq',
+    tokenize = 'trigram'
+);
+
+-- 通过触发器保持全文索引与 tickets 表同步
+CREATE TRIGGER tickets_fts_insert AFTER INSERT ON tickets BEGIN
+    INSERT INTO tickets_fts (rowid, title) VALUES (new.seq, new.title);
+END;
+
+CREATE TRIGGER tickets_fts_delete AFTER DELETE ON tickets BEGIN
+    INSERT INTO tickets_fts (tickets_fts, rowid, title) VALUES ('delete', old.seq, old.title);
+END;
+
+CREATE TRIGGER tickets_fts_update AFTER UPDATE OF title ON tickets BEGIN
+    INSERT INTO tickets_fts (tickets_fts, rowid, title) VALUES ('delete', old.seq, old.title);
+    INSERT INTO tickets_fts (rowid, title) VALUES (new.seq, new.title);
+END;
@@ -0,0 +1,4 @@
+-- 撤销 webhook 订阅与投递记录表
+
+DROP TABLE webhook_deliveries;
+DROP TABLE webhooks;
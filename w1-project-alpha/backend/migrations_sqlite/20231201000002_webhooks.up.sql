@@ -0,0 +1,35 @@
+-- 创建 webhooks 表
+-- events 为 JSON 数组，空数组表示订阅所有事件
+CREATE TABLE webhooks (
+    id BLOB PRIMARY KEY NOT NULL,
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    events TEXT NOT NULL DEFAULT '[]',
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- 创建 webhook_deliveries 表（发件箱 + 投递日志）
+-- 与 ticket 变更在同一事务中写入，由后台任务投递
+CREATE TABLE webhook_deliveries (
+    id BLOB PRIMARY KEY NOT NULL DEFAULT (randomblob(16)),
+    webhook_id BLOB NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+    event TEXT NOT NULL,
+    payload TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending',
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TEXT NOT NULL,
+    last_status_code INTEGER,
+    last_error TEXT,
+    created_at TEXT NOT NULL,
+    delivered_at TEXT
+);
+
+-- 创建索引
+-- 待投递记录索引（后台任务按 next_attempt_at 拉取）
+CREATE INDEX idx_webhook_deliveries_pending ON webhook_deliveries(next_attempt_at)
+    WHERE status = 'pending';
+
+-- 投递日志查询索引
+CREATE INDEX idx_webhook_deliveries_webhook_id ON webhook_deliveries(webhook_id, created_at DESC);
@@ -0,0 +1,3 @@
+-- 撤销投递记录的 trace 上下文列
+
+ALTER TABLE webhook_deliveries DROP COLUMN traceparent;
@@ -0,0 +1,3 @@
+-- 记录产生事件的请求的 W3C traceparent，投递 webhook 时延续同一条链路
+-- 未启用 otel 特性或请求未被追踪时为 NULL
+ALTER TABLE webhook_deliveries ADD COLUMN traceparent TEXT;
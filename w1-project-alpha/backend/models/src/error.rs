@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// Body of every error response.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ErrorResponse {
+    pub error: String,
+    /// The HTTP status code, repeated for clients that only see the body
+    pub status: u16,
+    /// The `X-Request-Id` of the failed request, to quote in bug reports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+}
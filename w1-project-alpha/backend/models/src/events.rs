@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::tag::Tag;
+use crate::ticket::Ticket;
+
+/// What a user is doing with a ticket.
+///
+/// Ordered so that `Editing` wins when the same user has several connections
+/// open on one ticket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceMode {
+    #[default]
+    Viewing,
+    Editing,
+}
+
+/// A user currently present on a ticket, as sent to clients.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct PresenceEntry {
+    pub user: String,
+    pub mode: PresenceMode,
+}
+
+/// A change to persisted data, published after the mutation succeeded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ChangeEvent {
+    TicketCreated { ticket: Ticket },
+    TicketUpdated { ticket: Ticket },
+    TicketDeleted { id: Uuid },
+    TicketTagAdded { ticket_id: Uuid, tag_id: Uuid },
+    TicketTagRemoved { ticket_id: Uuid, tag_id: Uuid },
+    TagCreated { tag: Tag },
+    TagUpdated { tag: Tag },
+    TagDeleted { id: Uuid },
+}
+
+impl ChangeEvent {
+    /// Dotted event name used for webhook subscriptions, e.g. `ticket.created`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ChangeEvent::TicketCreated { .. } => "ticket.created",
+            ChangeEvent::TicketUpdated { .. } => "ticket.updated",
+            ChangeEvent::TicketDeleted { .. } => "ticket.deleted",
+            ChangeEvent::TicketTagAdded { .. } => "ticket.tag_added",
+            ChangeEvent::TicketTagRemoved { .. } => "ticket.tag_removed",
+            ChangeEvent::TagCreated { .. } => "tag.created",
+            ChangeEvent::TagUpdated { .. } => "tag.updated",
+            ChangeEvent::TagDeleted { .. } => "tag.deleted",
+        }
+    }
+}
+
+/// Messages pushed to WebSocket clients.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// Data changed on the server.
+    Change(ChangeEvent),
+    /// The full list of users currently on a ticket.
+    Presence {
+        ticket_id: Uuid,
+        users: Vec<PresenceEntry>,
+    },
+    /// A user is typing in a field of a ticket.
+    Typing {
+        ticket_id: Uuid,
+        user: String,
+        field: String,
+    },
+}
@@ -0,0 +1,113 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// A row of the `_sqlx_migrations` bookkeeping table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow))]
+pub struct AppliedMigration {
+    pub version: i64,
+    pub description: String,
+    pub installed_on: DateTime<Utc>,
+    #[serde(skip)]
+    pub success: bool,
+    /// Checksum of the migration file as it was applied.
+    #[serde(skip)]
+    pub checksum: Vec<u8>,
+}
+
+/// Where a migration stands in a database, as shown by `migrate status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationState {
+    Applied,
+    Pending,
+    /// Recorded as started but not finished; the schema needs a manual look.
+    Failed,
+    /// Applied, but not built into this binary, e.g. by a newer release.
+    Unknown,
+    /// Applied from a migration file that has since been edited.
+    Modified,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub state: MigrationState,
+    pub installed_on: Option<DateTime<Utc>>,
+    /// Whether `migrate down` can revert it.
+    pub reversible: bool,
+}
+
+/// Connection pool usage at one point in time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolStatus {
+    /// Open connections, idle or checked out.
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+    pub max: u32,
+    /// `in_use / max`, from 0.0 to 1.0.
+    pub saturation: f64,
+}
+
+impl PoolStatus {
+    pub fn new(size: u32, idle: u32, max: u32) -> Self {
+        let in_use = size.saturating_sub(idle);
+        Self {
+            size,
+            idle,
+            in_use,
+            max,
+            saturation: if max == 0 {
+                0.0
+            } else {
+                f64::from(in_use) / f64::from(max)
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthResponse {
+    pub status: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadinessResponse {
+    /// `ready` or `not_ready`.
+    pub status: String,
+    pub database: DatabaseCheck,
+    pub migrations: MigrationCheck,
+    pub pool: PoolCheck,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatabaseCheck {
+    pub ok: bool,
+    pub latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MigrationCheck {
+    pub ok: bool,
+    /// Versions built into this binary but not applied yet.
+    pub pending: Vec<i64>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PoolCheck {
+    #[serde(flatten)]
+    pub status: PoolStatus,
+    /// Informational only; a busy pool does not make the service unready.
+    pub saturated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionResponse {
+    pub version: String,
+    pub git_sha: String,
+    pub migrations: Vec<AppliedMigration>,
+}
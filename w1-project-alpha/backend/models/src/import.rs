@@ -0,0 +1,39 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A validated import row, ready to be inserted.
+#[derive(Debug, Clone)]
+pub struct ImportTicket {
+    pub title: String,
+    pub description: Option<String>,
+    pub completed: bool,
+    /// Tag names; missing tags are created during the import.
+    pub tags: Vec<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// Problems found in one input row. `row` is 1-based and excludes the CSV header.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ImportRowError {
+    pub row: usize,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct ImportReport {
+    pub dry_run: bool,
+    /// Rows read from the input
+    pub total: usize,
+    /// Rows imported, or that would be imported on a dry run
+    pub imported: usize,
+    pub failed: usize,
+    /// Tag names created, or that would be created on a dry run
+    pub created_tags: Vec<String>,
+    /// Ids of the new tickets, in input order; empty on a dry run
+    pub ticket_ids: Vec<Uuid>,
+    pub errors: Vec<ImportRowError>,
+}
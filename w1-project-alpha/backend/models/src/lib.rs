@@ -0,0 +1,25 @@
+//! Request and response types of the Project Alpha API, shared by the
+//! server and the Rust client so that both sides agree at compile time.
+//!
+//! The `server` feature adds the database row mapping and the OpenAPI
+//! schemas, which clients do not need.
+
+pub mod error;
+pub mod events;
+pub mod format;
+pub mod health;
+pub mod import;
+pub mod page;
+pub mod tag;
+pub mod ticket;
+pub mod webhook;
+
+pub use error::*;
+pub use events::*;
+pub use format::*;
+pub use health::*;
+pub use import::*;
+pub use page::*;
+pub use tag::*;
+pub use ticket::*;
+pub use webhook::*;
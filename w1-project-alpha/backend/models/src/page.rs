@@ -0,0 +1,119 @@
+use serde::{Deserialize, Serialize};
+
+/// Largest accepted `per_page`.
+pub const MAX_PER_PAGE: u32 = 100;
+
+/// `?page=` and `?per_page=` of a paginated list. Pages are 1-based.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "server", into_params(parameter_in = Query))]
+pub struct PageParams {
+    /// Page number, from 1 (default 1)
+    #[serde(default = "default_page")]
+    pub page: u32,
+    /// Items per page, 1 to 100 (default 20)
+    #[serde(default = "default_per_page")]
+    pub per_page: u32,
+}
+
+fn default_page() -> u32 {
+    1
+}
+
+fn default_per_page() -> u32 {
+    20
+}
+
+impl Default for PageParams {
+    fn default() -> Self {
+        Self {
+            page: default_page(),
+            per_page: default_per_page(),
+        }
+    }
+}
+
+impl PageParams {
+    /// Check the parameters, returning the message to report when they
+    /// are out of range.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.page == 0 {
+            return Err("page starts at 1".to_string());
+        }
+        if !(1..=MAX_PER_PAGE).contains(&self.per_page) {
+            return Err(format!("per_page must be between 1 and {}", MAX_PER_PAGE));
+        }
+        Ok(())
+    }
+
+    pub fn limit(&self) -> i64 {
+        i64::from(self.per_page)
+    }
+
+    pub fn offset(&self) -> i64 {
+        i64::from(self.page.saturating_sub(1)) * i64::from(self.per_page)
+    }
+}
+
+/// One page of a list, with what a client needs to render pagination.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub page: u32,
+    pub per_page: u32,
+    /// Items matching the query across all pages
+    pub total: i64,
+    pub total_pages: i64,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, params: PageParams, total: i64) -> Self {
+        let per_page = i64::from(params.per_page.max(1));
+        Self {
+            items,
+            page: params.page,
+            per_page: params.per_page,
+            total,
+            total_pages: (total + per_page - 1) / per_page,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            page: self.page,
+            per_page: self.per_page,
+            total: self.total,
+            total_pages: self.total_pages,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_and_page_counts() {
+        let params = PageParams {
+            page: 3,
+            per_page: 10,
+        };
+        assert_eq!(params.offset(), 20);
+        assert_eq!(params.limit(), 10);
+
+        assert_eq!(Page::new(vec![1], params, 21).total_pages, 3);
+        assert_eq!(Page::<u8>::new(vec![], params, 0).total_pages, 0);
+        assert_eq!(Page::<u8>::new(vec![], params, 30).total_pages, 3);
+    }
+
+    #[test]
+    fn rejects_out_of_range_params() {
+        assert!(PageParams::default().validate().is_ok());
+        for (page, per_page) in [(0, 20), (1, 0), (1, MAX_PER_PAGE + 1)] {
+            let params = PageParams { page, per_page };
+            assert!(params.validate().is_err());
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow, utoipa::ToSchema))]
+pub struct Ticket {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub completed: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct CreateTicketRequest {
+    pub title: String,
+    pub description: Option<String>,
+    pub tag_ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct UpdateTicketRequest {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub completed: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct TicketWithTags {
+    #[serde(flatten)]
+    pub ticket: Ticket,
+    pub tags: Vec<super::tag::Tag>,
+}
+
+/// Filters shared by ticket listing, export and every other ticket query.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::IntoParams))]
+#[cfg_attr(feature = "server", into_params(parameter_in = Query))]
+pub struct TicketFilter {
+    /// Only tickets carrying this tag
+    pub tag: Option<Uuid>,
+    /// Case-insensitive match on the title
+    pub search: Option<String>,
+    pub completed: Option<bool>,
+}
+
+/// Ticket counts by state and by tag.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct TicketStats {
+    pub open: i64,
+    pub completed: i64,
+    /// Every tag, including unused ones, sorted by name.
+    pub by_tag: Vec<TagCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(sqlx::FromRow, utoipa::ToSchema))]
+pub struct TagCount {
+    pub tag_id: Uuid,
+    pub name: String,
+    pub tickets: i64,
+}
+
+/// Whether a ticket is still to be done; replaces `completed` in API v2.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum TicketStatus {
+    Open,
+    Completed,
+}
+
+impl TicketStatus {
+    pub fn from_completed(completed: bool) -> Self {
+        if completed {
+            TicketStatus::Completed
+        } else {
+            TicketStatus::Open
+        }
+    }
+
+    pub fn is_completed(self) -> bool {
+        self == TicketStatus::Completed
+    }
+}
+
+/// A ticket as represented by API v2: a `status` instead of the `completed`
+/// flag, and always with its tags.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct TicketV2 {
+    pub id: Uuid,
+    pub title: String,
+    pub description: Option<String>,
+    pub status: TicketStatus,
+    pub tags: Vec<super::tag::Tag>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<TicketWithTags> for TicketV2 {
+    fn from(TicketWithTags { ticket, tags }: TicketWithTags) -> Self {
+        TicketV2 {
+            id: ticket.id,
+            title: ticket.title,
+            description: ticket.description,
+            status: TicketStatus::from_completed(ticket.completed),
+            tags,
+            created_at: ticket.created_at,
+            updated_at: ticket.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "server", derive(utoipa::ToSchema))]
+pub struct UpdateTicketRequestV2 {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub status: Option<TicketStatus>,
+}
+
+impl From<UpdateTicketRequestV2> for UpdateTicketRequest {
+    fn from(request: UpdateTicketRequestV2) -> Self {
+        UpdateTicketRequest {
+            title: request.title,
+            description: request.description,
+            completed: request.status.map(TicketStatus::is_completed),
+        }
+    }
+}
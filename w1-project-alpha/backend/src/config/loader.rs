@@ -0,0 +1,410 @@
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+use toml::{Table, Value};
+
+use super::Config;
+
+/// Prefix of the environment variables that override settings, e.g.
+/// `APP_SERVER_PORT` for `server.port`.
+pub const ENV_PREFIX: &str = "APP_";
+
+/// Unprefixed variables that are honoured as well, below their `APP_`
+/// counterparts, because other tooling or existing `.env` files already set
+/// them.
+const PLAIN_ENV_VARS: &[(&str, &str)] = &[
+    ("DATABASE_URL", "database.url"),
+    ("LOG_FORMAT", "log.format"),
+    // Read by releases before the layered configuration
+    ("HOST", "server.host"),
+    ("PORT", "server.port"),
+    ("DATABASE_MAX_CONNECTIONS", "database.max_connections"),
+];
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to read config file {}: {source}", path.display())]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error("Invalid TOML in {origin}: {message}")]
+    Parse { origin: String, message: String },
+
+    #[error("Unknown setting '{key}' in {origin}")]
+    UnknownKey { key: String, origin: String },
+
+    #[error("Invalid value for '{key}' in {origin}: expected {expected}, got {value}")]
+    InvalidValue {
+        key: String,
+        value: String,
+        expected: &'static str,
+        origin: String,
+    },
+
+    #[error("Invalid configuration:\n  - {}", .0.join("\n  - "))]
+    Invalid(Vec<String>),
+}
+
+/// Builds a [`Config`] from layers applied in order; later layers win.
+///
+/// Every layer is checked against the defaults, so a misspelt key or a value
+/// of the wrong type is reported with where it came from.
+pub struct ConfigLoader {
+    table: Table,
+}
+
+impl ConfigLoader {
+    /// Start from the built-in defaults.
+    pub fn new() -> Self {
+        let table = Table::try_from(Config::default()).expect("defaults serialize to TOML");
+        Self { table }
+    }
+
+    /// Merge a TOML config file.
+    pub fn file(&mut self, path: &Path) -> Result<&mut Self, ConfigError> {
+        let contents = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        self.toml(&contents, &path.display().to_string())
+    }
+
+    /// Merge TOML text; `origin` names it in error messages.
+    pub fn toml(&mut self, contents: &str, origin: &str) -> Result<&mut Self, ConfigError> {
+        let layer: Table = contents
+            .parse()
+            .map_err(|e: toml::de::Error| ConfigError::Parse {
+                origin: origin.to_string(),
+                message: e.message().to_string(),
+            })?;
+        merge(&mut self.table, layer, "", origin)?;
+        Ok(self)
+    }
+
+    /// Apply `APP_<SECTION>_<KEY>` variables. Variables that match no
+    /// setting are ignored, since the prefix is shared with the rest of the
+    /// environment. `DATABASE_URL` (expected by sqlx and most tooling),
+    /// `LOG_FORMAT`, and the `HOST`, `PORT` and `DATABASE_MAX_CONNECTIONS`
+    /// of earlier releases are honoured as well, below their prefixed names.
+    pub fn env<I>(&mut self, vars: I) -> Result<&mut Self, ConfigError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let plain_key = |name: &str| {
+            PLAIN_ENV_VARS
+                .iter()
+                .find(|(plain, _)| *plain == name)
+                .map(|(_, key)| key.to_string())
+        };
+        let mut vars: Vec<_> = vars.into_iter().collect();
+        // Apply the plain names first so the prefixed ones override them
+        vars.sort_by_key(|(name, _)| plain_key(name).is_none());
+
+        for (name, value) in vars {
+            let key = if let Some(key) = plain_key(&name) {
+                key
+            } else if let Some(rest) = name.strip_prefix(ENV_PREFIX) {
+                match self.env_key(&rest.to_lowercase()) {
+                    Some(key) => key,
+                    None => continue,
+                }
+            } else {
+                continue;
+            };
+            self.assign(&key, &value, &name)?;
+        }
+        Ok(self)
+    }
+
+    /// Set a single `section.key` from its string form, as given on the
+    /// command line.
+    pub fn set(&mut self, key: &str, value: &str) -> Result<&mut Self, ConfigError> {
+        self.assign(key, value, "command-line arguments")?;
+        Ok(self)
+    }
+
+    /// Deserialize and validate the merged layers.
+    pub fn finish(&self) -> Result<Config, ConfigError> {
+        let config: Config =
+            Value::Table(self.table.clone())
+                .try_into()
+                .map_err(|e: toml::de::Error| ConfigError::Parse {
+                    origin: "merged configuration".to_string(),
+                    message: e.message().to_string(),
+                })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Map `server_port` to `server.port`, trying every section since section
+    /// names may themselves contain underscores.
+    fn env_key(&self, name: &str) -> Option<String> {
+        self.table.iter().find_map(|(section, value)| {
+            let field = name.strip_prefix(section.as_str())?.strip_prefix('_')?;
+            value
+                .as_table()?
+                .contains_key(field)
+                .then(|| format!("{}.{}", section, field))
+        })
+    }
+
+    /// Parse `raw` as the type of the setting it replaces.
+    fn assign(&mut self, key: &str, raw: &str, origin: &str) -> Result<(), ConfigError> {
+        let unknown = || ConfigError::UnknownKey {
+            key: key.to_string(),
+            origin: origin.to_string(),
+        };
+        let (section, field) = key.split_once('.').ok_or_else(unknown)?;
+        let slot = self
+            .table
+            .get_mut(section)
+            .and_then(Value::as_table_mut)
+            .and_then(|table| table.get_mut(field))
+            .ok_or_else(unknown)?;
+
+        let invalid = |expected| ConfigError::InvalidValue {
+            key: key.to_string(),
+            value: raw.to_string(),
+            expected,
+            origin: origin.to_string(),
+        };
+        *slot = match slot {
+            Value::String(_) => Value::String(raw.to_string()),
+            Value::Integer(_) => {
+                Value::Integer(raw.trim().parse().map_err(|_| invalid("an integer"))?)
+            }
+            Value::Float(_) => Value::Float(raw.trim().parse().map_err(|_| invalid("a number"))?),
+            Value::Boolean(_) => {
+                Value::Boolean(raw.trim().parse().map_err(|_| invalid("true or false"))?)
+            }
+            // Lists are comma separated
+            Value::Array(_) => Value::Array(
+                raw.split(',')
+                    .map(str::trim)
+                    .filter(|item| !item.is_empty())
+                    .map(|item| Value::String(item.to_string()))
+                    .collect(),
+            ),
+            Value::Table(_) | Value::Datetime(_) => return Err(unknown()),
+        };
+        Ok(())
+    }
+}
+
+impl Default for ConfigLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Merge `layer` into `base`, rejecting keys and types the defaults do not
+/// have.
+fn merge(base: &mut Table, layer: Table, prefix: &str, origin: &str) -> Result<(), ConfigError> {
+    for (name, value) in layer {
+        let key = format!("{}{}", prefix, name);
+        let Some(slot) = base.get_mut(&name) else {
+            return Err(ConfigError::UnknownKey {
+                key,
+                origin: origin.to_string(),
+            });
+        };
+
+        match (slot, value) {
+            (Value::Table(slot), Value::Table(value)) => {
+                merge(slot, value, &format!("{}.", key), origin)?;
+            }
+            (slot, value) if slot.same_type(&value) => *slot = value,
+            (slot, value) => {
+                return Err(ConfigError::InvalidValue {
+                    key,
+                    value: value.to_string(),
+                    expected: slot.type_str(),
+                    origin: origin.to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::LogFormat;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn later_layers_win() {
+        let config = ConfigLoader::new()
+            .toml(
+                r#"
+                [database]
+                url = "postgres://file/alpha"
+                max_connections = 20
+
+                [server]
+                port = 8080
+                "#,
+                "alpha.toml",
+            )
+            .unwrap()
+            .env(vars(&[
+                ("APP_DATABASE_MAX_CONNECTIONS", "30"),
+                ("APP_SERVER_PORT", "9090"),
+                ("APP_CORS_ALLOW_CREDENTIALS", "true"),
+                (
+                    "APP_CORS_ALLOWED_ORIGINS",
+                    "https://app.example.com, https://*.preview.example.com",
+                ),
+                ("APP_UNRELATED", "ignored"),
+                ("HOME", "/root"),
+            ]))
+            .unwrap()
+            .set("server.port", "7070")
+            .unwrap()
+            .finish()
+            .unwrap();
+
+        assert_eq!(config.database.url, "postgres://file/alpha");
+        assert_eq!(config.database.max_connections, 30);
+        assert_eq!(config.database.acquire_timeout_secs, 30);
+        assert_eq!(config.server.port, 7070);
+        assert_eq!(config.server.host, "127.0.0.1");
+        assert!(config.cors.allow_credentials);
+        assert_eq!(
+            config.cors.allowed_origins,
+            ["https://app.example.com", "https://*.preview.example.com"]
+        );
+    }
+
+    #[test]
+    fn prefixed_database_url_beats_the_plain_one() {
+        let config = ConfigLoader::new()
+            .env(vars(&[
+                ("APP_DATABASE_URL", "postgres://app/alpha"),
+                ("DATABASE_URL", "postgres://plain/alpha"),
+            ]))
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(config.database.url, "postgres://app/alpha");
+
+        let config = ConfigLoader::new()
+            .env(vars(&[("DATABASE_URL", "postgres://plain/alpha")]))
+            .unwrap()
+            .finish()
+            .unwrap();
+        assert_eq!(config.database.url, "postgres://plain/alpha");
+    }
+
+    #[test]
+    fn legacy_plain_variables_are_honoured() {
+        let load = |pairs: &[(&str, &str)]| {
+            ConfigLoader::new()
+                .env(vars(&[("DATABASE_URL", "postgres://localhost/alpha")]))
+                .unwrap()
+                .env(vars(pairs))
+                .unwrap()
+                .finish()
+        };
+
+        let config = load(&[
+            ("HOST", "127.0.0.1"),
+            ("PORT", "8080"),
+            ("DATABASE_MAX_CONNECTIONS", "20"),
+        ])
+        .unwrap();
+        assert_eq!(config.server.host, "127.0.0.1");
+        assert_eq!(config.server.port, 8080);
+        assert_eq!(config.database.max_connections, 20);
+
+        let config = load(&[("PORT", "8080"), ("APP_SERVER_PORT", "9090")]).unwrap();
+        assert_eq!(config.server.port, 9090);
+
+        assert!(matches!(
+            ConfigLoader::new().env(vars(&[("PORT", "http")])),
+            Err(ConfigError::InvalidValue { origin, .. }) if origin == "PORT"
+        ));
+    }
+
+    #[test]
+    fn log_format_comes_from_either_variable() {
+        let load = |pairs: &[(&str, &str)]| {
+            ConfigLoader::new()
+                .env(vars(&[("DATABASE_URL", "postgres://localhost/alpha")]))
+                .unwrap()
+                .env(vars(pairs))
+                .unwrap()
+                .finish()
+        };
+
+        assert_eq!(load(&[]).unwrap().log.format, LogFormat::Text);
+        assert_eq!(
+            load(&[("LOG_FORMAT", "json")]).unwrap().log.format,
+            LogFormat::Json
+        );
+        assert_eq!(
+            load(&[("LOG_FORMAT", "json"), ("APP_LOG_FORMAT", "text")])
+                .unwrap()
+                .log
+                .format,
+            LogFormat::Text
+        );
+        assert!(matches!(
+            load(&[("LOG_FORMAT", "yaml")]),
+            Err(ConfigError::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn errors_name_the_key_and_its_origin() {
+        let err = ConfigLoader::new()
+            .toml("[database]\nmax_conections = 3", "alpha.toml")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Unknown setting 'database.max_conections' in alpha.toml"
+        );
+
+        let err = ConfigLoader::new()
+            .toml("[server]\nport = \"eighty\"", "alpha.toml")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Invalid value for 'server.port' in alpha.toml: expected integer, got \"eighty\""
+        );
+
+        let err = ConfigLoader::new()
+            .env(vars(&[("APP_SERVER_PORT", "eighty")]))
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Invalid value for 'server.port' in APP_SERVER_PORT: expected an integer, got eighty"
+        );
+
+        let err = ConfigLoader::new().set("server.prot", "1").err().unwrap();
+        assert!(matches!(err, ConfigError::UnknownKey { .. }));
+    }
+
+    #[test]
+    fn out_of_range_values_fail_to_deserialize() {
+        let err = ConfigLoader::new()
+            .set("database.url", "postgres://localhost/alpha")
+            .unwrap()
+            .set("server.port", "70000")
+            .unwrap()
+            .finish()
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::Parse { .. }), "{}", err);
+    }
+}
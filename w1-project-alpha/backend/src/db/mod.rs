@@ -0,0 +1,356 @@
+use sqlx::migrate::{Migrate, MigrateError, Migrator};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::PgPool;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::config::DatabaseConfig;
+use crate::models::{AppliedMigration, MigrationState, MigrationStatus, PoolStatus};
+use crate::repositories::Repositories;
+use crate::utils::backoff::backoff_delay;
+use crate::utils::error::{AppError, Result};
+
+/// Upper bound for the delay between startup connection attempts.
+const MAX_CONNECT_BACKOFF: Duration = Duration::from_secs(30);
+
+static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+#[cfg(feature = "sqlite")]
+static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations_sqlite");
+
+const SELECT_MIGRATIONS: &str = "SELECT version, description, installed_on, success, checksum
+     FROM _sqlx_migrations
+     ORDER BY version";
+
+/// A connection pool for the backend named by `DATABASE_URL`.
+///
+/// `postgres://` and `postgresql://` URLs use Postgres; `sqlite:` URLs use
+/// SQLite when the crate is built with the `sqlite` feature.
+#[derive(Clone)]
+pub enum Database {
+    Postgres(PgPool),
+    #[cfg(feature = "sqlite")]
+    Sqlite(sqlx::SqlitePool),
+}
+
+impl Database {
+    /// Open a pool for `config.url`, retrying with exponential backoff while
+    /// the database is unreachable.
+    pub async fn connect(config: &DatabaseConfig) -> Result<Self> {
+        let mut attempt = 0;
+        loop {
+            match Self::try_connect(config).await {
+                Err(AppError::Database(err))
+                    if attempt < config.connect_retries && is_transient(&err) =>
+                {
+                    attempt += 1;
+                    let delay = backoff_delay(
+                        attempt as i32,
+                        config.connect_backoff(),
+                        MAX_CONNECT_BACKOFF,
+                    );
+                    tracing::warn!(
+                        "Database not reachable (attempt {}/{}): {}; retrying in {:?}",
+                        attempt,
+                        config.connect_retries + 1,
+                        err,
+                        delay
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn try_connect(config: &DatabaseConfig) -> Result<Self> {
+        let url = config.url.as_str();
+        match scheme(url) {
+            "postgres" | "postgresql" => {
+                let mut options = PgConnectOptions::from_str(url)?;
+                if let Some(timeout) = config.statement_timeout() {
+                    options = options.options([("statement_timeout", timeout.as_millis())]);
+                }
+                let pool = PgPoolOptions::new()
+                    .max_connections(config.max_connections)
+                    .min_connections(config.min_connections)
+                    .acquire_timeout(config.acquire_timeout())
+                    .idle_timeout(config.idle_timeout())
+                    .max_lifetime(config.max_lifetime())
+                    .connect_with(options)
+                    .await?;
+                Ok(Database::Postgres(pool))
+            }
+            #[cfg(feature = "sqlite")]
+            "sqlite" => {
+                use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+                let options = SqliteConnectOptions::from_str(url)?
+                    .create_if_missing(true)
+                    .foreign_keys(true);
+                let pool = SqlitePoolOptions::new().acquire_timeout(config.acquire_timeout());
+                // Every connection to an in-memory database gets its own copy,
+                // and the data is gone once that connection closes
+                let pool = if url.contains(":memory:") || url.contains("mode=memory") {
+                    pool.max_connections(1)
+                        .min_connections(1)
+                        .idle_timeout(None)
+                        .max_lifetime(None)
+                } else {
+                    pool.max_connections(config.max_connections)
+                        .min_connections(config.min_connections)
+                        .idle_timeout(config.idle_timeout())
+                        .max_lifetime(config.max_lifetime())
+                };
+                Ok(Database::Sqlite(pool.connect_with(options).await?))
+            }
+            #[cfg(not(feature = "sqlite"))]
+            "sqlite" => Err(AppError::Validation(
+                "DATABASE_URL uses sqlite, but this build does not include the `sqlite` feature"
+                    .to_string(),
+            )),
+            other => Err(AppError::Validation(format!(
+                "Unsupported DATABASE_URL scheme '{}', expected postgres or sqlite",
+                other
+            ))),
+        }
+    }
+
+    /// Apply the migrations of this backend.
+    pub async fn migrate(&self) -> Result<()> {
+        let result = match self {
+            Database::Postgres(pool) => POSTGRES_MIGRATOR.run(pool).await,
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(pool) => SQLITE_MIGRATOR.run(pool).await,
+        };
+        result.map_err(|e| AppError::Internal(format!("Migration failed: {}", e)))
+    }
+
+    /// Revert the applied migrations newer than `target`, newest first. A
+    /// target of 0 reverts all of them.
+    pub async fn undo(&self, target: i64) -> Result<()> {
+        let result = match self {
+            Database::Postgres(pool) => POSTGRES_MIGRATOR.undo(pool, target).await,
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(pool) => SQLITE_MIGRATOR.undo(pool, target).await,
+        };
+        result.map_err(|e| AppError::Internal(format!("Reverting migrations failed: {}", e)))
+    }
+
+    /// Every migration built into this binary or recorded in the database,
+    /// oldest first. Creates the bookkeeping table in a new database.
+    pub async fn migration_status(&self) -> Result<Vec<MigrationStatus>> {
+        let created = match self {
+            Database::Postgres(pool) => pool.acquire().await?.ensure_migrations_table().await,
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(pool) => pool.acquire().await?.ensure_migrations_table().await,
+        };
+        created.map_err(|e: MigrateError| AppError::Internal(e.to_string()))?;
+
+        let applied = self.applied_migrations().await?;
+        let migrator = self.migrator();
+        let reversible = |version: i64| {
+            migrator
+                .iter()
+                .any(|m| m.version == version && m.migration_type.is_down_migration())
+        };
+
+        let mut status: Vec<MigrationStatus> = migrator
+            .iter()
+            .filter(|m| m.migration_type.is_up_migration())
+            .map(|m| {
+                let record = applied.iter().find(|a| a.version == m.version);
+                MigrationStatus {
+                    version: m.version,
+                    description: m.description.to_string(),
+                    state: match record {
+                        Some(record) if !record.success => MigrationState::Failed,
+                        Some(record) if *record.checksum != *m.checksum => MigrationState::Modified,
+                        Some(_) => MigrationState::Applied,
+                        None => MigrationState::Pending,
+                    },
+                    installed_on: record.map(|record| record.installed_on),
+                    reversible: reversible(m.version),
+                }
+            })
+            .collect();
+        for record in &applied {
+            if !status.iter().any(|s| s.version == record.version) {
+                status.push(MigrationStatus {
+                    version: record.version,
+                    description: record.description.clone(),
+                    state: MigrationState::Unknown,
+                    installed_on: Some(record.installed_on),
+                    reversible: false,
+                });
+            }
+        }
+        status.sort_by_key(|s| s.version);
+        Ok(status)
+    }
+
+    /// Run a script of several SQL statements in one transaction.
+    pub async fn execute_script(&self, sql: &str) -> Result<()> {
+        match self {
+            Database::Postgres(pool) => {
+                let mut tx = pool.begin().await?;
+                sqlx::raw_sql(sql).execute(&mut *tx).await?;
+                tx.commit().await?;
+            }
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(pool) => {
+                let mut tx = pool.begin().await?;
+                sqlx::raw_sql(sql).execute(&mut *tx).await?;
+                tx.commit().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// `postgres` or `sqlite`.
+    pub fn backend(&self) -> &'static str {
+        match self {
+            Database::Postgres(_) => "postgres",
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(_) => "sqlite",
+        }
+    }
+
+    fn migrator(&self) -> &'static Migrator {
+        match self {
+            Database::Postgres(_) => &POSTGRES_MIGRATOR,
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(_) => &SQLITE_MIGRATOR,
+        }
+    }
+
+    /// Run a trivial query to prove a connection can be acquired and used.
+    pub async fn ping(&self) -> Result<()> {
+        match self {
+            Database::Postgres(pool) => {
+                sqlx::query("SELECT 1").execute(pool).await?;
+            }
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(pool) => {
+                sqlx::query("SELECT 1").execute(pool).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Migrations recorded in the database, oldest first.
+    pub async fn applied_migrations(&self) -> Result<Vec<AppliedMigration>> {
+        let migrations = match self {
+            Database::Postgres(pool) => {
+                sqlx::query_as::<_, AppliedMigration>(SELECT_MIGRATIONS)
+                    .fetch_all(pool)
+                    .await?
+            }
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(pool) => {
+                sqlx::query_as::<_, AppliedMigration>(SELECT_MIGRATIONS)
+                    .fetch_all(pool)
+                    .await?
+            }
+        };
+        Ok(migrations)
+    }
+
+    /// Versions of the migrations built into this binary that have not been
+    /// applied successfully.
+    pub async fn pending_migrations(&self) -> Result<Vec<i64>> {
+        let applied: Vec<i64> = self
+            .applied_migrations()
+            .await?
+            .into_iter()
+            .filter(|m| m.success)
+            .map(|m| m.version)
+            .collect();
+
+        Ok(self
+            .migrator()
+            .iter()
+            .filter(|m| m.migration_type.is_up_migration() && !applied.contains(&m.version))
+            .map(|m| m.version)
+            .collect())
+    }
+
+    pub fn pool_status(&self) -> PoolStatus {
+        match self {
+            Database::Postgres(pool) => PoolStatus::new(
+                pool.size(),
+                pool.num_idle() as u32,
+                pool.options().get_max_connections(),
+            ),
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(pool) => PoolStatus::new(
+                pool.size(),
+                pool.num_idle() as u32,
+                pool.options().get_max_connections(),
+            ),
+        }
+    }
+
+    /// Wait for checked-out connections to be returned, then close them all.
+    pub async fn close(&self) {
+        match self {
+            Database::Postgres(pool) => pool.close().await,
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(pool) => pool.close().await,
+        }
+    }
+
+    pub fn repositories(&self) -> Repositories {
+        match self {
+            Database::Postgres(pool) => Repositories::new(pool.clone()),
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(pool) => Repositories::sqlite(pool.clone()),
+        }
+    }
+}
+
+fn scheme(url: &str) -> &str {
+    url.split_once(':').map(|(scheme, _)| scheme).unwrap_or("")
+}
+
+/// Errors worth retrying at startup: the server is down, still booting, or
+/// every connection attempt timed out.
+fn is_transient(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+        // cannot_connect_now: Postgres is starting up or recovering
+        sqlx::Error::Database(db_err) => db_err.code().as_deref() == Some("57P03"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[tokio::test]
+    async fn connect_retries_until_attempts_run_out() {
+        // Nothing listens on port 1, so every attempt fails
+        let mut config = DatabaseConfig::new("postgres://postgres@127.0.0.1:1/alpha");
+        config.connect_retries = 2;
+        config.connect_backoff_ms = 20;
+        // Give up on each attempt at once instead of waiting inside the pool
+        config.acquire_timeout_secs = 0;
+
+        let started = Instant::now();
+        let result = Database::connect(&config).await;
+
+        assert!(matches!(result, Err(AppError::Database(_))));
+        // Two retries wait 20ms and then 40ms
+        assert!(started.elapsed() >= Duration::from_millis(60));
+    }
+
+    #[tokio::test]
+    async fn connect_does_not_retry_configuration_errors() {
+        let mut config = DatabaseConfig::new("mysql://localhost/alpha");
+        config.connect_backoff_ms = 10_000;
+
+        let result = Database::connect(&config).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use tokio::sync::broadcast;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+use super::presence::PresenceRegistry;
+use crate::models::{ChangeEvent, PresenceEntry, PresenceMode, ServerMessage};
+
+/// Number of messages a slow subscriber may fall behind before it starts
+/// skipping messages.
+const DEFAULT_CAPACITY: usize = 256;
+
+/// A broadcast message together with the connection that caused it, so that
+/// the originating connection can skip its own hints.
+#[derive(Debug, Clone)]
+pub struct HubMessage {
+    pub origin: Option<Uuid>,
+    pub message: ServerMessage,
+}
+
+/// In-process broadcast hub for change events and collaboration presence.
+///
+/// Cloning is cheap; all clones share the same channel and presence state.
+#[derive(Clone)]
+pub struct EventHub {
+    sender: broadcast::Sender<HubMessage>,
+    presence: Arc<PresenceRegistry>,
+    closed: CancellationToken,
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl EventHub {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            presence: Arc::new(PresenceRegistry::new()),
+            closed: CancellationToken::new(),
+        }
+    }
+
+    /// Ask subscribers to deliver what they have already received and then
+    /// disconnect. Used on shutdown.
+    pub fn close(&self) {
+        self.closed.cancel();
+    }
+
+    /// Resolve once [`close`](Self::close) has been called.
+    pub async fn closed(&self) {
+        self.closed.cancelled().await
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<HubMessage> {
+        self.sender.subscribe()
+    }
+
+    /// Publish a data change to every subscriber.
+    pub fn publish(&self, event: ChangeEvent) {
+        self.send(None, ServerMessage::Change(event));
+    }
+
+    /// Mark a connection as viewing or editing a ticket.
+    pub fn join(&self, conn_id: Uuid, ticket_id: Uuid, user: &str, mode: PresenceMode) {
+        if let Some(users) = self.presence.join(conn_id, ticket_id, user, mode) {
+            self.send(Some(conn_id), ServerMessage::Presence { ticket_id, users });
+        }
+    }
+
+    pub fn leave(&self, conn_id: Uuid, ticket_id: Uuid) {
+        if let Some(users) = self.presence.leave(conn_id, ticket_id) {
+            self.send(Some(conn_id), ServerMessage::Presence { ticket_id, users });
+        }
+    }
+
+    /// Drop every presence entry held by a closed connection.
+    pub fn disconnect(&self, conn_id: Uuid) {
+        for (ticket_id, users) in self.presence.disconnect(conn_id) {
+            self.send(Some(conn_id), ServerMessage::Presence { ticket_id, users });
+        }
+    }
+
+    /// Broadcast a typing hint. Typing implies the user is editing the ticket.
+    pub fn typing(&self, conn_id: Uuid, ticket_id: Uuid, user: &str, field: String) {
+        self.join(conn_id, ticket_id, user, PresenceMode::Editing);
+        self.send(
+            Some(conn_id),
+            ServerMessage::Typing {
+                ticket_id,
+                user: user.to_string(),
+                field,
+            },
+        );
+    }
+
+    pub fn presence(&self, ticket_id: Uuid) -> Vec<PresenceEntry> {
+        self.presence.users(ticket_id)
+    }
+
+    fn send(&self, origin: Option<Uuid>, message: ServerMessage) {
+        // Sending only fails when nobody is subscribed, which is fine.
+        let _ = self.sender.send(HubMessage { origin, message });
+    }
+}
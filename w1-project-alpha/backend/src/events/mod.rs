@@ -0,0 +1,7 @@
+pub mod hub;
+pub mod presence;
+
+pub use hub::*;
+pub use presence::*;
+
+pub use crate::models::{ChangeEvent, PresenceEntry, PresenceMode, ServerMessage};
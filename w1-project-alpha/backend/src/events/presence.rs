@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::models::{PresenceEntry, PresenceMode};
+
+#[derive(Debug, Clone)]
+struct Session {
+    conn_id: Uuid,
+    user: String,
+    mode: PresenceMode,
+}
+
+/// Tracks which connections are looking at which tickets.
+///
+/// Entries are keyed by connection rather than by user so that closing one
+/// browser tab does not hide a user who still has the ticket open elsewhere.
+#[derive(Debug, Default)]
+pub struct PresenceRegistry {
+    tickets: Mutex<HashMap<Uuid, Vec<Session>>>,
+}
+
+impl PresenceRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a connection on a ticket, or update its mode if already present.
+    ///
+    /// Returns the new presence list when it changed, `None` otherwise.
+    pub fn join(
+        &self,
+        conn_id: Uuid,
+        ticket_id: Uuid,
+        user: &str,
+        mode: PresenceMode,
+    ) -> Option<Vec<PresenceEntry>> {
+        let mut tickets = self.tickets.lock().expect("presence lock poisoned");
+        let sessions = tickets.entry(ticket_id).or_default();
+
+        match sessions.iter_mut().find(|s| s.conn_id == conn_id) {
+            Some(session) if session.mode == mode => return None,
+            Some(session) => session.mode = mode,
+            None => sessions.push(Session {
+                conn_id,
+                user: user.to_string(),
+                mode,
+            }),
+        }
+
+        Some(Self::snapshot(sessions))
+    }
+
+    /// Remove a connection from a ticket.
+    ///
+    /// Returns the new presence list when the connection was present.
+    pub fn leave(&self, conn_id: Uuid, ticket_id: Uuid) -> Option<Vec<PresenceEntry>> {
+        let mut tickets = self.tickets.lock().expect("presence lock poisoned");
+        let sessions = tickets.get_mut(&ticket_id)?;
+
+        let before = sessions.len();
+        sessions.retain(|s| s.conn_id != conn_id);
+        if sessions.len() == before {
+            return None;
+        }
+
+        let snapshot = Self::snapshot(sessions);
+        if sessions.is_empty() {
+            tickets.remove(&ticket_id);
+        }
+        Some(snapshot)
+    }
+
+    /// Remove a connection from every ticket it joined.
+    ///
+    /// Returns the new presence list of each affected ticket.
+    pub fn disconnect(&self, conn_id: Uuid) -> Vec<(Uuid, Vec<PresenceEntry>)> {
+        let mut tickets = self.tickets.lock().expect("presence lock poisoned");
+        let mut changed = Vec::new();
+
+        tickets.retain(|ticket_id, sessions| {
+            let before = sessions.len();
+            sessions.retain(|s| s.conn_id != conn_id);
+            if sessions.len() != before {
+                changed.push((*ticket_id, Self::snapshot(sessions)));
+            }
+            !sessions.is_empty()
+        });
+
+        changed
+    }
+
+    /// Current presence list for a ticket, empty when nobody is on it.
+    pub fn users(&self, ticket_id: Uuid) -> Vec<PresenceEntry> {
+        let tickets = self.tickets.lock().expect("presence lock poisoned");
+        tickets
+            .get(&ticket_id)
+            .map(|sessions| Self::snapshot(sessions))
+            .unwrap_or_default()
+    }
+
+    /// Collapse sessions into one entry per user, sorted by user name.
+    fn snapshot(sessions: &[Session]) -> Vec<PresenceEntry> {
+        let mut by_user: HashMap<&str, PresenceMode> = HashMap::new();
+        for session in sessions {
+            let mode = by_user.entry(&session.user).or_insert(session.mode);
+            *mode = (*mode).max(session.mode);
+        }
+
+        let mut entries: Vec<PresenceEntry> = by_user
+            .into_iter()
+            .map(|(user, mode)| PresenceEntry {
+                user: user.to_string(),
+                mode,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.user.cmp(&b.user));
+        entries
+    }
+}
@@ -0,0 +1,55 @@
+use async_graphql::dataloader::Loader;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::models::Tag;
+use crate::repositories::TicketStore;
+use crate::utils::error::AppError;
+
+/// Tags of tickets, read with one query for all tickets resolved together
+/// instead of one per ticket. Tickets without tags have no entry.
+pub struct TicketTagsLoader {
+    tickets: Arc<dyn TicketStore>,
+}
+
+impl TicketTagsLoader {
+    pub fn new(tickets: Arc<dyn TicketStore>) -> Self {
+        Self { tickets }
+    }
+}
+
+impl Loader<Uuid> for TicketTagsLoader {
+    type Value = Vec<Tag>;
+    type Error = Arc<AppError>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, Vec<Tag>>, Self::Error> {
+        self.tickets.find_tags(keys).await.map_err(Arc::new)
+    }
+}
+
+/// Number of tickets carrying each tag, from a single count over all tags.
+pub struct TagTicketCountLoader {
+    tickets: Arc<dyn TicketStore>,
+}
+
+impl TagTicketCountLoader {
+    pub fn new(tickets: Arc<dyn TicketStore>) -> Self {
+        Self { tickets }
+    }
+}
+
+impl Loader<Uuid> for TagTicketCountLoader {
+    type Value = i64;
+    type Error = Arc<AppError>;
+
+    async fn load(&self, keys: &[Uuid]) -> Result<HashMap<Uuid, i64>, Self::Error> {
+        let stats = self.tickets.stats().await.map_err(Arc::new)?;
+        Ok(stats
+            .by_tag
+            .into_iter()
+            .filter(|count| keys.contains(&count.tag_id))
+            .map(|count| (count.tag_id, count.tickets))
+            .collect())
+    }
+}
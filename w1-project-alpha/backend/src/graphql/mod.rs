@@ -0,0 +1,66 @@
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{EmptySubscription, Error, ErrorExtensions, Schema};
+use axum::http::StatusCode;
+
+use crate::middleware::RequestId;
+use crate::state::AppState;
+use crate::utils::error::AppError;
+
+pub mod loaders;
+pub mod mutation;
+pub mod query;
+pub mod types;
+
+pub use loaders::{TagTicketCountLoader, TicketTagsLoader};
+pub use mutation::MutationRoot;
+pub use query::QueryRoot;
+pub use types::{Tag, Ticket, TicketConnection};
+
+/// Where GraphQL requests are accepted and GraphiQL is served.
+pub const GRAPHQL_PATH: &str = "/graphql";
+
+/// Deepest accepted query. Tickets and tags link to each other, so without a
+/// limit a single query could nest connections without end.
+const MAX_DEPTH: usize = 10;
+
+pub type AlphaSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+/// The schema over the services of `state`.
+///
+/// Queries read through the same services as the REST handlers, and
+/// mutations apply the same validation and publish the same change events.
+pub fn schema(state: &AppState) -> AlphaSchema {
+    let tickets = state.repositories.ticket.clone();
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(state.services.ticket.clone())
+        .data(state.services.tag.clone())
+        .data(DataLoader::new(
+            TicketTagsLoader::new(tickets.clone()),
+            tokio::spawn,
+        ))
+        .data(DataLoader::new(
+            TagTicketCountLoader::new(tickets),
+            tokio::spawn,
+        ))
+        .limit_depth(MAX_DEPTH)
+        .finish()
+}
+
+/// The message a REST client would get, with a machine readable `code` and
+/// the request ID in the extensions.
+impl ErrorExtensions for AppError {
+    fn extend(&self) -> Error {
+        let (status, message) = self.status_and_message();
+        let code = match status {
+            StatusCode::NOT_FOUND => "NOT_FOUND",
+            StatusCode::BAD_REQUEST => "BAD_USER_INPUT",
+            _ => "INTERNAL_SERVER_ERROR",
+        };
+        Error::new(message).extend_with(|_, extensions| {
+            extensions.set("code", code);
+            if let Some(id) = RequestId::current() {
+                extensions.set("requestId", id.0);
+            }
+        })
+    }
+}
@@ -0,0 +1,173 @@
+use async_graphql::{Context, ErrorExtensions, InputObject, Object, Result};
+use uuid::Uuid;
+
+use super::types::{Tag, Ticket};
+use crate::models::{CreateTagRequest, CreateTicketRequest, UpdateTagRequest, UpdateTicketRequest};
+use crate::services::{TagService, TicketService};
+
+#[derive(InputObject)]
+pub struct CreateTicketInput {
+    pub title: String,
+    pub description: Option<String>,
+    pub tag_ids: Option<Vec<Uuid>>,
+}
+
+impl From<CreateTicketInput> for CreateTicketRequest {
+    fn from(input: CreateTicketInput) -> Self {
+        Self {
+            title: input.title,
+            description: input.description,
+            tag_ids: input.tag_ids,
+        }
+    }
+}
+
+/// Fields left out are kept.
+#[derive(InputObject)]
+pub struct UpdateTicketInput {
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub completed: Option<bool>,
+}
+
+impl From<UpdateTicketInput> for UpdateTicketRequest {
+    fn from(input: UpdateTicketInput) -> Self {
+        Self {
+            title: input.title,
+            description: input.description,
+            completed: input.completed,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct CreateTagInput {
+    pub name: String,
+    /// `#RRGGBB` or `#RGB`
+    pub color: Option<String>,
+}
+
+impl From<CreateTagInput> for CreateTagRequest {
+    fn from(input: CreateTagInput) -> Self {
+        Self {
+            name: input.name,
+            color: input.color,
+        }
+    }
+}
+
+/// Fields left out are kept.
+#[derive(InputObject)]
+pub struct UpdateTagInput {
+    pub name: Option<String>,
+    pub color: Option<String>,
+}
+
+impl From<UpdateTagInput> for UpdateTagRequest {
+    fn from(input: UpdateTagInput) -> Self {
+        Self {
+            name: input.name,
+            color: input.color,
+        }
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn create_ticket(&self, ctx: &Context<'_>, input: CreateTicketInput) -> Result<Ticket> {
+        let ticket = tickets(ctx)
+            .create(input.into())
+            .await
+            .map_err(|err| err.extend())?;
+        Ok(ticket.into())
+    }
+
+    async fn update_ticket(
+        &self,
+        ctx: &Context<'_>,
+        id: Uuid,
+        input: UpdateTicketInput,
+    ) -> Result<Ticket> {
+        let ticket = tickets(ctx)
+            .update(id, input.into())
+            .await
+            .map_err(|err| err.extend())?;
+        Ok(ticket.into())
+    }
+
+    async fn toggle_ticket_completed(&self, ctx: &Context<'_>, id: Uuid) -> Result<Ticket> {
+        let ticket = tickets(ctx)
+            .toggle_completed(id)
+            .await
+            .map_err(|err| err.extend())?;
+        Ok(ticket.into())
+    }
+
+    /// Returns the id of the deleted ticket
+    async fn delete_ticket(&self, ctx: &Context<'_>, id: Uuid) -> Result<Uuid> {
+        tickets(ctx).delete(id).await.map_err(|err| err.extend())?;
+        Ok(id)
+    }
+
+    async fn add_tag_to_ticket(
+        &self,
+        ctx: &Context<'_>,
+        ticket_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<Ticket> {
+        let service = tickets(ctx);
+        service
+            .add_tag(ticket_id, tag_id)
+            .await
+            .map_err(|err| err.extend())?;
+        let ticket = service.get(ticket_id).await.map_err(|err| err.extend())?;
+        Ok(ticket.into())
+    }
+
+    async fn remove_tag_from_ticket(
+        &self,
+        ctx: &Context<'_>,
+        ticket_id: Uuid,
+        tag_id: Uuid,
+    ) -> Result<Ticket> {
+        let service = tickets(ctx);
+        service
+            .remove_tag(ticket_id, tag_id)
+            .await
+            .map_err(|err| err.extend())?;
+        let ticket = service.get(ticket_id).await.map_err(|err| err.extend())?;
+        Ok(ticket.into())
+    }
+
+    async fn create_tag(&self, ctx: &Context<'_>, input: CreateTagInput) -> Result<Tag> {
+        let tag = tags(ctx)
+            .create(input.into())
+            .await
+            .map_err(|err| err.extend())?;
+        Ok(Tag(tag))
+    }
+
+    async fn update_tag(&self, ctx: &Context<'_>, id: Uuid, input: UpdateTagInput) -> Result<Tag> {
+        let tag = tags(ctx)
+            .update(id, input.into())
+            .await
+            .map_err(|err| err.extend())?;
+        Ok(Tag(tag))
+    }
+
+    /// Deletes the tag and unlinks it from every ticket; returns its id
+    async fn delete_tag(&self, ctx: &Context<'_>, id: Uuid) -> Result<Uuid> {
+        tags(ctx).delete(id).await.map_err(|err| err.extend())?;
+        Ok(id)
+    }
+}
+
+fn tickets<'a>(ctx: &Context<'a>) -> &'a TicketService {
+    ctx.data_unchecked::<TicketService>()
+}
+
+fn tags<'a>(ctx: &Context<'a>) -> &'a TagService {
+    ctx.data_unchecked::<TagService>()
+}
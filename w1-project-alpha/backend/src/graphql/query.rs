@@ -0,0 +1,145 @@
+use async_graphql::connection::{self, Connection, Edge, OpaqueCursor};
+use async_graphql::{Context, ErrorExtensions, Object, Result};
+use uuid::Uuid;
+
+use super::types::{ConnectionFields, Tag, Ticket, TicketConnection};
+use crate::models::{TicketFilter, MAX_PER_PAGE};
+use crate::services::{TagService, TicketService};
+use crate::utils::error::{self, AppError};
+
+/// Page size when neither `first` nor `last` is given.
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Tickets matching the filters, newest first
+    #[allow(clippy::too_many_arguments)]
+    async fn tickets(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(desc = "Only tickets carrying this tag")] tag: Option<Uuid>,
+        #[graphql(desc = "Case-insensitive match on the title")] search: Option<String>,
+        completed: Option<bool>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<TicketConnection> {
+        let filter = TicketFilter {
+            tag,
+            search,
+            completed,
+        };
+        ticket_connection(ctx, filter, after, before, first, last).await
+    }
+
+    async fn ticket(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<Ticket>> {
+        match ctx.data_unchecked::<TicketService>().get(id).await {
+            Ok(ticket) => Ok(Some(ticket.into())),
+            Err(AppError::NotFound(_)) => Ok(None),
+            Err(err) => Err(err.extend()),
+        }
+    }
+
+    /// Every tag, sorted by name
+    async fn tags(&self, ctx: &Context<'_>) -> Result<Vec<Tag>> {
+        let tags = ctx
+            .data_unchecked::<TagService>()
+            .list()
+            .await
+            .map_err(|err| err.extend())?;
+        Ok(tags.into_iter().map(Tag).collect())
+    }
+
+    async fn tag(&self, ctx: &Context<'_>, id: Uuid) -> Result<Option<Tag>> {
+        match ctx.data_unchecked::<TagService>().get(id).await {
+            Ok(tag) => Ok(Some(Tag(tag))),
+            Err(AppError::NotFound(_)) => Ok(None),
+            Err(err) => Err(err.extend()),
+        }
+    }
+}
+
+/// A Relay connection over the tickets matching `filter`, paged by offset.
+///
+/// Each edge's cursor is its position in the filtered list, so `after` and
+/// `before` select a range of positions, and `first` or `last` take that
+/// many from its start or end.
+pub(crate) async fn ticket_connection(
+    ctx: &Context<'_>,
+    filter: TicketFilter,
+    after: Option<String>,
+    before: Option<String>,
+    first: Option<i32>,
+    last: Option<i32>,
+) -> Result<TicketConnection> {
+    let service = ctx.data_unchecked::<TicketService>();
+    connection::query(
+        after,
+        before,
+        first,
+        last,
+        |after: Option<OpaqueCursor<i64>>, before: Option<OpaqueCursor<i64>>, first, last| async move {
+            let after = after.map(|cursor| cursor.0);
+            let before = before.map(|cursor| cursor.0);
+            ticket_range(service, &filter, after, before, first, last)
+                .await
+                .map_err(|err| err.extend())
+        },
+    )
+    .await
+}
+
+async fn ticket_range(
+    service: &TicketService,
+    filter: &TicketFilter,
+    after: Option<i64>,
+    before: Option<i64>,
+    first: Option<usize>,
+    last: Option<usize>,
+) -> error::Result<TicketConnection> {
+    if first.is_some() && last.is_some() {
+        return Err(AppError::Validation(
+            "Pass either first or last, not both".to_string(),
+        ));
+    }
+    let size = first.or(last).unwrap_or(DEFAULT_PAGE_SIZE);
+    if size > MAX_PER_PAGE as usize {
+        return Err(AppError::Validation(format!(
+            "first and last must not exceed {}",
+            MAX_PER_PAGE
+        )));
+    }
+    let size = size as i64;
+
+    // Cursors are opaque to clients but not tamper-proof
+    let start = after.map_or(0, |position| position + 1).max(0);
+    let end = match before {
+        Some(position) => position,
+        // Counting from the end needs to know where the end is
+        None if last.is_some() => service.list_range(filter, 0, 0).await?.1,
+        None => start + size,
+    };
+    let (offset, limit) = if last.is_some() {
+        let offset = (end - size).max(start);
+        (offset, end - offset)
+    } else {
+        (start, size.min(end - start))
+    };
+
+    let (tickets, total) = service.list_range(filter, offset, limit.max(0)).await?;
+    let mut connection = Connection::with_additional_fields(
+        offset > 0,
+        offset + (tickets.len() as i64) < total,
+        ConnectionFields { total_count: total },
+    );
+    connection.edges.extend(
+        tickets
+            .into_iter()
+            .zip(offset..)
+            .map(|(ticket, position)| Edge::new(OpaqueCursor(position), Ticket::from(ticket))),
+    );
+    Ok(connection)
+}
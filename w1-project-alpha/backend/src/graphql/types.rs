@@ -0,0 +1,134 @@
+use async_graphql::connection::{Connection, EmptyFields, OpaqueCursor};
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, ErrorExtensions, Object, Result, SimpleObject};
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+use super::loaders::{TagTicketCountLoader, TicketTagsLoader};
+use super::query::ticket_connection;
+use crate::models::{self, TicketFilter, TicketWithTags};
+
+/// A page of tickets. Cursors are positions in the filtered list.
+pub type TicketConnection = Connection<OpaqueCursor<i64>, Ticket, ConnectionFields, EmptyFields>;
+
+#[derive(SimpleObject)]
+pub struct ConnectionFields {
+    /// Tickets matching the filters across all pages
+    pub total_count: i64,
+}
+
+/// A ticket. Its tags are loaded together with those of the other tickets in
+/// the response, unless they were read along with the ticket.
+pub struct Ticket {
+    ticket: models::Ticket,
+    tags: Option<Vec<models::Tag>>,
+}
+
+impl From<models::Ticket> for Ticket {
+    fn from(ticket: models::Ticket) -> Self {
+        Self { ticket, tags: None }
+    }
+}
+
+impl From<TicketWithTags> for Ticket {
+    fn from(ticket: TicketWithTags) -> Self {
+        Self {
+            ticket: ticket.ticket,
+            tags: Some(ticket.tags),
+        }
+    }
+}
+
+#[Object]
+impl Ticket {
+    async fn id(&self) -> Uuid {
+        self.ticket.id
+    }
+
+    async fn title(&self) -> &str {
+        &self.ticket.title
+    }
+
+    async fn description(&self) -> Option<&str> {
+        self.ticket.description.as_deref()
+    }
+
+    async fn completed(&self) -> bool {
+        self.ticket.completed
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.ticket.created_at
+    }
+
+    async fn updated_at(&self) -> DateTime<Utc> {
+        self.ticket.updated_at
+    }
+
+    /// Tags of the ticket, sorted by name
+    async fn tags(&self, ctx: &Context<'_>) -> Result<Vec<Tag>> {
+        let tags = match &self.tags {
+            Some(tags) => tags.clone(),
+            None => ctx
+                .data_unchecked::<DataLoader<TicketTagsLoader>>()
+                .load_one(self.ticket.id)
+                .await
+                .map_err(|err| err.extend())?
+                .unwrap_or_default(),
+        };
+        Ok(tags.into_iter().map(Tag).collect())
+    }
+}
+
+/// A tag that can be attached to tickets.
+pub struct Tag(pub models::Tag);
+
+#[Object]
+impl Tag {
+    async fn id(&self) -> Uuid {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    /// `#RRGGBB` or `#RGB`
+    async fn color(&self) -> Option<&str> {
+        self.0.color.as_deref()
+    }
+
+    async fn created_at(&self) -> DateTime<Utc> {
+        self.0.created_at
+    }
+
+    /// How many tickets carry the tag
+    async fn ticket_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        let count = ctx
+            .data_unchecked::<DataLoader<TagTicketCountLoader>>()
+            .load_one(self.0.id)
+            .await
+            .map_err(|err| err.extend())?;
+        Ok(count.unwrap_or_default())
+    }
+
+    /// Tickets carrying the tag, newest first
+    #[allow(clippy::too_many_arguments)]
+    async fn tickets(
+        &self,
+        ctx: &Context<'_>,
+        search: Option<String>,
+        completed: Option<bool>,
+        after: Option<String>,
+        before: Option<String>,
+        first: Option<i32>,
+        last: Option<i32>,
+    ) -> Result<TicketConnection> {
+        let filter = TicketFilter {
+            tag: Some(self.0.id),
+            search,
+            completed,
+        };
+        ticket_connection(ctx, filter, after, before, first, last).await
+    }
+}
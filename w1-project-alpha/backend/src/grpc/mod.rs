@@ -0,0 +1,101 @@
+use axum::http::StatusCode;
+use axum::Router;
+use chrono::{DateTime, Utc};
+use prost_types::Timestamp;
+use tonic::server::NamedService;
+use tonic::Status;
+use uuid::Uuid;
+
+use crate::state::AppState;
+use crate::utils::error::AppError;
+
+pub mod tags;
+pub mod tickets;
+
+pub use tags::TagGrpc;
+pub use tickets::TicketGrpc;
+
+/// Messages, servers and clients generated from `proto/alpha/v1`.
+pub mod pb {
+    tonic::include_proto!("alpha.v1");
+}
+
+use pb::tag_service_server::TagServiceServer;
+use pb::ticket_service_server::TicketServiceServer;
+
+/// The gRPC services over the services of `state`, routed by their
+/// `/alpha.v1.<Service>/<Method>` paths.
+///
+/// They share validation, errors and change events with the REST handlers,
+/// and can be merged into the HTTP router, which speaks HTTP/2 as well, or
+/// served on a port of their own.
+pub fn grpc_routes(state: &AppState) -> Router {
+    let tickets = TicketServiceServer::new(TicketGrpc::new(
+        state.services.ticket.clone(),
+        state.events.clone(),
+    ));
+    let tags = TagServiceServer::new(TagGrpc::new(state.services.tag.clone()));
+    Router::new()
+        .route_service(&service_path(&tickets), tickets)
+        .route_service(&service_path(&tags), tags)
+}
+
+fn service_path<S: NamedService>(_: &S) -> String {
+    format!("/{}/{{*method}}", S::NAME)
+}
+
+/// The message a REST client would get, with the matching gRPC code.
+impl From<AppError> for Status {
+    fn from(err: AppError) -> Self {
+        let (status, message) = err.status_and_message();
+        match status {
+            StatusCode::NOT_FOUND => Status::not_found(message),
+            StatusCode::BAD_REQUEST => Status::invalid_argument(message),
+            _ => Status::internal(message),
+        }
+    }
+}
+
+/// Parse an id sent as a string; `field` names it in the error.
+fn parse_id(field: &str, value: &str) -> Result<Uuid, Status> {
+    Uuid::parse_str(value)
+        .map_err(|_| Status::invalid_argument(format!("{} '{}' is not a valid UUID", field, value)))
+}
+
+fn timestamp(time: DateTime<Utc>) -> Option<Timestamp> {
+    Some(Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tonic::Code;
+
+    #[test]
+    fn app_errors_map_to_grpc_codes() {
+        let status = Status::from(AppError::NotFound("Ticket missing".to_string()));
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(status.message(), "Ticket missing");
+
+        let status = Status::from(AppError::Validation("title must not be empty".to_string()));
+        assert_eq!(status.code(), Code::InvalidArgument);
+
+        // Internal details stay in the server log
+        let status = Status::from(AppError::Internal("disk on fire".to_string()));
+        assert_eq!(status.code(), Code::Internal);
+        assert!(!status.message().contains("disk"));
+    }
+
+    #[test]
+    fn parse_id_rejects_malformed_ids() {
+        let id = Uuid::new_v4();
+        assert_eq!(parse_id("id", &id.to_string()).unwrap(), id);
+
+        let status = parse_id("ticket_id", "42").unwrap_err();
+        assert_eq!(status.code(), Code::InvalidArgument);
+        assert!(status.message().starts_with("ticket_id '42'"));
+    }
+}
@@ -0,0 +1,93 @@
+use tonic::{Request, Response, Status};
+
+use super::pb::{self, tag_service_server::TagService as TagRpc};
+use super::{parse_id, timestamp};
+use crate::models::{CreateTagRequest, Tag, UpdateTagRequest};
+use crate::services::TagService;
+
+impl From<Tag> for pb::Tag {
+    fn from(tag: Tag) -> Self {
+        Self {
+            id: tag.id.to_string(),
+            name: tag.name,
+            color: tag.color,
+            created_at: timestamp(tag.created_at),
+        }
+    }
+}
+
+/// `alpha.v1.TagService` over [`TagService`].
+pub struct TagGrpc {
+    service: TagService,
+}
+
+impl TagGrpc {
+    pub fn new(service: TagService) -> Self {
+        Self { service }
+    }
+}
+
+#[tonic::async_trait]
+impl TagRpc for TagGrpc {
+    async fn list_tags(
+        &self,
+        _request: Request<pb::ListTagsRequest>,
+    ) -> Result<Response<pb::ListTagsResponse>, Status> {
+        let tags = self.service.list().await?;
+        Ok(Response::new(pb::ListTagsResponse {
+            tags: tags.into_iter().map(Into::into).collect(),
+        }))
+    }
+
+    async fn get_tag(
+        &self,
+        request: Request<pb::GetTagRequest>,
+    ) -> Result<Response<pb::Tag>, Status> {
+        let id = parse_id("id", &request.into_inner().id)?;
+        let tag = self.service.get(id).await?;
+        Ok(Response::new(tag.into()))
+    }
+
+    async fn create_tag(
+        &self,
+        request: Request<pb::CreateTagRequest>,
+    ) -> Result<Response<pb::Tag>, Status> {
+        let request = request.into_inner();
+        let tag = self
+            .service
+            .create(CreateTagRequest {
+                name: request.name,
+                color: request.color,
+            })
+            .await?;
+        Ok(Response::new(tag.into()))
+    }
+
+    async fn update_tag(
+        &self,
+        request: Request<pb::UpdateTagRequest>,
+    ) -> Result<Response<pb::Tag>, Status> {
+        let request = request.into_inner();
+        let id = parse_id("id", &request.id)?;
+        let tag = self
+            .service
+            .update(
+                id,
+                UpdateTagRequest {
+                    name: request.name,
+                    color: request.color,
+                },
+            )
+            .await?;
+        Ok(Response::new(tag.into()))
+    }
+
+    async fn delete_tag(
+        &self,
+        request: Request<pb::DeleteTagRequest>,
+    ) -> Result<Response<()>, Status> {
+        let id = parse_id("id", &request.into_inner().id)?;
+        self.service.delete(id).await?;
+        Ok(Response::new(()))
+    }
+}
@@ -0,0 +1,250 @@
+use futures_util::stream::BoxStream;
+use tokio::sync::broadcast::error::RecvError;
+use tonic::{Request, Response, Status};
+use uuid::Uuid;
+
+use super::pb::{self, ticket_event::Event, ticket_service_server::TicketService as TicketRpc};
+use super::{parse_id, timestamp};
+use crate::events::{ChangeEvent, EventHub, HubMessage, ServerMessage};
+use crate::models::{
+    CreateTicketRequest, PageParams, Ticket, TicketFilter, TicketWithTags, UpdateTicketRequest,
+};
+use crate::services::TicketService;
+
+impl From<Ticket> for pb::Ticket {
+    fn from(ticket: Ticket) -> Self {
+        Self {
+            id: ticket.id.to_string(),
+            title: ticket.title,
+            description: ticket.description,
+            completed: ticket.completed,
+            tags: Vec::new(),
+            created_at: timestamp(ticket.created_at),
+            updated_at: timestamp(ticket.updated_at),
+        }
+    }
+}
+
+impl From<TicketWithTags> for pb::Ticket {
+    fn from(TicketWithTags { ticket, tags }: TicketWithTags) -> Self {
+        Self {
+            tags: tags.into_iter().map(Into::into).collect(),
+            ..ticket.into()
+        }
+    }
+}
+
+/// `alpha.v1.TicketService` over [`TicketService`], with change events read
+/// from the [`EventHub`].
+pub struct TicketGrpc {
+    service: TicketService,
+    events: EventHub,
+}
+
+impl TicketGrpc {
+    pub fn new(service: TicketService, events: EventHub) -> Self {
+        Self { service, events }
+    }
+
+    /// The ticket as stored after a mutation, tags included.
+    async fn reload(&self, ticket: Ticket) -> Result<Response<pb::Ticket>, Status> {
+        let ticket = self.service.get(ticket.id).await?;
+        Ok(Response::new(ticket.into()))
+    }
+}
+
+#[tonic::async_trait]
+impl TicketRpc for TicketGrpc {
+    async fn list_tickets(
+        &self,
+        request: Request<pb::ListTicketsRequest>,
+    ) -> Result<Response<pb::ListTicketsResponse>, Status> {
+        let request = request.into_inner();
+        let filter = TicketFilter {
+            tag: request.tag.map(|tag| parse_id("tag", &tag)).transpose()?,
+            search: request.search,
+            completed: request.completed,
+        };
+        // Unset fields arrive as 0 and mean the REST defaults
+        let defaults = PageParams::default();
+        let params = PageParams {
+            page: match request.page {
+                0 => defaults.page,
+                page => page,
+            },
+            per_page: match request.per_page {
+                0 => defaults.per_page,
+                per_page => per_page,
+            },
+        };
+
+        let page = self.service.list_page(&filter, params).await?;
+        Ok(Response::new(pb::ListTicketsResponse {
+            tickets: page.items.into_iter().map(Into::into).collect(),
+            page: page.page,
+            per_page: page.per_page,
+            total: page.total,
+            total_pages: page.total_pages,
+        }))
+    }
+
+    async fn get_ticket(
+        &self,
+        request: Request<pb::GetTicketRequest>,
+    ) -> Result<Response<pb::Ticket>, Status> {
+        let id = parse_id("id", &request.into_inner().id)?;
+        let ticket = self.service.get(id).await?;
+        Ok(Response::new(ticket.into()))
+    }
+
+    async fn create_ticket(
+        &self,
+        request: Request<pb::CreateTicketRequest>,
+    ) -> Result<Response<pb::Ticket>, Status> {
+        let request = request.into_inner();
+        let tag_ids = request
+            .tag_ids
+            .iter()
+            .map(|id| parse_id("tag_ids", id))
+            .collect::<Result<Vec<_>, _>>()?;
+        let ticket = self
+            .service
+            .create(CreateTicketRequest {
+                title: request.title,
+                description: request.description,
+                tag_ids: (!tag_ids.is_empty()).then_some(tag_ids),
+            })
+            .await?;
+        self.reload(ticket).await
+    }
+
+    async fn update_ticket(
+        &self,
+        request: Request<pb::UpdateTicketRequest>,
+    ) -> Result<Response<pb::Ticket>, Status> {
+        let request = request.into_inner();
+        let id = parse_id("id", &request.id)?;
+        let ticket = self
+            .service
+            .update(
+                id,
+                UpdateTicketRequest {
+                    title: request.title,
+                    description: request.description,
+                    completed: request.completed,
+                },
+            )
+            .await?;
+        self.reload(ticket).await
+    }
+
+    async fn toggle_ticket_completed(
+        &self,
+        request: Request<pb::ToggleTicketCompletedRequest>,
+    ) -> Result<Response<pb::Ticket>, Status> {
+        let id = parse_id("id", &request.into_inner().id)?;
+        let ticket = self.service.toggle_completed(id).await?;
+        self.reload(ticket).await
+    }
+
+    async fn delete_ticket(
+        &self,
+        request: Request<pb::DeleteTicketRequest>,
+    ) -> Result<Response<()>, Status> {
+        let id = parse_id("id", &request.into_inner().id)?;
+        self.service.delete(id).await?;
+        Ok(Response::new(()))
+    }
+
+    async fn add_ticket_tag(
+        &self,
+        request: Request<pb::TicketTagRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+        let ticket_id = parse_id("ticket_id", &request.ticket_id)?;
+        let tag_id = parse_id("tag_id", &request.tag_id)?;
+        self.service.add_tag(ticket_id, tag_id).await?;
+        Ok(Response::new(()))
+    }
+
+    async fn remove_ticket_tag(
+        &self,
+        request: Request<pb::TicketTagRequest>,
+    ) -> Result<Response<()>, Status> {
+        let request = request.into_inner();
+        let ticket_id = parse_id("ticket_id", &request.ticket_id)?;
+        let tag_id = parse_id("tag_id", &request.tag_id)?;
+        self.service.remove_tag(ticket_id, tag_id).await?;
+        Ok(Response::new(()))
+    }
+
+    type WatchTicketsStream = BoxStream<'static, Result<pb::TicketEvent, Status>>;
+
+    /// Subscribes before responding, so every change made after the client
+    /// got the response headers is delivered. The stream ends when the
+    /// server shuts down.
+    async fn watch_tickets(
+        &self,
+        request: Request<pb::WatchTicketsRequest>,
+    ) -> Result<Response<Self::WatchTicketsStream>, Status> {
+        let only = request
+            .into_inner()
+            .ticket_id
+            .map(|id| parse_id("ticket_id", &id))
+            .transpose()?;
+        let mut receiver = self.events.subscribe();
+        let events = self.events.clone();
+
+        let stream = async_stream::stream! {
+            loop {
+                let message = tokio::select! {
+                    message = receiver.recv() => message,
+                    _ = events.closed() => break,
+                };
+                match message {
+                    Ok(HubMessage {
+                        message: ServerMessage::Change(change),
+                        ..
+                    }) => {
+                        if let Some(event) = ticket_event(change, only) {
+                            yield Ok(event);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(skipped)) => {
+                        tracing::warn!("gRPC ticket watcher lagged, skipped {} messages", skipped);
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        };
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// The event for a change to a ticket, if it concerns `only` (or any ticket
+/// when `None`); tag changes elsewhere are not ticket events.
+fn ticket_event(change: ChangeEvent, only: Option<Uuid>) -> Option<pb::TicketEvent> {
+    let tag_change = |ticket_id: Uuid, tag_id: Uuid| pb::TicketTagChange {
+        ticket_id: ticket_id.to_string(),
+        tag_id: tag_id.to_string(),
+    };
+    let (id, event) = match change {
+        ChangeEvent::TicketCreated { ticket } => (ticket.id, Event::Created(ticket.into())),
+        ChangeEvent::TicketUpdated { ticket } => (ticket.id, Event::Updated(ticket.into())),
+        ChangeEvent::TicketDeleted { id } => (id, Event::Deleted(id.to_string())),
+        ChangeEvent::TicketTagAdded { ticket_id, tag_id } => {
+            (ticket_id, Event::TagAdded(tag_change(ticket_id, tag_id)))
+        }
+        ChangeEvent::TicketTagRemoved { ticket_id, tag_id } => {
+            (ticket_id, Event::TagRemoved(tag_change(ticket_id, tag_id)))
+        }
+        ChangeEvent::TagCreated { .. }
+        | ChangeEvent::TagUpdated { .. }
+        | ChangeEvent::TagDeleted { .. } => return None,
+    };
+    if only.is_some_and(|only| only != id) {
+        return None;
+    }
+    Some(pb::TicketEvent { event: Some(event) })
+}
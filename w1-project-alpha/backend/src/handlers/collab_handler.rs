@@ -0,0 +1,156 @@
+use axum::{
+    extract::{
+        ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+        Path, Query, State,
+    },
+    response::Response,
+    Json,
+};
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{
+    events::{EventHub, HubMessage, PresenceEntry, PresenceMode, ServerMessage},
+    utils::error::{AppError, ErrorResponse, Result},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CollabParams {
+    /// Display name shown to other users
+    pub user: String,
+}
+
+/// Messages sent by WebSocket clients.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Join {
+        ticket_id: Uuid,
+        #[serde(default)]
+        mode: PresenceMode,
+    },
+    Leave {
+        ticket_id: Uuid,
+    },
+    Typing {
+        ticket_id: Uuid,
+        field: String,
+    },
+}
+
+/// Upgrade to the collaboration socket.
+///
+/// The socket receives every change event plus presence and typing hints for
+/// all tickets; clients filter by `ticket_id`.
+#[utoipa::path(
+    get,
+    path = "/ws",
+    tag = "collaboration",
+    params(CollabParams),
+    responses(
+        (status = 101, description = "Switched to the WebSocket protocol"),
+        (status = 400, description = "Empty user name", body = ErrorResponse),
+    )
+)]
+pub async fn collab_ws(
+    ws: WebSocketUpgrade,
+    Query(params): Query<CollabParams>,
+    State(events): State<EventHub>,
+) -> Result<Response> {
+    let user = params.user.trim().to_string();
+    if user.is_empty() {
+        return Err(AppError::Validation("user must not be empty".to_string()));
+    }
+
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, events, user)))
+}
+
+/// Current presence on a ticket, for clients that have not opened the socket yet.
+#[utoipa::path(
+    get,
+    path = "/tickets/{id}/presence",
+    tag = "collaboration",
+    params(("id" = Uuid, Path, description = "Ticket id")),
+    responses((status = 200, description = "Users viewing or editing the ticket", body = [PresenceEntry]))
+)]
+pub async fn get_ticket_presence(
+    Path(id): Path<Uuid>,
+    State(events): State<EventHub>,
+) -> Json<Vec<PresenceEntry>> {
+    Json(events.presence(id))
+}
+
+async fn handle_socket(mut socket: WebSocket, events: EventHub, user: String) {
+    let conn_id = Uuid::new_v4();
+    let mut rx = events.subscribe();
+
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    match serde_json::from_str::<ClientMessage>(&text) {
+                        Ok(message) => apply(&events, conn_id, &user, message),
+                        Err(err) => tracing::debug!("Ignoring invalid collab message: {}", err),
+                    }
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+            outgoing = rx.recv() => match outgoing {
+                Ok(hub_message) => {
+                    if !forward(&mut socket, conn_id, hub_message).await {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!("Collab connection {} skipped {} messages", conn_id, skipped);
+                }
+                Err(RecvError::Closed) => break,
+            },
+            _ = events.closed() => {
+                // Flush what was published before shutdown, then say goodbye
+                while let Ok(hub_message) = rx.try_recv() {
+                    if !forward(&mut socket, conn_id, hub_message).await {
+                        break;
+                    }
+                }
+                let _ = socket
+                    .send(Message::Close(Some(CloseFrame {
+                        code: close_code::AWAY,
+                        reason: "server shutting down".into(),
+                    })))
+                    .await;
+                break;
+            }
+        }
+    }
+
+    events.disconnect(conn_id);
+}
+
+/// Send a hub message to the socket. Returns `false` once the socket is gone.
+async fn forward(socket: &mut WebSocket, conn_id: Uuid, hub_message: HubMessage) -> bool {
+    // Do not echo typing hints back to the connection that sent them
+    if hub_message.origin == Some(conn_id)
+        && matches!(hub_message.message, ServerMessage::Typing { .. })
+    {
+        return true;
+    }
+    let Ok(json) = serde_json::to_string(&hub_message.message) else {
+        return true;
+    };
+    socket.send(Message::Text(json.into())).await.is_ok()
+}
+
+fn apply(events: &EventHub, conn_id: Uuid, user: &str, message: ClientMessage) {
+    match message {
+        ClientMessage::Join { ticket_id, mode } => events.join(conn_id, ticket_id, user, mode),
+        ClientMessage::Leave { ticket_id } => events.leave(conn_id, ticket_id),
+        ClientMessage::Typing { ticket_id, field } => {
+            events.typing(conn_id, ticket_id, user, field)
+        }
+    }
+}
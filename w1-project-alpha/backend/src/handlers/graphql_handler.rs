@@ -0,0 +1,18 @@
+use async_graphql::http::GraphiQLSource;
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{extract::State, response::Html};
+
+use crate::graphql::{AlphaSchema, GRAPHQL_PATH};
+
+/// Execute a GraphQL query or mutation.
+pub async fn execute_graphql(
+    State(schema): State<AlphaSchema>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    schema.execute(request.into_inner()).await.into()
+}
+
+/// GraphiQL, for exploring the schema and trying out queries.
+pub async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint(GRAPHQL_PATH).finish())
+}
@@ -0,0 +1,100 @@
+use axum::{extract::State, http::StatusCode, Json};
+use std::time::{Duration, Instant};
+
+use crate::{
+    db::Database,
+    models::{
+        DatabaseCheck, HealthResponse, MigrationCheck, PoolCheck, ReadinessResponse,
+        VersionResponse,
+    },
+    utils::error::Result,
+};
+
+/// How long `/readyz` waits for the database before reporting not ready.
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Pool usage at or above this fraction is flagged as saturated.
+const SATURATION_THRESHOLD: f64 = 0.9;
+
+/// Liveness: the process is up and serving requests.
+pub async fn healthz() -> Json<HealthResponse> {
+    Json(HealthResponse {
+        status: "ok".to_string(),
+    })
+}
+
+/// Readiness: the database answers and the schema is fully migrated.
+/// Responds with 503 otherwise so load balancers stop routing traffic here.
+pub async fn readyz(State(database): State<Database>) -> (StatusCode, Json<ReadinessResponse>) {
+    let started = Instant::now();
+    let database_check = match tokio::time::timeout(PING_TIMEOUT, database.ping()).await {
+        Ok(Ok(())) => DatabaseCheck {
+            ok: true,
+            latency_ms: Some(started.elapsed().as_millis() as u64),
+            error: None,
+        },
+        Ok(Err(e)) => DatabaseCheck {
+            ok: false,
+            latency_ms: None,
+            error: Some(e.to_string()),
+        },
+        Err(_) => DatabaseCheck {
+            ok: false,
+            latency_ms: None,
+            error: Some(format!("ping timed out after {:?}", PING_TIMEOUT)),
+        },
+    };
+
+    let migrations = if database_check.ok {
+        match database.pending_migrations().await {
+            Ok(pending) => MigrationCheck {
+                ok: pending.is_empty(),
+                pending,
+                error: None,
+            },
+            Err(e) => MigrationCheck {
+                ok: false,
+                pending: Vec::new(),
+                error: Some(e.to_string()),
+            },
+        }
+    } else {
+        MigrationCheck {
+            ok: false,
+            pending: Vec::new(),
+            error: Some("database unavailable".to_string()),
+        }
+    };
+
+    let status = database.pool_status();
+    let pool = PoolCheck {
+        saturated: status.saturation >= SATURATION_THRESHOLD,
+        status,
+    };
+
+    let ready = database_check.ok && migrations.ok;
+    let code = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        code,
+        Json(ReadinessResponse {
+            status: if ready { "ready" } else { "not_ready" }.to_string(),
+            database: database_check,
+            migrations,
+            pool,
+        }),
+    )
+}
+
+/// Build information: crate version, git commit and applied migrations.
+pub async fn version(State(database): State<Database>) -> Result<Json<VersionResponse>> {
+    let migrations = database.applied_migrations().await?;
+    Ok(Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        git_sha: env!("GIT_SHA").to_string(),
+        migrations,
+    }))
+}
@@ -0,0 +1,25 @@
+use axum::{extract::State, http::header, response::IntoResponse};
+
+use crate::{
+    db::Database,
+    metrics::{self, Metrics},
+    repositories::Repositories,
+    utils::error::Result,
+};
+
+/// Prometheus scrape endpoint. Pool and ticket gauges are refreshed on each
+/// scrape; if the ticket counts cannot be read, the last values are kept.
+pub async fn metrics(
+    State(metrics): State<Metrics>,
+    State(database): State<Database>,
+    State(repositories): State<Repositories>,
+) -> Result<impl IntoResponse> {
+    metrics.set_pool_status(&database.pool_status());
+    match repositories.ticket.stats().await {
+        Ok(stats) => metrics.set_ticket_stats(&stats),
+        Err(e) => tracing::warn!("Failed to collect ticket stats for metrics: {}", e),
+    }
+
+    let body = metrics.render()?;
+    Ok(([(header::CONTENT_TYPE, metrics::CONTENT_TYPE)], body))
+}
@@ -1,5 +1,16 @@
+pub mod collab_handler;
+pub mod graphql_handler;
+pub mod health_handler;
+pub mod metrics_handler;
 pub mod tag_handler;
 pub mod ticket_handler;
+pub mod v2;
+pub mod webhook_handler;
 
+pub use collab_handler::*;
+pub use graphql_handler::*;
+pub use health_handler::*;
+pub use metrics_handler::*;
 pub use tag_handler::*;
 pub use ticket_handler::*;
+pub use webhook_handler::*;
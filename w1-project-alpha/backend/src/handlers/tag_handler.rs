@@ -7,46 +7,89 @@ use uuid::Uuid;
 
 use crate::{
     models::{CreateTagRequest, Tag, UpdateTagRequest},
-    repositories::Repositories,
-    utils::error::Result,
+    services::TagService,
+    utils::error::{ErrorResponse, Result},
 };
 
-pub async fn get_tags(State(repositories): State<Repositories>) -> Result<Json<Vec<Tag>>> {
-    let tags = repositories.tag.find_all().await?;
+#[utoipa::path(
+    get,
+    path = "/tags",
+    tag = "tags",
+    responses((status = 200, description = "All tags, sorted by name", body = [Tag]))
+)]
+pub async fn get_tags(State(service): State<TagService>) -> Result<Json<Vec<Tag>>> {
+    let tags = service.list().await?;
     Ok(Json(tags))
 }
 
-pub async fn get_tag(
-    Path(id): Path<Uuid>,
-    State(repositories): State<Repositories>,
-) -> Result<Json<Tag>> {
-    let tag = repositories.tag.find_by_id(id).await?.ok_or_else(|| {
-        crate::utils::error::AppError::NotFound(format!("Tag with id {} not found", id))
-    })?;
+#[utoipa::path(
+    get,
+    path = "/tags/{id}",
+    tag = "tags",
+    params(("id" = Uuid, Path, description = "Tag id")),
+    responses(
+        (status = 200, description = "The tag", body = Tag),
+        (status = 404, description = "No such tag", body = ErrorResponse),
+    )
+)]
+pub async fn get_tag(Path(id): Path<Uuid>, State(service): State<TagService>) -> Result<Json<Tag>> {
+    let tag = service.get(id).await?;
     Ok(Json(tag))
 }
 
+#[utoipa::path(
+    post,
+    path = "/tags",
+    tag = "tags",
+    request_body = CreateTagRequest,
+    responses(
+        (status = 201, description = "Tag created", body = Tag),
+        (status = 400, description = "Invalid or duplicate name, or invalid color", body = ErrorResponse),
+    )
+)]
 pub async fn create_tag(
-    State(repositories): State<Repositories>,
+    State(service): State<TagService>,
     Json(request): Json<CreateTagRequest>,
 ) -> Result<(StatusCode, Json<Tag>)> {
-    let tag = repositories.tag.create(request).await?;
+    let tag = service.create(request).await?;
     Ok((StatusCode::CREATED, Json(tag)))
 }
 
+#[utoipa::path(
+    put,
+    path = "/tags/{id}",
+    tag = "tags",
+    params(("id" = Uuid, Path, description = "Tag id")),
+    request_body = UpdateTagRequest,
+    responses(
+        (status = 200, description = "Tag updated", body = Tag),
+        (status = 400, description = "Invalid or duplicate name, or invalid color", body = ErrorResponse),
+        (status = 404, description = "No such tag", body = ErrorResponse),
+    )
+)]
 pub async fn update_tag(
     Path(id): Path<Uuid>,
-    State(repositories): State<Repositories>,
+    State(service): State<TagService>,
     Json(request): Json<UpdateTagRequest>,
 ) -> Result<Json<Tag>> {
-    let tag = repositories.tag.update(id, request).await?;
+    let tag = service.update(id, request).await?;
     Ok(Json(tag))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/tags/{id}",
+    tag = "tags",
+    params(("id" = Uuid, Path, description = "Tag id")),
+    responses(
+        (status = 204, description = "Tag deleted and removed from every ticket"),
+        (status = 404, description = "No such tag", body = ErrorResponse),
+    )
+)]
 pub async fn delete_tag(
     Path(id): Path<Uuid>,
-    State(repositories): State<Repositories>,
+    State(service): State<TagService>,
 ) -> Result<StatusCode> {
-    repositories.tag.delete(id).await?;
+    service.delete(id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
@@ -1,90 +1,286 @@
 use axum::{
+    body::{Body, Bytes},
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
+use chrono::Utc;
+use futures_util::TryStreamExt;
 use serde::Deserialize;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 use crate::{
-    models::{CreateTicketRequest, Ticket, TicketWithTags, UpdateTicketRequest},
-    repositories::Repositories,
-    utils::error::Result,
+    models::{
+        CreateTicketRequest, ImportReport, Ticket, TicketFilter, TicketWithTags,
+        UpdateTicketRequest,
+    },
+    services::TicketService,
+    utils::{
+        error::{AppError, ErrorResponse, Result},
+        export::{self, ExportFormat},
+        import::ImportFormat,
+    },
 };
 
-#[derive(Debug, Deserialize)]
-pub struct TicketQuery {
-    pub tag: Option<Uuid>,
-    pub search: Option<String>,
-    pub completed: Option<bool>,
-}
-
+#[utoipa::path(
+    get,
+    path = "/tickets",
+    tag = "tickets",
+    params(TicketFilter),
+    responses((status = 200, description = "Matching tickets, newest first", body = [TicketWithTags]))
+)]
 pub async fn get_tickets(
-    Query(query): Query<TicketQuery>,
-    State(repositories): State<Repositories>,
+    Query(filter): Query<TicketFilter>,
+    State(service): State<TicketService>,
 ) -> Result<Json<Vec<TicketWithTags>>> {
-    let tickets = repositories
-        .ticket
-        .find_all(query.tag, query.search, query.completed)
-        .await?;
+    let tickets = service.list(&filter).await?;
     Ok(Json(tickets))
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Export tickets matching the usual list filters as CSV, JSON or NDJSON.
+///
+/// The body is streamed as rows are read from the database.
+#[utoipa::path(
+    get,
+    path = "/tickets/export",
+    tag = "tickets",
+    params(TicketFilter, ExportQuery),
+    responses((
+        status = 200,
+        description = "Matching tickets as an attachment",
+        content(
+            (String = "text/csv"),
+            ([TicketWithTags] = "application/json"),
+            (String = "application/x-ndjson"),
+        )
+    ))
+)]
+pub async fn export_tickets(
+    Query(filter): Query<TicketFilter>,
+    Query(export): Query<ExportQuery>,
+    State(service): State<TicketService>,
+) -> Response {
+    let tickets = service.stream(filter);
+    let body = export::encode(export.format, tickets)
+        .inspect_err(|err| tracing::error!("Ticket export failed: {}", err));
+
+    let filename = format!(
+        "tickets-{}.{}",
+        Utc::now().format("%Y%m%d%H%M%S"),
+        export.format.extension()
+    );
+
+    (
+        [
+            (
+                header::CONTENT_TYPE,
+                export.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        Body::from_stream(body),
+    )
+        .into_response()
+}
+
+/// Largest accepted import body.
+pub const IMPORT_BODY_LIMIT: usize = 32 * 1024 * 1024;
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ImportQuery {
+    /// Overrides the format inferred from `Content-Type`
+    pub format: Option<ImportFormat>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// Import tickets from a CSV or JSON body.
+///
+/// Invalid rows are reported and skipped; all valid rows, and any tags they
+/// need, are written in one transaction. With `dry_run=true` nothing is written.
+#[utoipa::path(
+    post,
+    path = "/tickets/import",
+    tag = "tickets",
+    params(ImportQuery),
+    request_body(
+        description = "Rows in the export format; `id` columns are ignored",
+        content((String = "text/csv"), (Vec<serde_json::Value> = "application/json"))
+    ),
+    responses(
+        (status = 200, description = "What was imported and which rows were skipped", body = ImportReport),
+        (status = 400, description = "Unknown format or unreadable body", body = ErrorResponse),
+        (status = 413, description = "Body larger than 32 MiB"),
+    )
+)]
+pub async fn import_tickets(
+    Query(query): Query<ImportQuery>,
+    State(service): State<TicketService>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<Json<ImportReport>> {
+    let format = match query.format {
+        Some(format) => format,
+        None => headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(ImportFormat::from_content_type)
+            .ok_or_else(|| {
+                AppError::Validation(
+                    "Import body must be text/csv or application/json, or pass ?format="
+                        .to_string(),
+                )
+            })?,
+    };
+
+    let report = service.import(format, &body, query.dry_run).await?;
+    Ok(Json(report))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tickets/{id}",
+    tag = "tickets",
+    params(("id" = Uuid, Path, description = "Ticket id")),
+    responses(
+        (status = 200, description = "The ticket with its tags", body = TicketWithTags),
+        (status = 404, description = "No such ticket", body = ErrorResponse),
+    )
+)]
 pub async fn get_ticket(
     Path(id): Path<Uuid>,
-    State(repositories): State<Repositories>,
+    State(service): State<TicketService>,
 ) -> Result<Json<TicketWithTags>> {
-    let ticket = repositories.ticket.find_by_id(id).await?.ok_or_else(|| {
-        crate::utils::error::AppError::NotFound(format!("Ticket with id {} not found", id))
-    })?;
+    let ticket = service.get(id).await?;
     Ok(Json(ticket))
 }
 
+#[utoipa::path(
+    post,
+    path = "/tickets",
+    tag = "tickets",
+    request_body = CreateTicketRequest,
+    responses(
+        (status = 201, description = "Ticket created", body = Ticket),
+        (status = 400, description = "Empty title or unknown tag", body = ErrorResponse),
+    )
+)]
 pub async fn create_ticket(
-    State(repositories): State<Repositories>,
+    State(service): State<TicketService>,
     Json(request): Json<CreateTicketRequest>,
 ) -> Result<(StatusCode, Json<Ticket>)> {
-    let ticket = repositories.ticket.create(request).await?;
+    let ticket = service.create(request).await?;
     Ok((StatusCode::CREATED, Json(ticket)))
 }
 
+#[utoipa::path(
+    put,
+    path = "/tickets/{id}",
+    tag = "tickets",
+    params(("id" = Uuid, Path, description = "Ticket id")),
+    request_body = UpdateTicketRequest,
+    responses(
+        (status = 200, description = "Ticket updated", body = Ticket),
+        (status = 400, description = "Empty title", body = ErrorResponse),
+        (status = 404, description = "No such ticket", body = ErrorResponse),
+    )
+)]
 pub async fn update_ticket(
     Path(id): Path<Uuid>,
-    State(repositories): State<Repositories>,
+    State(service): State<TicketService>,
     Json(request): Json<UpdateTicketRequest>,
 ) -> Result<Json<Ticket>> {
-    let ticket = repositories.ticket.update(id, request).await?;
+    let ticket = service.update(id, request).await?;
     Ok(Json(ticket))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/tickets/{id}",
+    tag = "tickets",
+    params(("id" = Uuid, Path, description = "Ticket id")),
+    responses(
+        (status = 204, description = "Ticket deleted"),
+        (status = 404, description = "No such ticket", body = ErrorResponse),
+    )
+)]
 pub async fn delete_ticket(
     Path(id): Path<Uuid>,
-    State(repositories): State<Repositories>,
+    State(service): State<TicketService>,
 ) -> Result<StatusCode> {
-    repositories.ticket.delete(id).await?;
+    service.delete(id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    patch,
+    path = "/tickets/{id}/toggle",
+    tag = "tickets",
+    params(("id" = Uuid, Path, description = "Ticket id")),
+    responses(
+        (status = 200, description = "Ticket with `completed` flipped", body = Ticket),
+        (status = 404, description = "No such ticket", body = ErrorResponse),
+    )
+)]
 pub async fn toggle_ticket_completed(
     Path(id): Path<Uuid>,
-    State(repositories): State<Repositories>,
+    State(service): State<TicketService>,
 ) -> Result<Json<Ticket>> {
-    let ticket = repositories.ticket.toggle_completed(id).await?;
+    let ticket = service.toggle_completed(id).await?;
     Ok(Json(ticket))
 }
 
+#[utoipa::path(
+    post,
+    path = "/tickets/{ticket_id}/tags/{tag_id}",
+    tag = "tickets",
+    params(
+        ("ticket_id" = Uuid, Path, description = "Ticket id"),
+        ("tag_id" = Uuid, Path, description = "Tag id"),
+    ),
+    responses(
+        (status = 204, description = "Tag added; adding it twice is not an error"),
+        (status = 404, description = "No such ticket or tag", body = ErrorResponse),
+    )
+)]
 pub async fn add_tag_to_ticket(
     Path((ticket_id, tag_id)): Path<(Uuid, Uuid)>,
-    State(repositories): State<Repositories>,
+    State(service): State<TicketService>,
 ) -> Result<StatusCode> {
-    repositories.ticket.add_tag(ticket_id, tag_id).await?;
+    service.add_tag(ticket_id, tag_id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
 
+#[utoipa::path(
+    delete,
+    path = "/tickets/{ticket_id}/tags/{tag_id}",
+    tag = "tickets",
+    params(
+        ("ticket_id" = Uuid, Path, description = "Ticket id"),
+        ("tag_id" = Uuid, Path, description = "Tag id"),
+    ),
+    responses(
+        (status = 204, description = "Tag removed"),
+        (status = 404, description = "No such ticket, or the ticket does not carry the tag", body = ErrorResponse),
+    )
+)]
 pub async fn remove_tag_from_ticket(
     Path((ticket_id, tag_id)): Path<(Uuid, Uuid)>,
-    State(repositories): State<Repositories>,
+    State(service): State<TicketService>,
 ) -> Result<StatusCode> {
-    repositories.ticket.remove_tag(ticket_id, tag_id).await?;
+    service.remove_tag(ticket_id, tag_id).await?;
     Ok(StatusCode::NO_CONTENT)
 }
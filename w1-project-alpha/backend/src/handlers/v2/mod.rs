@@ -0,0 +1,6 @@
+//! Handlers whose request or response shapes differ in API v2. Everything
+//! else is served by the v1 handlers under both versions.
+
+pub mod ticket_handler;
+
+pub use ticket_handler::*;
@@ -0,0 +1,111 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    models::{
+        CreateTicketRequest, Page, PageParams, TicketFilter, TicketV2, UpdateTicketRequestV2,
+    },
+    services::TicketService,
+    utils::error::{ErrorResponse, Result},
+};
+
+#[utoipa::path(
+    get,
+    path = "/tickets",
+    tag = "tickets",
+    params(TicketFilter, PageParams),
+    responses(
+        (status = 200, description = "One page of matching tickets, newest first", body = Page<TicketV2>),
+        (status = 400, description = "Page or page size out of range", body = ErrorResponse),
+    )
+)]
+pub async fn get_tickets(
+    Query(filter): Query<TicketFilter>,
+    Query(params): Query<PageParams>,
+    State(service): State<TicketService>,
+) -> Result<Json<Page<TicketV2>>> {
+    let page = service.list_page(&filter, params).await?;
+    Ok(Json(page.map(TicketV2::from)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tickets/{id}",
+    tag = "tickets",
+    params(("id" = Uuid, Path, description = "Ticket id")),
+    responses(
+        (status = 200, description = "The ticket", body = TicketV2),
+        (status = 404, description = "No such ticket", body = ErrorResponse),
+    )
+)]
+pub async fn get_ticket(
+    Path(id): Path<Uuid>,
+    State(service): State<TicketService>,
+) -> Result<Json<TicketV2>> {
+    let ticket = service.get(id).await?;
+    Ok(Json(ticket.into()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/tickets",
+    tag = "tickets",
+    request_body = CreateTicketRequest,
+    responses(
+        (status = 201, description = "Ticket created", body = TicketV2),
+        (status = 400, description = "Empty title or unknown tag", body = ErrorResponse),
+    )
+)]
+pub async fn create_ticket(
+    State(service): State<TicketService>,
+    Json(request): Json<CreateTicketRequest>,
+) -> Result<(StatusCode, Json<TicketV2>)> {
+    let ticket = service.create(request).await?;
+    let ticket = service.get(ticket.id).await?;
+    Ok((StatusCode::CREATED, Json(ticket.into())))
+}
+
+#[utoipa::path(
+    put,
+    path = "/tickets/{id}",
+    tag = "tickets",
+    params(("id" = Uuid, Path, description = "Ticket id")),
+    request_body = UpdateTicketRequestV2,
+    responses(
+        (status = 200, description = "Ticket updated", body = TicketV2),
+        (status = 400, description = "Empty title", body = ErrorResponse),
+        (status = 404, description = "No such ticket", body = ErrorResponse),
+    )
+)]
+pub async fn update_ticket(
+    Path(id): Path<Uuid>,
+    State(service): State<TicketService>,
+    Json(request): Json<UpdateTicketRequestV2>,
+) -> Result<Json<TicketV2>> {
+    service.update(id, request.into()).await?;
+    let ticket = service.get(id).await?;
+    Ok(Json(ticket.into()))
+}
+
+#[utoipa::path(
+    patch,
+    path = "/tickets/{id}/toggle",
+    tag = "tickets",
+    params(("id" = Uuid, Path, description = "Ticket id")),
+    responses(
+        (status = 200, description = "Ticket with its status flipped", body = TicketV2),
+        (status = 404, description = "No such ticket", body = ErrorResponse),
+    )
+)]
+pub async fn toggle_ticket_completed(
+    Path(id): Path<Uuid>,
+    State(service): State<TicketService>,
+) -> Result<Json<TicketV2>> {
+    service.toggle_completed(id).await?;
+    let ticket = service.get(id).await?;
+    Ok(Json(ticket.into()))
+}
@@ -0,0 +1,139 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{
+    models::{CreateWebhookRequest, UpdateWebhookRequest, Webhook, WebhookDelivery},
+    repositories::Repositories,
+    utils::error::{AppError, ErrorResponse, Result},
+};
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeliveryQuery {
+    /// Deliveries to return, 1 to 200 (default 50)
+    pub limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    responses((status = 200, description = "All webhooks, oldest first", body = [Webhook]))
+)]
+pub async fn get_webhooks(State(repositories): State<Repositories>) -> Result<Json<Vec<Webhook>>> {
+    let webhooks = repositories.webhook.find_all().await?;
+    Ok(Json(webhooks))
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Webhook id")),
+    responses(
+        (status = 200, description = "The webhook", body = Webhook),
+        (status = 404, description = "No such webhook", body = ErrorResponse),
+    )
+)]
+pub async fn get_webhook(
+    Path(id): Path<Uuid>,
+    State(repositories): State<Repositories>,
+) -> Result<Json<Webhook>> {
+    let webhook = repositories
+        .webhook
+        .find_by_id(id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Webhook with id {} not found", id)))?;
+    Ok(Json(webhook))
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Webhook created", body = Webhook),
+        (status = 400, description = "Invalid url, empty secret or unknown event", body = ErrorResponse),
+    )
+)]
+pub async fn create_webhook(
+    State(repositories): State<Repositories>,
+    Json(request): Json<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<Webhook>)> {
+    let webhook = repositories.webhook.create(request).await?;
+    Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+#[utoipa::path(
+    put,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Webhook id")),
+    request_body = UpdateWebhookRequest,
+    responses(
+        (status = 200, description = "Webhook updated", body = Webhook),
+        (status = 400, description = "Invalid url, empty secret or unknown event", body = ErrorResponse),
+        (status = 404, description = "No such webhook", body = ErrorResponse),
+    )
+)]
+pub async fn update_webhook(
+    Path(id): Path<Uuid>,
+    State(repositories): State<Repositories>,
+    Json(request): Json<UpdateWebhookRequest>,
+) -> Result<Json<Webhook>> {
+    let webhook = repositories.webhook.update(id, request).await?;
+    Ok(Json(webhook))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{id}",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Webhook id")),
+    responses(
+        (status = 204, description = "Webhook and its delivery log deleted"),
+        (status = 404, description = "No such webhook", body = ErrorResponse),
+    )
+)]
+pub async fn delete_webhook(
+    Path(id): Path<Uuid>,
+    State(repositories): State<Repositories>,
+) -> Result<StatusCode> {
+    repositories.webhook.delete(id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Delivery log of a webhook, newest first (`limit` defaults to 50, at most 200).
+#[utoipa::path(
+    get,
+    path = "/webhooks/{id}/deliveries",
+    tag = "webhooks",
+    params(("id" = Uuid, Path, description = "Webhook id"), DeliveryQuery),
+    responses(
+        (status = 200, description = "Recent deliveries, newest first", body = [WebhookDelivery]),
+        (status = 404, description = "No such webhook", body = ErrorResponse),
+    )
+)]
+pub async fn get_webhook_deliveries(
+    Path(id): Path<Uuid>,
+    Query(query): Query<DeliveryQuery>,
+    State(repositories): State<Repositories>,
+) -> Result<Json<Vec<WebhookDelivery>>> {
+    if repositories.webhook.find_by_id(id).await?.is_none() {
+        return Err(AppError::NotFound(format!(
+            "Webhook with id {} not found",
+            id
+        )));
+    }
+
+    let limit = query.limit.unwrap_or(50).clamp(1, 200);
+    let deliveries = repositories.webhook.find_deliveries(id, limit).await?;
+    Ok(Json(deliveries))
+}
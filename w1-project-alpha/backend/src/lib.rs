@@ -1,11 +1,28 @@
 pub mod config;
+pub mod db;
+pub mod events;
+pub mod graphql;
+pub mod grpc;
 pub mod handlers;
-pub mod models;
+pub mod metrics;
+pub mod middleware;
+pub mod openapi;
 pub mod repositories;
 pub mod routes;
 pub mod services;
+pub mod shutdown;
+pub mod state;
+pub mod synthetic;
+pub mod telemetry;
 pub mod utils;
+pub mod webhooks;
+
+pub use project_alpha_models as models;
 
 pub use config::Config;
+pub use db::Database;
+pub use events::EventHub;
+pub use metrics::Metrics;
 pub use repositories::Repositories;
-pub use routes::create_routes;
+pub use routes::{create_app, create_grpc_app, create_routes};
+pub use state::AppState;
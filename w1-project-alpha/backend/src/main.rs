@@ -1,77 +1,546 @@
-use axum::http::header;
-use axum::Router;
-use std::net::SocketAddr;
-use tower_http::cors::{Any, CorsLayer};
-use tower_http::trace::TraceLayer;
-use tracing::info;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-
-mod config;
-mod handlers;
-mod models;
-mod repositories;
-mod routes;
-mod services;
-mod utils;
-
-use config::Config;
-use repositories::Repositories;
-use routes::create_routes;
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand};
+use std::error::Error;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use tracing::{info, warn};
+
+use project_alpha_backend::models::{MigrationState, MigrationStatus};
+use project_alpha_backend::shutdown::Shutdown;
+use project_alpha_backend::synthetic::{self, Generator, SyntheticConfig};
+use project_alpha_backend::webhooks::{DispatcherConfig, WebhookDispatcher};
+use project_alpha_backend::{
+    create_app, create_grpc_app, shutdown, telemetry, AppState, Config, Database,
+};
+
+/// 内置的示例数据（Postgres 语法）
+const SEED_SQL: &str = include_str!("../migrations/seed.sql");
+
+/// Project Alpha 工单服务
+///
+/// 配置按以下顺序叠加，后者覆盖前者：默认值、`--config` 指定的 TOML 文件、`APP_` 前缀的环境变量（如 `APP_SERVER_PORT`）、命令行参数。
+#[derive(Parser)]
+#[command(version)]
+struct Cli {
+    /// TOML 配置文件路径
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// 监听地址（server.host）
+    #[arg(long, global = true)]
+    host: Option<String>,
+
+    /// 监听端口（server.port）
+    #[arg(long, global = true)]
+    port: Option<u16>,
+
+    /// 数据库连接串（database.url）
+    #[arg(long, global = true, value_name = "URL")]
+    database_url: Option<String>,
+
+    /// 覆盖任意配置项，如 `--set database.max_connections=20`
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE", value_parser = parse_override)]
+    overrides: Vec<(String, String)>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// 启动服务（未指定子命令时的默认行为）
+    Serve(ServeArgs),
+    /// 管理数据库表结构，便于在启动服务前单独执行迁移
+    Migrate {
+        #[command(subcommand)]
+        command: MigrateCommand,
+    },
+    /// 向数据库写入示例数据（可重复执行，已存在的行会被跳过）
+    Seed(SeedArgs),
+    /// 校验配置并测试数据库连接，失败时以状态码 1 退出
+    Check,
+    /// 配置相关命令
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Args, Default)]
+struct ServeArgs {
+    /// 启动时不执行数据库迁移（由部署流程单独运行 `migrate up`）
+    #[arg(long)]
+    no_migrate: bool,
+}
+
+#[derive(Subcommand)]
+enum MigrateCommand {
+    /// 执行所有未应用的迁移
+    Up,
+    /// 回滚迁移，默认只回滚最近的一个
+    Down {
+        /// 回滚最近的 N 个迁移
+        #[arg(long, default_value_t = 1, conflicts_with = "to")]
+        steps: usize,
+        /// 回滚到该版本为止（保留该版本及更早的迁移），0 表示全部回滚
+        #[arg(long, value_name = "VERSION")]
+        to: Option<i64>,
+    },
+    /// 列出所有迁移及其状态
+    Status,
+}
+
+#[derive(Args)]
+struct SeedArgs {
+    /// 要执行的 SQL 脚本，默认使用内置的 migrations/seed.sql（仅适用于 Postgres）
+    #[arg(long, value_name = "PATH")]
+    file: Option<PathBuf>,
+
+    /// 改为生成合成数据（压测与演示用）；参数相同时生成的行完全相同，重复执行不会重复写入
+    #[arg(long, conflicts_with = "file")]
+    generate: bool,
+
+    /// 生成的工单数量
+    #[arg(long, default_value_t = 1_000, requires = "generate")]
+    tickets: usize,
+
+    /// 生成的标签数量，标签的使用频率按 Zipf 分布递减
+    #[arg(long, default_value_t = 30, requires = "generate")]
+    tags: usize,
+
+    /// 随机种子
+    #[arg(long, default_value_t = 1, requires = "generate")]
+    seed: u64,
+
+    /// 已完成工单的比例（0 到 1）
+    #[arg(long, default_value_t = 0.6, requires = "generate")]
+    completed_ratio: f64,
+
+    /// 工单的创建时间分布在截止日期之前的这么多个月内
+    #[arg(long, default_value_t = 12, requires = "generate")]
+    months: u32,
+
+    /// 时间范围的截止日期（YYYY-MM-DD），默认为今天；复现数据时需与原来相同
+    #[arg(long, value_name = "DATE", requires = "generate")]
+    until: Option<NaiveDate>,
+
+    /// 每个事务写入的工单数
+    #[arg(long, default_value_t = synthetic::DEFAULT_BATCH_SIZE, requires = "generate")]
+    batch_size: usize,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommand {
+    /// 以 TOML 格式打印生效的配置（密码已隐藏）
+    Print,
+}
+
+fn parse_override(arg: &str) -> Result<(String, String), String> {
+    arg.split_once('=')
+        .map(|(key, value)| (key.trim().to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", arg))
+}
+
+impl Cli {
+    /// 命令行参数是优先级最高的一层配置
+    fn overrides(&self) -> Vec<(String, String)> {
+        let mut overrides = self.overrides.clone();
+        if let Some(host) = &self.host {
+            overrides.push(("server.host".to_string(), host.clone()));
+        }
+        if let Some(port) = self.port {
+            overrides.push(("server.port".to_string(), port.to_string()));
+        }
+        if let Some(url) = &self.database_url {
+            overrides.push(("database.url".to_string(), url.clone()));
+        }
+        overrides
+    }
+}
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // 加载环境变量
+async fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
+
+    // 加载 .env 中的环境变量
     dotenvy::dotenv().ok();
 
-    // 初始化日志
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::EnvFilter::new(
-            std::env::var("RUST_LOG")
-                .unwrap_or_else(|_| "project_alpha_backend=debug,tower_http=debug".into()),
-        ))
-        .with(tracing_subscriber::fmt::layer())
-        .init();
-
-    // 加载配置
-    let config = Config::from_env()?;
+    // 加载并校验配置，出错时直接给出可读的错误信息
+    let config = match Config::load(cli.config.as_deref(), &cli.overrides()) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(2);
+        }
+    };
+
+    // 管理命令只输出结果，不初始化日志与链路导出；失败时打印错误并以状态码 1 退出
+    let result = match cli.command.unwrap_or(Command::Serve(ServeArgs::default())) {
+        Command::Serve(args) => return serve(config, !args.no_migrate).await,
+        Command::Config {
+            command: ConfigCommand::Print,
+        } => {
+            print!("{}", config.to_toml());
+            return Ok(());
+        }
+        Command::Migrate { command } => migrate(&config, command).await,
+        Command::Seed(args) => seed(&config, args).await,
+        Command::Check => check(&config, cli.config.as_deref()).await,
+    };
+    if let Err(err) = result {
+        eprintln!("error: {}", err);
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+async fn serve(config: Config, migrate: bool) -> Result<(), Box<dyn Error>> {
+    // 初始化日志（级别由 RUST_LOG 控制，LOG_FORMAT=json 时输出 JSON 行）；配置了 [otel] endpoint 时同时导出 span
+    let telemetry = telemetry::init(&config.log, &config.otel)?;
+
+    // DatabaseConfig 的 Debug 输出会隐藏数据库密码
     info!("Loaded configuration: {:?}", config);
 
-    // 连接数据库
-    let pool = sqlx::PgPool::connect(&config.database.url).await?;
+    // 连接数据库（根据 DATABASE_URL 的 scheme 选择 Postgres 或 SQLite，数据库未就绪时按退避重试）
+    let database = Database::connect(&config.database).await?;
     info!("Connected to database");
 
-    // 运行迁移
-    sqlx::migrate!("./migrations").run(&pool).await?;
-    info!("Database migrations completed");
-
-    // 创建仓库
-    let repositories = Repositories::new(pool);
-
-    // 创建应用
-    let app = Router::new()
-        .merge(create_routes(repositories))
-        .layer(
-            CorsLayer::new()
-                .allow_origin(Any)
-                .allow_methods(Any)
-                .allow_headers([header::CONTENT_TYPE, header::AUTHORIZATION, header::ACCEPT])
-                .expose_headers(Any),
-        )
-        .layer(TraceLayer::new_for_http());
+    // 运行迁移；使用 --no-migrate 时只检查是否有未应用的迁移
+    if migrate {
+        database.migrate().await?;
+        info!("Database migrations completed");
+    } else {
+        match database.pending_migrations().await {
+            Ok(pending) if pending.is_empty() => info!("Skipping migrations, schema is up to date"),
+            Ok(pending) => warn!(
+                "Skipping migrations with {} pending; /readyz reports not ready until `migrate up` runs",
+                pending.len()
+            ),
+            Err(err) => warn!("Skipping migrations, could not read their status: {}", err),
+        }
+    }
+
+    // 创建共享状态（仓库、服务与事件总线）
+    let state = AppState::new(database.clone());
+
+    // 收到 SIGINT/SIGTERM 时开始停机，HTTP 服务与后台任务据此停止；
+    // 各停机步骤共用同一个截止时间，整个停机过程不超过 shutdown_timeout
+    let shutdown = Shutdown::new(config.server.shutdown_timeout());
+
+    // 启动 webhook 投递任务
+    let dispatcher = WebhookDispatcher::new(
+        state.repositories.webhook.clone(),
+        DispatcherConfig::default(),
+    )?;
+    let dispatcher = tokio::spawn(dispatcher.run(shutdown.token()));
+
+    // 创建应用（CORS 策略来自配置中的 [cors] 段；[grpc] port 为 0 时 gRPC 服务与 HTTP 共用端口）
+    let app = create_app(state.clone(), &config);
+
+    // 监听退出信号：停止接收新连接，并通知 WebSocket 连接推送完剩余事件后断开
+    tokio::spawn({
+        let shutdown = shutdown.clone();
+        let events = state.events.clone();
+        async move {
+            shutdown::signal().await;
+            info!("Shutdown signal received");
+            shutdown.start();
+            events.close();
+        }
+    });
 
     // 启动服务器
-    let addr: SocketAddr = format!("{}:{}", config.server.host, config.server.port)
-        .parse()
-        .map_err(|e| {
-            format!(
-                "Invalid server address {}:{} - {}",
-                config.server.host, config.server.port, e
+    let addr = config.server.addr()?;
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    // 记录实际监听的地址（端口配置为 0 时由系统分配）
+    info!("Listening on {}", listener.local_addr()?);
+    let http = shutdown::serve(listener, app, shutdown.clone());
+
+    // 配置了独立端口时，gRPC 服务单独监听，与 HTTP 服务一同停止
+    if config.grpc.has_own_port() {
+        let grpc_listener =
+            tokio::net::TcpListener::bind(config.server.grpc_addr(&config.grpc)?).await?;
+        info!("gRPC listening on {}", grpc_listener.local_addr()?);
+        let grpc = shutdown::serve(
+            grpc_listener,
+            create_grpc_app(state.clone()),
+            shutdown.clone(),
+        );
+        tokio::try_join!(http, grpc)?;
+    } else {
+        http.await?;
+    }
+
+    // 服务器自行退出时也要停止后台任务
+    shutdown.start();
+
+    // 在剩余时间内等待 webhook 发件箱中已到期的投递发送完毕
+    if tokio::time::timeout_at(shutdown.deadline(), dispatcher)
+        .await
+        .is_err()
+    {
+        warn!(
+            "Webhook dispatcher did not finish within the {:?} shutdown timeout",
+            shutdown.drain_timeout()
+        );
+    }
+
+    // 关闭连接池；被丢弃的请求可能仍占用连接，最多等到截止时间
+    shutdown::close_database(&database, &shutdown).await;
+    info!("Shutdown complete");
+
+    // 发送缓冲中的 span
+    telemetry.shutdown();
+
+    Ok(())
+}
+
+async fn migrate(config: &Config, command: MigrateCommand) -> Result<(), Box<dyn Error>> {
+    let database = Database::connect(&config.database).await?;
+    let status = database.migration_status().await?;
+
+    match command {
+        MigrateCommand::Up => {
+            let pending: Vec<&MigrationStatus> = status
+                .iter()
+                .filter(|m| m.state == MigrationState::Pending)
+                .collect();
+            database.migrate().await?;
+            if pending.is_empty() {
+                println!("Schema is up to date");
+            }
+            for migration in pending {
+                println!("Applied {} {}", migration.version, migration.description);
+            }
+        }
+        MigrateCommand::Down { steps, to } => {
+            let applied: Vec<&MigrationStatus> = status
+                .iter()
+                .filter(|m| {
+                    matches!(
+                        m.state,
+                        MigrationState::Applied
+                            | MigrationState::Unknown
+                            | MigrationState::Modified
+                    )
+                })
+                .collect();
+            let target = match to {
+                Some(version) => version,
+                None => applied.iter().rev().nth(steps).map_or(0, |m| m.version),
+            };
+            let reverted: Vec<&MigrationStatus> = applied
+                .into_iter()
+                .rev()
+                .filter(|m| m.version > target)
+                .collect();
+            // 先确认每个迁移都能回滚，避免只回滚一半
+            if let Some(migration) = reverted.iter().find(|m| !m.reversible) {
+                return Err(format!(
+                    "Migration {} {} cannot be reverted by this binary",
+                    migration.version, migration.description
+                )
+                .into());
+            }
+            database.undo(target).await?;
+            if reverted.is_empty() {
+                println!("Nothing to revert");
+            }
+            for migration in reverted {
+                println!("Reverted {} {}", migration.version, migration.description);
+            }
+        }
+        MigrateCommand::Status => print!("{}", migration_table(&status)),
+    }
+
+    database.close().await;
+    Ok(())
+}
+
+fn migration_table(status: &[MigrationStatus]) -> String {
+    let width = status
+        .iter()
+        .map(|m| m.description.chars().count())
+        .chain(["DESCRIPTION".len()])
+        .max()
+        .unwrap_or_default();
+    let mut table = format!(
+        "{:<14}  {:<width$}  {:<8}  INSTALLED ON\n",
+        "VERSION", "DESCRIPTION", "STATE"
+    );
+    for migration in status {
+        let state = state_name(migration.state);
+        let installed_on = migration
+            .installed_on
+            .map(|at| at.format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        let row = format!(
+            "{:<14}  {:<width$}  {:<8}  {}",
+            migration.version, migration.description, state, installed_on
+        );
+        table.push_str(row.trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+fn state_name(state: MigrationState) -> &'static str {
+    match state {
+        MigrationState::Applied => "applied",
+        MigrationState::Pending => "pending",
+        MigrationState::Failed => "failed",
+        MigrationState::Unknown => "unknown",
+        MigrationState::Modified => "modified",
+    }
+}
+
+async fn seed(config: &Config, args: SeedArgs) -> Result<(), Box<dyn Error>> {
+    let database = Database::connect(&config.database).await?;
+    let script = match &args.file {
+        _ if args.generate => None,
+        Some(path) => Some((
+            std::fs::read_to_string(path)
+                .map_err(|err| format!("Failed to read {}: {}", path.display(), err))?,
+            path.display().to_string(),
+        )),
+        None if database.backend() == "postgres" => {
+            Some((SEED_SQL.to_string(), "migrations/seed.sql".to_string()))
+        }
+        None => {
+            return Err(format!(
+                "The built-in seed data is written for Postgres; pass --file with a script \
+                 or use --generate for {}",
+                database.backend()
             )
-        })?;
-    info!("Starting server on {}", addr);
+            .into())
+        }
+    };
 
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    // 失败、被修改或本程序不认识的迁移需要人工处理，不能当作尚未应用
+    let status = database.migration_status().await?;
+    if let Some(migration) = status
+        .iter()
+        .find(|m| !matches!(m.state, MigrationState::Applied | MigrationState::Pending))
+    {
+        return Err(format!(
+            "Migration {} {} is {}; check `migrate status` before seeding",
+            migration.version,
+            migration.description,
+            state_name(migration.state)
+        )
+        .into());
+    }
+    let pending = status
+        .iter()
+        .filter(|m| m.state == MigrationState::Pending)
+        .count();
+    if pending > 0 {
+        return Err(format!(
+            "{} migrations are not applied; run `migrate up` first",
+            pending
+        )
+        .into());
+    }
+
+    match script {
+        Some((sql, source)) => {
+            database.execute_script(&sql).await?;
+            println!("Seeded the database from {}", source);
+        }
+        None => generate(&database, &args).await?,
+    }
+    database.close().await;
+    Ok(())
+}
+
+/// 生成合成数据；终端上在标准错误输出进度，结束时打印复现所需的参数
+async fn generate(database: &Database, args: &SeedArgs) -> Result<(), Box<dyn Error>> {
+    let defaults = SyntheticConfig::default();
+    let config = SyntheticConfig {
+        seed: args.seed,
+        tickets: args.tickets,
+        tags: args.tags,
+        completed_ratio: args.completed_ratio,
+        months: args.months,
+        until: args.until.map_or(defaults.until, |date| {
+            date.and_time(Default::default()).and_utc()
+        }),
+        ..defaults
+    };
+    let (seed, until, total) = (config.seed, config.until, config.tickets);
+    let generator = Generator::new(config)?;
+
+    let started = Instant::now();
+    let interactive = std::io::stderr().is_terminal();
+    let report = synthetic::load(database, generator, args.batch_size, |done| {
+        if interactive {
+            eprint!("\rGenerated {}/{} tickets", done, total);
+            std::io::stderr().flush().ok();
+        }
+    })
+    .await;
+    if interactive && total > 0 {
+        eprintln!();
+    }
+    let report = report?;
+
+    println!(
+        "Inserted {} tags, {} tickets and {} ticket tags in {:.1?}",
+        report.tags,
+        report.tickets,
+        report.ticket_tags,
+        started.elapsed()
+    );
+    println!(
+        "Reproduce with: seed --generate --seed {} --tickets {} --tags {} --completed-ratio {} --months {} --until {}",
+        seed,
+        args.tickets,
+        args.tags,
+        args.completed_ratio,
+        args.months,
+        until.format("%Y-%m-%d")
+    );
+    Ok(())
+}
+
+/// 逐项输出检查结果；数据库不可用时返回错误
+async fn check(config: &Config, path: Option<&Path>) -> Result<(), Box<dyn Error>> {
+    let source = path.map_or_else(
+        || "defaults and environment".to_string(),
+        |path| path.display().to_string(),
+    );
+    println!("config      ok ({})", source);
+
+    // 只尝试一次，不按启动时的退避策略重试
+    let mut database_config = config.database.clone();
+    database_config.connect_retries = 0;
+    let started = Instant::now();
+    let database = match Database::connect(&database_config).await {
+        Ok(database) => database,
+        Err(err) => {
+            println!("database    failed");
+            return Err(format!("Cannot connect to the database: {}", err).into());
+        }
+    };
+    if let Err(err) = database.ping().await {
+        println!("database    failed");
+        return Err(format!("Database does not answer queries: {}", err).into());
+    }
+    println!(
+        "database    ok ({}, {} ms)",
+        database.backend(),
+        started.elapsed().as_millis()
+    );
 
+    match database.pending_migrations().await {
+        Ok(pending) if pending.is_empty() => println!("migrations  up to date"),
+        Ok(pending) => println!(
+            "migrations  {} pending, run `migrate up` before serving with --no-migrate",
+            pending.len()
+        ),
+        Err(_) => println!("migrations  none applied, run `migrate up`"),
+    }
+    database.close().await;
     Ok(())
 }
@@ -0,0 +1,320 @@
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::models::{PoolStatus, TicketStats};
+use crate::utils::error::{AppError, Result};
+
+/// Route label for requests that matched no route, so that probing random
+/// paths cannot create unbounded label values.
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+/// Content type of [`Metrics::render`].
+pub const CONTENT_TYPE: &str = prometheus::TEXT_FORMAT;
+
+/// Query latency buckets in seconds, from half a millisecond up.
+const QUERY_BUCKETS: &[f64] = &[
+    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+];
+
+/// Prometheus metrics of one server instance.
+///
+/// HTTP and query metrics are recorded as requests happen; pool and ticket
+/// gauges are refreshed when `/metrics` is scraped. Clones share the same
+/// registry.
+#[derive(Clone)]
+pub struct Metrics {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    query_duration: HistogramVec,
+    query_errors: IntCounterVec,
+    queries_in_flight: IntGauge,
+    pool_size: IntGauge,
+    pool_idle: IntGauge,
+    pool_max: IntGauge,
+    pool_waiters: IntGauge,
+    tickets: IntGaugeVec,
+    tag_tickets: IntGaugeVec,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let http_requests = IntCounterVec::new(
+            Opts::new("http_requests_total", "HTTP requests handled"),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+        let http_request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "http_request_duration_seconds",
+                "Time to produce the response headers",
+            ),
+            &["method", "route", "status"],
+        )
+        .expect("valid metric");
+        let query_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "db_query_duration_seconds",
+                "Duration of repository calls, including waiting for a connection",
+            )
+            .buckets(QUERY_BUCKETS.to_vec()),
+            &["repository", "operation"],
+        )
+        .expect("valid metric");
+        let query_errors = IntCounterVec::new(
+            Opts::new(
+                "db_query_errors_total",
+                "Repository calls that failed with a database error",
+            ),
+            &["repository", "operation"],
+        )
+        .expect("valid metric");
+        let queries_in_flight =
+            IntGauge::new("db_queries_in_flight", "Repository calls currently running")
+                .expect("valid metric");
+        let pool_size = IntGauge::new("db_pool_size", "Open connections, idle or in use")
+            .expect("valid metric");
+        let pool_idle = IntGauge::new("db_pool_idle", "Idle connections").expect("valid metric");
+        let pool_max = IntGauge::new("db_pool_max_connections", "Configured pool limit")
+            .expect("valid metric");
+        let pool_waiters = IntGauge::new(
+            "db_pool_waiters",
+            "Repository calls in flight beyond the connections in use, an estimate of \
+             callers waiting for a connection",
+        )
+        .expect("valid metric");
+        let tickets = IntGaugeVec::new(Opts::new("tickets", "Tickets by status"), &["status"])
+            .expect("valid metric");
+        let tag_tickets = IntGaugeVec::new(
+            Opts::new("tag_tickets", "Tickets carrying each tag"),
+            &["tag"],
+        )
+        .expect("valid metric");
+
+        for collector in [
+            Box::new(http_requests.clone()) as Box<dyn prometheus::core::Collector>,
+            Box::new(http_request_duration.clone()),
+            Box::new(query_duration.clone()),
+            Box::new(query_errors.clone()),
+            Box::new(queries_in_flight.clone()),
+            Box::new(pool_size.clone()),
+            Box::new(pool_idle.clone()),
+            Box::new(pool_max.clone()),
+            Box::new(pool_waiters.clone()),
+            Box::new(tickets.clone()),
+            Box::new(tag_tickets.clone()),
+        ] {
+            registry
+                .register(collector)
+                .expect("metric names are unique");
+        }
+
+        Self {
+            inner: Arc::new(Inner {
+                registry,
+                http_requests,
+                http_request_duration,
+                query_duration,
+                query_errors,
+                queries_in_flight,
+                pool_size,
+                pool_idle,
+                pool_max,
+                pool_waiters,
+                tickets,
+                tag_tickets,
+            }),
+        }
+    }
+
+    pub fn observe_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let status = status.to_string();
+        let labels = [method, route, status.as_str()];
+        self.inner.http_requests.with_label_values(&labels).inc();
+        self.inner
+            .http_request_duration
+            .with_label_values(&labels)
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Start timing a repository call; the duration is recorded when the
+    /// returned timer is dropped.
+    pub fn query_timer(&self, repository: &'static str, operation: &'static str) -> QueryTimer {
+        self.inner.queries_in_flight.inc();
+        QueryTimer {
+            metrics: self.clone(),
+            repository,
+            operation,
+            started: Instant::now(),
+        }
+    }
+
+    /// Time `future` as one repository call.
+    pub async fn time_query<T>(
+        &self,
+        repository: &'static str,
+        operation: &'static str,
+        future: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        let timer = self.query_timer(repository, operation);
+        let result = future.await;
+        if let Err(err) = &result {
+            timer.record_error(err);
+        }
+        result
+    }
+
+    pub fn set_pool_status(&self, status: &PoolStatus) {
+        self.inner.pool_size.set(i64::from(status.size));
+        self.inner.pool_idle.set(i64::from(status.idle));
+        self.inner.pool_max.set(i64::from(status.max));
+        let waiting = self.inner.queries_in_flight.get() - i64::from(status.in_use);
+        self.inner.pool_waiters.set(waiting.max(0));
+    }
+
+    pub fn set_ticket_stats(&self, stats: &TicketStats) {
+        self.inner
+            .tickets
+            .with_label_values(&["open"])
+            .set(stats.open);
+        self.inner
+            .tickets
+            .with_label_values(&["completed"])
+            .set(stats.completed);
+
+        // Start over so that deleted or renamed tags disappear
+        self.inner.tag_tickets.reset();
+        for tag in &stats.by_tag {
+            self.inner
+                .tag_tickets
+                .with_label_values(&[tag.name.as_str()])
+                .set(tag.tickets);
+        }
+    }
+
+    /// Every metric in the Prometheus text exposition format.
+    pub fn render(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.inner.registry.gather(), &mut buffer)
+            .map_err(|e| AppError::Internal(format!("Failed to encode metrics: {}", e)))?;
+        String::from_utf8(buffer)
+            .map_err(|e| AppError::Internal(format!("Failed to encode metrics: {}", e)))
+    }
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A running repository call, see [`Metrics::query_timer`].
+pub struct QueryTimer {
+    metrics: Metrics,
+    repository: &'static str,
+    operation: &'static str,
+    started: Instant,
+}
+
+impl QueryTimer {
+    /// Count the call as failed if the database itself reported an error;
+    /// not-found and validation errors are ordinary outcomes.
+    pub fn record_error(&self, err: &AppError) {
+        if matches!(err, AppError::Database(_)) {
+            self.metrics
+                .inner
+                .query_errors
+                .with_label_values(&[self.repository, self.operation])
+                .inc();
+        }
+    }
+}
+
+impl Drop for QueryTimer {
+    fn drop(&mut self) {
+        let inner = &self.metrics.inner;
+        inner.queries_in_flight.dec();
+        inner
+            .query_duration
+            .with_label_values(&[self.repository, self.operation])
+            .observe(self.started.elapsed().as_secs_f64());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TagCount;
+    use uuid::Uuid;
+
+    fn stats(tags: &[(&str, i64)]) -> TicketStats {
+        TicketStats {
+            open: 3,
+            completed: 1,
+            by_tag: tags
+                .iter()
+                .map(|(name, tickets)| TagCount {
+                    tag_id: Uuid::new_v4(),
+                    name: name.to_string(),
+                    tickets: *tickets,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn renders_requests_and_domain_gauges() {
+        let metrics = Metrics::new();
+        metrics.observe_request("GET", "/api/tickets/{id}", 200, Duration::from_millis(3));
+        metrics.observe_request("GET", "/api/tickets/{id}", 200, Duration::from_millis(5));
+        metrics.set_ticket_stats(&stats(&[("bug", 2), ("ui", 0)]));
+
+        let text = metrics.render().unwrap();
+        assert!(text.contains(
+            r#"http_requests_total{method="GET",route="/api/tickets/{id}",status="200"} 2"#
+        ));
+        assert!(text.contains(
+            r#"http_request_duration_seconds_count{method="GET",route="/api/tickets/{id}",status="200"} 2"#
+        ));
+        assert!(text.contains(r#"tickets{status="open"} 3"#));
+        assert!(text.contains(r#"tag_tickets{tag="bug"} 2"#));
+
+        // A deleted tag no longer shows up on the next refresh
+        metrics.set_ticket_stats(&stats(&[("ui", 1)]));
+        let text = metrics.render().unwrap();
+        assert!(!text.contains(r#"tag="bug""#));
+        assert!(text.contains(r#"tag_tickets{tag="ui"} 1"#));
+    }
+
+    #[test]
+    fn query_timer_tracks_in_flight_calls_and_errors() {
+        let metrics = Metrics::new();
+        let timer = metrics.query_timer("ticket", "find_all");
+        metrics.set_pool_status(&PoolStatus::new(0, 0, 5));
+        assert_eq!(metrics.inner.pool_waiters.get(), 1);
+
+        timer.record_error(&AppError::NotFound("gone".to_string()));
+        timer.record_error(&AppError::Database(sqlx::Error::PoolTimedOut));
+        drop(timer);
+
+        assert_eq!(metrics.inner.queries_in_flight.get(), 0);
+        let text = metrics.render().unwrap();
+        assert!(
+            text.contains(r#"db_query_errors_total{operation="find_all",repository="ticket"} 1"#)
+        );
+        assert!(text.contains(
+            r#"db_query_duration_seconds_count{operation="find_all",repository="ticket"} 1"#
+        ));
+    }
+}
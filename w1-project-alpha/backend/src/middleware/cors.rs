@@ -0,0 +1,274 @@
+use axum::http::{HeaderName, HeaderValue, Method};
+use std::time::Duration;
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, Any, CorsLayer, ExposeHeaders};
+
+use crate::config::CorsConfig;
+
+/// One entry of `cors.allowed_origins`.
+#[derive(Debug, Clone, PartialEq)]
+enum OriginPattern {
+    Any,
+    Exact(String),
+    /// `https://*.example.com`: the scheme and the suffix after the `*`,
+    /// including its leading dot.
+    Subdomain {
+        scheme: String,
+        suffix: String,
+    },
+}
+
+impl OriginPattern {
+    fn parse(pattern: &str) -> Result<Self, String> {
+        let pattern = pattern.trim().to_ascii_lowercase();
+        if pattern == "*" {
+            return Ok(OriginPattern::Any);
+        }
+
+        let (scheme, host) = pattern
+            .split_once("://")
+            .ok_or_else(|| format!("'{}' must look like https://host[:port]", pattern))?;
+        if scheme.is_empty() || host.is_empty() || host.contains('/') {
+            return Err(format!(
+                "'{}' must be a bare origin without a path, like https://host[:port]",
+                pattern
+            ));
+        }
+
+        match host.strip_prefix('*') {
+            Some(suffix)
+                if suffix.starts_with('.') && suffix.len() > 1 && !suffix.contains('*') =>
+            {
+                Ok(OriginPattern::Subdomain {
+                    scheme: format!("{}://", scheme),
+                    suffix: suffix.to_string(),
+                })
+            }
+            Some(_) => Err(format!(
+                "'{}' may only use a wildcard as the leftmost label, like https://*.example.com",
+                pattern
+            )),
+            None if host.contains('*') => Err(format!(
+                "'{}' may only use a wildcard as the leftmost label, like https://*.example.com",
+                pattern
+            )),
+            None => Ok(OriginPattern::Exact(pattern)),
+        }
+    }
+
+    fn matches(&self, origin: &str) -> bool {
+        match self {
+            OriginPattern::Any => true,
+            OriginPattern::Exact(exact) => origin.eq_ignore_ascii_case(exact),
+            OriginPattern::Subdomain { scheme, suffix } => {
+                let origin = origin.to_ascii_lowercase();
+                // The wildcard stands for one or more whole DNS labels, never
+                // for the parent domain itself
+                origin
+                    .strip_prefix(scheme.as_str())
+                    .and_then(|host| host.strip_suffix(suffix.as_str()))
+                    .is_some_and(|labels| {
+                        !labels.is_empty()
+                            && !labels.starts_with('.')
+                            && labels
+                                .chars()
+                                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+                    })
+            }
+        }
+    }
+}
+
+/// Problems with `config` that would make [`cors_layer`] misbehave or panic.
+pub fn validate(config: &CorsConfig) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    for origin in &config.allowed_origins {
+        if let Err(e) = OriginPattern::parse(origin) {
+            problems.push(format!("cors.allowed_origins: {}", e));
+        }
+    }
+    for method in config.allowed_methods.iter().filter(|m| *m != "*") {
+        if Method::from_bytes(method.as_bytes()).is_err() {
+            problems.push(format!(
+                "cors.allowed_methods: '{}' is not an HTTP method",
+                method
+            ));
+        }
+    }
+    for (key, headers) in [
+        ("allowed_headers", &config.allowed_headers),
+        ("exposed_headers", &config.exposed_headers),
+    ] {
+        for header in headers.iter().filter(|h| *h != "*") {
+            if HeaderName::from_bytes(header.as_bytes()).is_err() {
+                problems.push(format!("cors.{}: '{}' is not a header name", key, header));
+            }
+        }
+    }
+
+    if config.allow_credentials {
+        for (key, values) in [
+            ("allowed_origins", &config.allowed_origins),
+            ("allowed_methods", &config.allowed_methods),
+            ("allowed_headers", &config.allowed_headers),
+            ("exposed_headers", &config.exposed_headers),
+        ] {
+            if values.iter().any(|v| v.trim() == "*") {
+                problems.push(format!(
+                    "cors.{} cannot contain '*' when cors.allow_credentials is true",
+                    key
+                ));
+            }
+        }
+    }
+
+    problems
+}
+
+/// Build the CORS layer for `config`, which must have passed [`validate`].
+pub fn cors_layer(config: &CorsConfig) -> CorsLayer {
+    let patterns: Vec<OriginPattern> = config
+        .allowed_origins
+        .iter()
+        .filter_map(|origin| OriginPattern::parse(origin).ok())
+        .collect();
+
+    let allow_origin = if patterns.contains(&OriginPattern::Any) {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::predicate(move |origin: &HeaderValue, _| {
+            origin
+                .to_str()
+                .is_ok_and(|origin| patterns.iter().any(|p| p.matches(origin)))
+        })
+    };
+
+    let allow_methods = if config.allowed_methods.iter().any(|m| m == "*") {
+        AllowMethods::any()
+    } else {
+        AllowMethods::list(
+            config
+                .allowed_methods
+                .iter()
+                .filter_map(|m| Method::from_bytes(m.as_bytes()).ok()),
+        )
+    };
+
+    let allow_headers = if config.allowed_headers.iter().any(|h| h == "*") {
+        AllowHeaders::any()
+    } else {
+        AllowHeaders::list(header_names(&config.allowed_headers))
+    };
+
+    let expose_headers = if config.exposed_headers.iter().any(|h| h == "*") {
+        ExposeHeaders::from(Any)
+    } else {
+        ExposeHeaders::list(header_names(&config.exposed_headers))
+    };
+
+    let layer = CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods(allow_methods)
+        .allow_headers(allow_headers)
+        .expose_headers(expose_headers)
+        .allow_credentials(config.allow_credentials);
+
+    if config.max_age_secs > 0 {
+        layer.max_age(Duration::from_secs(config.max_age_secs))
+    } else {
+        layer
+    }
+}
+
+fn header_names(names: &[String]) -> Vec<HeaderName> {
+    names
+        .iter()
+        .filter_map(|name| HeaderName::from_bytes(name.as_bytes()).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn config(origins: &[&str]) -> CorsConfig {
+        CorsConfig {
+            allowed_origins: origins.iter().map(|o| o.to_string()).collect(),
+            ..CorsConfig::default()
+        }
+    }
+
+    async fn preflight(config: &CorsConfig, origin: &str) -> Option<String> {
+        let app = Router::new()
+            .route("/api/tickets", get(|| async { "ok" }))
+            .layer(cors_layer(config));
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(Method::OPTIONS)
+                    .uri("/api/tickets")
+                    .header("origin", origin)
+                    .header("access-control-request-method", "POST")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .map(|v| v.to_str().unwrap().to_string())
+    }
+
+    #[test]
+    fn wildcard_matches_subdomains_only() {
+        let pattern = OriginPattern::parse("https://*.example.com").unwrap();
+        assert!(pattern.matches("https://app.example.com"));
+        assert!(pattern.matches("https://a.b.EXAMPLE.com"));
+        assert!(!pattern.matches("https://example.com"));
+        assert!(!pattern.matches("http://app.example.com"));
+        assert!(!pattern.matches("https://evil-example.com"));
+        assert!(!pattern.matches("https://app.example.com.evil.net"));
+        assert!(!pattern.matches("https://app.example.com:8443"));
+    }
+
+    #[test]
+    fn validate_rejects_bad_patterns_and_credentialed_wildcards() {
+        let mut bad = config(&["https://app.example.com/", "https://api.*.example.com", "*"]);
+        bad.allowed_methods.push("GET POST".to_string());
+        bad.allow_credentials = true;
+
+        let problems = validate(&bad);
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+        assert!(problems[3].contains("cannot contain '*'"));
+
+        assert!(validate(&CorsConfig::default()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn preflight_allows_only_configured_origins() {
+        let config = config(&["https://app.example.com", "https://*.preview.example.com"]);
+
+        assert_eq!(
+            preflight(&config, "https://app.example.com")
+                .await
+                .as_deref(),
+            Some("https://app.example.com")
+        );
+        assert_eq!(
+            preflight(&config, "https://pr-42.preview.example.com")
+                .await
+                .as_deref(),
+            Some("https://pr-42.preview.example.com")
+        );
+        assert_eq!(preflight(&config, "https://evil.example.net").await, None);
+
+        let any = self::config(&["*"]);
+        assert_eq!(
+            preflight(&any, "https://evil.example.net").await.as_deref(),
+            Some("*")
+        );
+    }
+}
@@ -0,0 +1,9 @@
+pub mod cors;
+pub mod metrics;
+pub mod request_id;
+pub mod versioning;
+
+pub use cors::cors_layer;
+pub use metrics::track_requests;
+pub use request_id::{request_id, RequestId, REQUEST_ID_HEADER};
+pub use versioning::{negotiate_version, ApiVersion};
@@ -0,0 +1,108 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+
+pub static REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Longest client-supplied ID that is propagated; longer ones are replaced.
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+tokio::task_local! {
+    static CURRENT: RequestId;
+}
+
+/// Identifies one request in logs, error bodies and the `X-Request-Id`
+/// response header. Available as a request extension.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl RequestId {
+    /// The ID of the request being handled on this task, if any.
+    pub fn current() -> Option<RequestId> {
+        CURRENT.try_with(Clone::clone).ok()
+    }
+
+    /// Keep a caller's ID so a request can be followed across services, as
+    /// long as it is short printable ASCII; otherwise generate a new one.
+    fn from_request(request: &Request) -> Self {
+        request
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|id| {
+                !id.is_empty()
+                    && id.len() <= MAX_REQUEST_ID_LEN
+                    && id.bytes().all(|b| b.is_ascii_graphic())
+            })
+            .map(|id| RequestId(id.to_string()))
+            .unwrap_or_else(|| RequestId(Uuid::new_v4().to_string()))
+    }
+}
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Assign every request an ID, taken from `X-Request-Id` or generated, and
+/// echo it on the response. Must run outside the trace layer so the request
+/// span can record it.
+pub async fn request_id(mut request: Request, next: Next) -> Response {
+    let id = RequestId::from_request(&request);
+    request.extensions_mut().insert(id.clone());
+
+    let mut response = CURRENT.scope(id.clone(), next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(&id.0) {
+        response
+            .headers_mut()
+            .insert(REQUEST_ID_HEADER.clone(), value);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(id: Option<&str>) -> Request {
+        let mut builder = Request::builder().uri("/");
+        if let Some(id) = id {
+            builder = builder.header("x-request-id", id);
+        }
+        builder.body(axum::body::Body::empty()).unwrap()
+    }
+
+    #[test]
+    fn keeps_valid_ids_and_replaces_others() {
+        assert_eq!(
+            RequestId::from_request(&request(Some("edge-42"))).0,
+            "edge-42"
+        );
+
+        for id in [
+            None,
+            Some(""),
+            Some("has space"),
+            Some(&"x".repeat(200)[..]),
+        ] {
+            let generated = RequestId::from_request(&request(id)).0;
+            assert!(Uuid::parse_str(&generated).is_ok(), "{:?}", id);
+        }
+    }
+
+    #[tokio::test]
+    async fn current_is_scoped_to_the_request_task() {
+        assert_eq!(RequestId::current(), None);
+        let id = RequestId("abc".to_string());
+        let seen = CURRENT
+            .scope(id.clone(), async { RequestId::current() })
+            .await;
+        assert_eq!(seen, Some(id));
+    }
+}
@@ -0,0 +1,163 @@
+use axum::{
+    extract::{MatchedPath, OriginalUri, Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+    Router,
+};
+use chrono::{NaiveDate, NaiveTime};
+use std::sync::LazyLock;
+use tower::ServiceExt;
+
+pub const V1_MEDIA_TYPE: &str = "application/vnd.alpha.v1+json";
+pub const V2_MEDIA_TYPE: &str = "application/vnd.alpha.v2+json";
+
+/// The day unversioned `/api` paths were deprecated: the release that
+/// introduced `/api/v1` and `/api/v2`.
+pub const DEPRECATION_DATE: NaiveDate = NaiveDate::from_ymd_opt(2026, 11, 1).unwrap();
+
+/// The day unversioned `/api` paths stop being served, six months after
+/// [`DEPRECATION_DATE`] to give clients that long to move to `/api/v1`.
+pub const SUNSET_DATE: NaiveDate = NaiveDate::from_ymd_opt(2027, 5, 1).unwrap();
+
+static DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+static SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// [`DEPRECATION_DATE`] at midnight UTC as an RFC 9745 `Deprecation` value,
+/// a structured-field date (`@` and Unix seconds).
+static DEPRECATION_VALUE: LazyLock<HeaderValue> = LazyLock::new(|| {
+    let since = DEPRECATION_DATE.and_time(NaiveTime::MIN).and_utc();
+    HeaderValue::from_str(&format!("@{}", since.timestamp())).unwrap()
+});
+
+/// [`SUNSET_DATE`] at midnight UTC as an RFC 8594 `Sunset` value, an
+/// HTTP-date.
+static SUNSET_VALUE: LazyLock<HeaderValue> = LazyLock::new(|| {
+    let sunset = SUNSET_DATE.and_time(NaiveTime::MIN).and_utc();
+    HeaderValue::from_str(&sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string()).unwrap()
+});
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiVersion {
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    /// The version asked for by a vendor media type in `Accept`, if any.
+    pub fn from_accept(headers: &HeaderMap) -> Option<Self> {
+        headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','))
+            .map(|media| media.split(';').next().unwrap_or_default().trim())
+            .find_map(|media| {
+                if media.eq_ignore_ascii_case(V1_MEDIA_TYPE) {
+                    Some(ApiVersion::V1)
+                } else if media.eq_ignore_ascii_case(V2_MEDIA_TYPE) {
+                    Some(ApiVersion::V2)
+                } else {
+                    None
+                }
+            })
+    }
+
+    pub fn media_type(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => V1_MEDIA_TYPE,
+            ApiVersion::V2 => V2_MEDIA_TYPE,
+        }
+    }
+}
+
+/// Serve a request to the unversioned `/api` prefix, which is routed as v1.
+///
+/// A vendor media type in `Accept` picks the version, and JSON responses
+/// then carry that media type. Requests without one get v1 along with
+/// `Deprecation`, `Sunset` and a `Link` to the same path under `/api/v1`.
+pub async fn negotiate_version(
+    State(v2): State<Router>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let version = ApiVersion::from_accept(request.headers());
+    let successor = request
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().replacen("/api", "/api/v1", 1));
+
+    let mut response = match version {
+        Some(ApiVersion::V2) => {
+            // The v2 router records its own matched path; keeping the v1 one
+            // would make it append to it
+            request.extensions_mut().remove::<MatchedPath>();
+            v2.oneshot(request).await.unwrap_or_else(|err| match err {})
+        }
+        _ => next.run(request).await,
+    };
+
+    let headers = response.headers_mut();
+    headers.append(header::VARY, HeaderValue::from_static("accept"));
+    match version {
+        Some(version) => {
+            let is_json = headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with("application/json"));
+            if is_json {
+                headers.insert(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static(version.media_type()),
+                );
+            }
+        }
+        None => {
+            headers.insert(DEPRECATION.clone(), DEPRECATION_VALUE.clone());
+            headers.insert(SUNSET.clone(), SUNSET_VALUE.clone());
+            if let Some(link) = successor.and_then(|path| {
+                HeaderValue::from_str(&format!("<{}>; rel=\"successor-version\"", path)).ok()
+            }) {
+                headers.insert(header::LINK, link);
+            }
+        }
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn reads_the_version_from_accept() {
+        assert_eq!(ApiVersion::from_accept(&HeaderMap::new()), None);
+        assert_eq!(ApiVersion::from_accept(&accept("application/json")), None);
+        assert_eq!(
+            ApiVersion::from_accept(&accept("application/vnd.alpha.v2+json")),
+            Some(ApiVersion::V2)
+        );
+        assert_eq!(
+            ApiVersion::from_accept(&accept(
+                "text/html, application/vnd.alpha.v1+json;q=0.9, */*"
+            )),
+            Some(ApiVersion::V1)
+        );
+        assert_eq!(
+            ApiVersion::from_accept(&accept("Application/VND.Alpha.V2+JSON")),
+            Some(ApiVersion::V2)
+        );
+    }
+
+    #[test]
+    fn formats_the_deprecation_and_sunset_dates() {
+        assert_eq!(*DEPRECATION_VALUE, "@1793491200");
+        assert_eq!(*SUNSET_VALUE, "Sat, 01 May 2027 00:00:00 GMT");
+    }
+}
@@ -1,5 +0,0 @@
-pub mod tag;
-pub mod ticket;
-
-pub use tag::*;
-pub use ticket::*;
@@ -0,0 +1,114 @@
+use utoipa::openapi::path::Operation;
+use utoipa::OpenApi;
+
+use crate::handlers::{collab_handler, tag_handler, ticket_handler, v2, webhook_handler};
+use crate::middleware::versioning::{SUNSET_DATE, V2_MEDIA_TYPE};
+
+/// Where the document is served.
+pub const SPEC_PATH: &str = "/api/openapi.json";
+
+/// Where the Swagger UI is served.
+pub const UI_PATH: &str = "/api/docs";
+
+/// Title, description and tags shared by every version.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Project Alpha API",
+        description = "Tickets, tags, webhooks and the collaboration socket."
+    ),
+    tags(
+        (name = "tickets", description = "Tickets, their tags, bulk export and import"),
+        (name = "tags", description = "Tags that can be attached to tickets"),
+        (name = "webhooks", description = "Signed HTTP callbacks on ticket changes"),
+        (name = "collaboration", description = "Live change events, presence and typing hints"),
+    )
+)]
+struct ApiInfo;
+
+/// Routes that are the same in every version.
+#[derive(OpenApi)]
+#[openapi(paths(
+    ticket_handler::export_tickets,
+    ticket_handler::import_tickets,
+    ticket_handler::add_tag_to_ticket,
+    ticket_handler::remove_tag_from_ticket,
+    collab_handler::get_ticket_presence,
+    collab_handler::collab_ws,
+    tag_handler::get_tags,
+    tag_handler::create_tag,
+    tag_handler::get_tag,
+    tag_handler::update_tag,
+    tag_handler::delete_tag,
+    webhook_handler::get_webhooks,
+    webhook_handler::create_webhook,
+    webhook_handler::get_webhook,
+    webhook_handler::update_webhook,
+    webhook_handler::delete_webhook,
+    webhook_handler::get_webhook_deliveries,
+))]
+struct SharedApi;
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    ticket_handler::get_tickets,
+    ticket_handler::create_ticket,
+    ticket_handler::get_ticket,
+    ticket_handler::update_ticket,
+    ticket_handler::delete_ticket,
+    ticket_handler::toggle_ticket_completed,
+))]
+struct V1Api;
+
+#[derive(OpenApi)]
+#[openapi(paths(
+    v2::get_tickets,
+    v2::create_ticket,
+    v2::get_ticket,
+    v2::update_ticket,
+    ticket_handler::delete_ticket,
+    v2::toggle_ticket_completed,
+))]
+struct V2Api;
+
+/// The OpenAPI 3 document of the routes in [`create_routes`], generated from
+/// the `#[utoipa::path]` annotations on the handlers and the schemas of the
+/// models they use.
+///
+/// Each version is listed under its own prefix, with operation ids prefixed
+/// by the version so they stay unique. The deprecated unversioned `/api`
+/// prefix is described in the info section rather than listed again.
+///
+/// [`create_routes`]: crate::routes::create_routes
+pub fn spec() -> utoipa::openapi::OpenApi {
+    let mut doc = ApiInfo::openapi();
+    doc.info.description = Some(format!(
+        "Tickets, tags, webhooks and the collaboration socket.\n\n\
+         Unversioned `/api/...` paths serve v1 and are deprecated until {}; \
+         send `Accept: {}` to get v2 from them instead.",
+        SUNSET_DATE, V2_MEDIA_TYPE
+    ));
+
+    let v1 = V1Api::openapi().merge_from(SharedApi::openapi());
+    let v2 = V2Api::openapi().merge_from(SharedApi::openapi());
+    doc.nest("/api/v1", versioned(v1, "v1"))
+        .nest("/api/v2", versioned(v2, "v2"))
+}
+
+fn versioned(mut api: utoipa::openapi::OpenApi, version: &str) -> utoipa::openapi::OpenApi {
+    for item in api.paths.paths.values_mut() {
+        let operations: [&mut Option<Operation>; 5] = [
+            &mut item.get,
+            &mut item.post,
+            &mut item.put,
+            &mut item.patch,
+            &mut item.delete,
+        ];
+        for operation in operations.into_iter().flatten() {
+            if let Some(id) = &operation.operation_id {
+                operation.operation_id = Some(format!("{}_{}", version, id));
+            }
+        }
+    }
+    api
+}
@@ -0,0 +1,367 @@
+use super::{TagStore, TicketStore};
+use crate::models::{
+    CreateTagRequest, CreateTicketRequest, ImportTicket, Tag, TagCount, Ticket, TicketFilter,
+    TicketStats, TicketWithTags, UpdateTagRequest, UpdateTicketRequest,
+};
+use crate::utils::error::{AppError, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex, MutexGuard};
+use uuid::Uuid;
+
+#[derive(Default)]
+struct Data {
+    tickets: HashMap<Uuid, Ticket>,
+    tags: HashMap<Uuid, Tag>,
+    /// `(ticket_id, tag_id)` pairs, like the `ticket_tags` table.
+    links: BTreeSet<(Uuid, Uuid)>,
+}
+
+impl Data {
+    fn ticket_with_tags(&self, ticket: &Ticket) -> TicketWithTags {
+        let mut tags: Vec<Tag> = self
+            .links
+            .range((ticket.id, Uuid::nil())..=(ticket.id, Uuid::max()))
+            .filter_map(|(_, tag_id)| self.tags.get(tag_id).cloned())
+            .collect();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        TicketWithTags {
+            ticket: ticket.clone(),
+            tags,
+        }
+    }
+
+    fn matching(&self, filter: &TicketFilter) -> Vec<TicketWithTags> {
+        let search = filter.search.as_ref().map(|s| s.to_lowercase());
+        let mut tickets: Vec<&Ticket> = self
+            .tickets
+            .values()
+            .filter(|t| {
+                filter
+                    .tag
+                    .is_none_or(|tag| self.links.contains(&(t.id, tag)))
+            })
+            .filter(|t| {
+                search
+                    .as_ref()
+                    .is_none_or(|s| t.title.to_lowercase().contains(s))
+            })
+            .filter(|t| filter.completed.is_none_or(|c| t.completed == c))
+            .collect();
+        tickets.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(a.id.cmp(&b.id)));
+        tickets
+            .into_iter()
+            .map(|t| self.ticket_with_tags(t))
+            .collect()
+    }
+
+    fn ticket_mut(&mut self, id: Uuid) -> Result<&mut Ticket> {
+        self.tickets
+            .get_mut(&id)
+            .ok_or_else(|| AppError::NotFound(format!("Ticket with id {} not found", id)))
+    }
+
+    fn ensure_tag(&self, id: Uuid) -> Result<()> {
+        if !self.tags.contains_key(&id) {
+            return Err(AppError::NotFound(format!("Tag with id {} not found", id)));
+        }
+        Ok(())
+    }
+
+    fn ensure_unique_name(&self, name: &str, id: Option<Uuid>) -> Result<()> {
+        if self
+            .tags
+            .values()
+            .any(|tag| tag.name == name && Some(tag.id) != id)
+        {
+            return Err(AppError::Validation(format!(
+                "Tag with name '{}' already exists",
+                name
+            )));
+        }
+        Ok(())
+    }
+
+    fn insert_tag(&mut self, name: String, color: Option<String>, now: DateTime<Utc>) -> Tag {
+        let tag = Tag {
+            id: Uuid::new_v4(),
+            name,
+            color,
+            created_at: now,
+        };
+        self.tags.insert(tag.id, tag.clone());
+        tag
+    }
+}
+
+/// Process-local store implementing both `TicketStore` and `TagStore`.
+///
+/// Mirrors the Postgres repositories: same filters and ordering, unique tag
+/// names, and deleting a ticket or tag removes its links. Nothing is
+/// persisted and webhooks are not queued. Clones share the same data.
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    data: Arc<Mutex<Data>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn data(&self) -> MutexGuard<'_, Data> {
+        self.data.lock().expect("memory store poisoned")
+    }
+}
+
+/// Timestamps are stored with microsecond precision, as in Postgres.
+fn now() -> DateTime<Utc> {
+    let now = Utc::now();
+    now.duration_trunc(TimeDelta::microseconds(1))
+        .unwrap_or(now)
+}
+
+#[async_trait]
+impl TicketStore for MemoryStore {
+    async fn find_all(&self, filter: &TicketFilter) -> Result<Vec<TicketWithTags>> {
+        Ok(self.data().matching(filter))
+    }
+
+    async fn find_page(
+        &self,
+        filter: &TicketFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<TicketWithTags>, i64)> {
+        let tickets = self.data().matching(filter);
+        let total = tickets.len() as i64;
+        let page = tickets
+            .into_iter()
+            .skip(usize::try_from(offset).unwrap_or(0))
+            .take(usize::try_from(limit).unwrap_or(0))
+            .collect();
+        Ok((page, total))
+    }
+
+    fn stream_all(&self, filter: TicketFilter) -> BoxStream<'static, Result<TicketWithTags>> {
+        let tickets = self.data().matching(&filter);
+        stream::iter(tickets.into_iter().map(Ok)).boxed()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TicketWithTags>> {
+        let data = self.data();
+        Ok(data.tickets.get(&id).map(|t| data.ticket_with_tags(t)))
+    }
+
+    async fn find_tags(&self, ticket_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Tag>>> {
+        let data = self.data();
+        Ok(ticket_ids
+            .iter()
+            .filter_map(|id| data.tickets.get(id))
+            .map(|t| data.ticket_with_tags(t))
+            .filter(|t| !t.tags.is_empty())
+            .map(|t| (t.ticket.id, t.tags))
+            .collect())
+    }
+
+    async fn create(&self, request: CreateTicketRequest) -> Result<Ticket> {
+        let mut data = self.data();
+        let tag_ids = request.tag_ids.unwrap_or_default();
+        for tag_id in &tag_ids {
+            data.ensure_tag(*tag_id)?;
+        }
+
+        let now = now();
+        let ticket = Ticket {
+            id: Uuid::new_v4(),
+            title: request.title,
+            description: request.description,
+            completed: false,
+            created_at: now,
+            updated_at: now,
+        };
+        data.tickets.insert(ticket.id, ticket.clone());
+        for tag_id in tag_ids {
+            data.links.insert((ticket.id, tag_id));
+        }
+        Ok(ticket)
+    }
+
+    async fn update(&self, id: Uuid, request: UpdateTicketRequest) -> Result<Ticket> {
+        let mut data = self.data();
+        let ticket = data.ticket_mut(id)?;
+
+        if request.title.is_none() && request.description.is_none() && request.completed.is_none() {
+            return Ok(ticket.clone());
+        }
+        if let Some(title) = request.title {
+            ticket.title = title;
+        }
+        if let Some(description) = request.description {
+            ticket.description = Some(description);
+        }
+        if let Some(completed) = request.completed {
+            ticket.completed = completed;
+        }
+        ticket.updated_at = now();
+        Ok(ticket.clone())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        let mut data = self.data();
+        if data.tickets.remove(&id).is_none() {
+            return Err(AppError::NotFound(format!(
+                "Ticket with id {} not found",
+                id
+            )));
+        }
+        data.links.retain(|(ticket_id, _)| *ticket_id != id);
+        Ok(())
+    }
+
+    async fn toggle_completed(&self, id: Uuid) -> Result<Ticket> {
+        let mut data = self.data();
+        let ticket = data.ticket_mut(id)?;
+        ticket.completed = !ticket.completed;
+        ticket.updated_at = now();
+        Ok(ticket.clone())
+    }
+
+    async fn add_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<bool> {
+        let mut data = self.data();
+        data.ticket_mut(ticket_id)?;
+        data.ensure_tag(tag_id)?;
+        Ok(data.links.insert((ticket_id, tag_id)))
+    }
+
+    async fn remove_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()> {
+        if !self.data().links.remove(&(ticket_id, tag_id)) {
+            return Err(AppError::NotFound(format!(
+                "Tag {} not found on ticket {}",
+                tag_id, ticket_id
+            )));
+        }
+        Ok(())
+    }
+
+    async fn import(
+        &self,
+        tickets: &[ImportTicket],
+        mut tag_ids: HashMap<String, Uuid>,
+    ) -> Result<(Vec<Ticket>, Vec<Tag>)> {
+        let mut data = self.data();
+        let now = now();
+
+        let mut created_tags = Vec::new();
+        for name in tickets.iter().flat_map(|t| &t.tags) {
+            if tag_ids.contains_key(name) {
+                continue;
+            }
+            // Created since the caller looked the names up
+            if let Some(tag) = data.tags.values().find(|tag| &tag.name == name) {
+                tag_ids.insert(name.clone(), tag.id);
+                continue;
+            }
+            let tag = data.insert_tag(name.clone(), None, now);
+            tag_ids.insert(tag.name.clone(), tag.id);
+            created_tags.push(tag);
+        }
+
+        let mut imported = Vec::with_capacity(tickets.len());
+        for ticket in tickets {
+            let created_at = ticket.created_at.unwrap_or(now);
+            let row = Ticket {
+                id: Uuid::new_v4(),
+                title: ticket.title.clone(),
+                description: ticket.description.clone(),
+                completed: ticket.completed,
+                created_at,
+                updated_at: ticket.updated_at.unwrap_or(created_at),
+            };
+            for name in &ticket.tags {
+                data.links.insert((row.id, tag_ids[name]));
+            }
+            data.tickets.insert(row.id, row.clone());
+            imported.push(row);
+        }
+
+        Ok((imported, created_tags))
+    }
+
+    async fn stats(&self) -> Result<TicketStats> {
+        let data = self.data();
+        let completed = data.tickets.values().filter(|t| t.completed).count() as i64;
+        let mut by_tag: Vec<TagCount> = data
+            .tags
+            .values()
+            .map(|tag| TagCount {
+                tag_id: tag.id,
+                name: tag.name.clone(),
+                tickets: data.links.iter().filter(|(_, id)| *id == tag.id).count() as i64,
+            })
+            .collect();
+        by_tag.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(TicketStats {
+            open: data.tickets.len() as i64 - completed,
+            completed,
+            by_tag,
+        })
+    }
+}
+
+#[async_trait]
+impl TagStore for MemoryStore {
+    async fn find_all(&self) -> Result<Vec<Tag>> {
+        let mut tags: Vec<Tag> = self.data().tags.values().cloned().collect();
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(tags)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>> {
+        Ok(self.data().tags.get(&id).cloned())
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<Tag>> {
+        Ok(self
+            .data()
+            .tags
+            .values()
+            .find(|tag| tag.name == name)
+            .cloned())
+    }
+
+    async fn create(&self, request: CreateTagRequest) -> Result<Tag> {
+        let mut data = self.data();
+        data.ensure_unique_name(&request.name, None)?;
+        Ok(data.insert_tag(request.name, request.color, now()))
+    }
+
+    async fn update(&self, id: Uuid, request: UpdateTagRequest) -> Result<Tag> {
+        let mut data = self.data();
+        data.ensure_tag(id)?;
+        if let Some(ref name) = request.name {
+            data.ensure_unique_name(name, Some(id))?;
+        }
+
+        let tag = data.tags.get_mut(&id).expect("checked above");
+        if let Some(name) = request.name {
+            tag.name = name;
+        }
+        if let Some(color) = request.color {
+            tag.color = Some(color);
+        }
+        Ok(tag.clone())
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        let mut data = self.data();
+        if data.tags.remove(&id).is_none() {
+            return Err(AppError::NotFound(format!("Tag with id {} not found", id)));
+        }
+        data.links.retain(|(_, tag_id)| *tag_id != id);
+        Ok(())
+    }
+}
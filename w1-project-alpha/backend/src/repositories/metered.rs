@@ -0,0 +1,224 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::task::Poll;
+use std::time::Duration;
+use tracing::{Instrument, Span};
+use uuid::Uuid;
+
+use super::{TagStore, TicketStore, WebhookStore};
+use crate::metrics::Metrics;
+use crate::models::{
+    CreateTagRequest, CreateTicketRequest, CreateWebhookRequest, ImportTicket, PendingDelivery,
+    Tag, Ticket, TicketFilter, TicketStats, TicketWithTags, UpdateTagRequest, UpdateTicketRequest,
+    UpdateWebhookRequest, Webhook, WebhookDelivery,
+};
+use crate::utils::error::Result;
+
+/// Wraps a store and records the duration of every call in [`Metrics`],
+/// labelled with the repository name and the method. Each call also runs
+/// in a `db.query` span, which is what gets exported as the database span
+/// of a trace; sqlx's per-statement log events are recorded inside it.
+pub struct Metered<S: ?Sized> {
+    inner: Arc<S>,
+    metrics: Metrics,
+    repository: &'static str,
+}
+
+impl<S: ?Sized> Metered<S> {
+    pub fn new(inner: Arc<S>, metrics: Metrics, repository: &'static str) -> Self {
+        Self {
+            inner,
+            metrics,
+            repository,
+        }
+    }
+
+    fn span(&self, operation: &'static str) -> Span {
+        tracing::info_span!(
+            "db.query",
+            otel.name = %format!("{}.{}", self.repository, operation),
+            otel.kind = "client",
+            db.operation.name = operation,
+            repository = self.repository,
+        )
+    }
+
+    async fn time<T>(
+        &self,
+        operation: &'static str,
+        future: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        self.metrics
+            .time_query(self.repository, operation, future)
+            .instrument(self.span(operation))
+            .await
+    }
+}
+
+#[async_trait]
+impl TicketStore for Metered<dyn TicketStore> {
+    async fn find_all(&self, filter: &TicketFilter) -> Result<Vec<TicketWithTags>> {
+        self.time("find_all", self.inner.find_all(filter)).await
+    }
+
+    async fn find_page(
+        &self,
+        filter: &TicketFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<TicketWithTags>, i64)> {
+        self.time("find_page", self.inner.find_page(filter, limit, offset))
+            .await
+    }
+
+    /// Timed from when the stream is created until it is dropped.
+    fn stream_all(&self, filter: TicketFilter) -> BoxStream<'static, Result<TicketWithTags>> {
+        let timer = self.metrics.query_timer(self.repository, "stream_all");
+        let span = self.span("stream_all");
+        let mut inner = span.in_scope(|| self.inner.stream_all(filter));
+        stream::poll_fn(move |cx| {
+            let _entered = span.enter();
+            let item = inner.poll_next_unpin(cx);
+            if let Poll::Ready(Some(Err(err))) = &item {
+                timer.record_error(err);
+            }
+            item
+        })
+        .boxed()
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TicketWithTags>> {
+        self.time("find_by_id", self.inner.find_by_id(id)).await
+    }
+
+    async fn find_tags(&self, ticket_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Tag>>> {
+        self.time("find_tags", self.inner.find_tags(ticket_ids))
+            .await
+    }
+
+    async fn create(&self, request: CreateTicketRequest) -> Result<Ticket> {
+        self.time("create", self.inner.create(request)).await
+    }
+
+    async fn update(&self, id: Uuid, request: UpdateTicketRequest) -> Result<Ticket> {
+        self.time("update", self.inner.update(id, request)).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.time("delete", self.inner.delete(id)).await
+    }
+
+    async fn toggle_completed(&self, id: Uuid) -> Result<Ticket> {
+        self.time("toggle_completed", self.inner.toggle_completed(id))
+            .await
+    }
+
+    async fn add_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<bool> {
+        self.time("add_tag", self.inner.add_tag(ticket_id, tag_id))
+            .await
+    }
+
+    async fn remove_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()> {
+        self.time("remove_tag", self.inner.remove_tag(ticket_id, tag_id))
+            .await
+    }
+
+    async fn import(
+        &self,
+        tickets: &[ImportTicket],
+        tag_ids: HashMap<String, Uuid>,
+    ) -> Result<(Vec<Ticket>, Vec<Tag>)> {
+        self.time("import", self.inner.import(tickets, tag_ids))
+            .await
+    }
+
+    async fn stats(&self) -> Result<TicketStats> {
+        self.time("stats", self.inner.stats()).await
+    }
+}
+
+#[async_trait]
+impl TagStore for Metered<dyn TagStore> {
+    async fn find_all(&self) -> Result<Vec<Tag>> {
+        self.time("find_all", self.inner.find_all()).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>> {
+        self.time("find_by_id", self.inner.find_by_id(id)).await
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<Tag>> {
+        self.time("find_by_name", self.inner.find_by_name(name))
+            .await
+    }
+
+    async fn create(&self, request: CreateTagRequest) -> Result<Tag> {
+        self.time("create", self.inner.create(request)).await
+    }
+
+    async fn update(&self, id: Uuid, request: UpdateTagRequest) -> Result<Tag> {
+        self.time("update", self.inner.update(id, request)).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.time("delete", self.inner.delete(id)).await
+    }
+}
+
+#[async_trait]
+impl WebhookStore for Metered<dyn WebhookStore> {
+    async fn find_all(&self) -> Result<Vec<Webhook>> {
+        self.time("find_all", self.inner.find_all()).await
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Webhook>> {
+        self.time("find_by_id", self.inner.find_by_id(id)).await
+    }
+
+    async fn create(&self, request: CreateWebhookRequest) -> Result<Webhook> {
+        self.time("create", self.inner.create(request)).await
+    }
+
+    async fn update(&self, id: Uuid, request: UpdateWebhookRequest) -> Result<Webhook> {
+        self.time("update", self.inner.update(id, request)).await
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        self.time("delete", self.inner.delete(id)).await
+    }
+
+    async fn find_deliveries(&self, webhook_id: Uuid, limit: i64) -> Result<Vec<WebhookDelivery>> {
+        self.time(
+            "find_deliveries",
+            self.inner.find_deliveries(webhook_id, limit),
+        )
+        .await
+    }
+
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<PendingDelivery>> {
+        self.time("claim_due", self.inner.claim_due(limit, lease))
+            .await
+    }
+
+    async fn mark_delivered(&self, id: Uuid, status_code: i32) -> Result<()> {
+        self.time("mark_delivered", self.inner.mark_delivered(id, status_code))
+            .await
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        status_code: Option<i32>,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.time(
+            "mark_failed",
+            self.inner.mark_failed(id, status_code, error, retry_at),
+        )
+        .await
+    }
+}
@@ -1,22 +1,58 @@
 use sqlx::PgPool;
+use std::sync::Arc;
 
+use crate::metrics::Metrics;
+
+pub mod memory;
+pub mod metered;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod store;
 pub mod tag_repository;
 pub mod ticket_repository;
+pub mod webhook_repository;
 
+pub use memory::MemoryStore;
+pub use metered::Metered;
+pub use store::{TagStore, TicketStore, WebhookStore};
 pub use tag_repository::TagRepository;
 pub use ticket_repository::TicketRepository;
+pub use webhook_repository::WebhookRepository;
 
+/// The stores of one storage backend.
 #[derive(Clone)]
 pub struct Repositories {
-    pub ticket: TicketRepository,
-    pub tag: TagRepository,
+    pub ticket: Arc<dyn TicketStore>,
+    pub tag: Arc<dyn TagStore>,
+    pub webhook: Arc<dyn WebhookStore>,
 }
 
 impl Repositories {
+    /// Postgres repositories.
     pub fn new(pool: PgPool) -> Self {
         Self {
-            ticket: TicketRepository::new(pool.clone()),
-            tag: TagRepository::new(pool),
+            ticket: Arc::new(TicketRepository::new(pool.clone())),
+            tag: Arc::new(TagRepository::new(pool.clone())),
+            webhook: Arc::new(WebhookRepository::new(pool)),
+        }
+    }
+
+    /// SQLite repositories.
+    #[cfg(feature = "sqlite")]
+    pub fn sqlite(pool: sqlx::SqlitePool) -> Self {
+        Self {
+            ticket: Arc::new(sqlite::SqliteTicketRepository::new(pool.clone())),
+            tag: Arc::new(sqlite::SqliteTagRepository::new(pool.clone())),
+            webhook: Arc::new(sqlite::SqliteWebhookRepository::new(pool)),
+        }
+    }
+
+    /// The same stores, with every call timed in `metrics`.
+    pub fn metered(self, metrics: &Metrics) -> Self {
+        Self {
+            ticket: Arc::new(Metered::new(self.ticket, metrics.clone(), "ticket")),
+            tag: Arc::new(Metered::new(self.tag, metrics.clone(), "tag")),
+            webhook: Arc::new(Metered::new(self.webhook, metrics.clone(), "webhook")),
         }
     }
 }
@@ -0,0 +1,21 @@
+//! SQLite implementations of the stores, for single-user installs.
+//!
+//! Behaves like the Postgres repositories; title search uses the FTS5
+//! trigram index from `migrations_sqlite/` instead of `pg_trgm`.
+
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+
+pub mod tag_repository;
+pub mod ticket_repository;
+pub mod webhook_repository;
+
+pub use tag_repository::SqliteTagRepository;
+pub use ticket_repository::SqliteTicketRepository;
+pub use webhook_repository::SqliteWebhookRepository;
+
+/// The current time with microsecond precision, as Postgres stores it.
+fn now() -> DateTime<Utc> {
+    let now = Utc::now();
+    now.duration_trunc(TimeDelta::microseconds(1))
+        .unwrap_or(now)
+}
@@ -0,0 +1,117 @@
+use super::now;
+use crate::models::{CreateTagRequest, Tag, UpdateTagRequest};
+use crate::repositories::TagStore;
+use crate::utils::error::{AppError, Result};
+use async_trait::async_trait;
+use sqlx::SqlitePool;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct SqliteTagRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteTagRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TagStore for SqliteTagRepository {
+    async fn find_all(&self) -> Result<Vec<Tag>> {
+        let tags = sqlx::query_as::<_, Tag>(
+            "SELECT id, name, color, created_at
+             FROM tags
+             ORDER BY name",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(tags)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>> {
+        let tag = sqlx::query_as::<_, Tag>(
+            "SELECT id, name, color, created_at
+             FROM tags
+             WHERE id = ?1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<Tag>> {
+        let tag = sqlx::query_as::<_, Tag>(
+            "SELECT id, name, color, created_at
+             FROM tags
+             WHERE name = ?1",
+        )
+        .bind(name)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(tag)
+    }
+
+    async fn create(&self, request: CreateTagRequest) -> Result<Tag> {
+        let tag = sqlx::query_as::<_, Tag>(
+            "INSERT INTO tags (id, name, color, created_at)
+             VALUES (?1, ?2, ?3, ?4)
+             RETURNING id, name, color, created_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&request.name)
+        .bind(&request.color)
+        .bind(now())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| duplicate_name(e, &request.name))?;
+
+        Ok(tag)
+    }
+
+    async fn update(&self, id: Uuid, request: UpdateTagRequest) -> Result<Tag> {
+        let tag = sqlx::query_as::<_, Tag>(
+            "UPDATE tags
+             SET name = COALESCE(?1, name),
+                 color = COALESCE(?2, color)
+             WHERE id = ?3
+             RETURNING id, name, color, created_at",
+        )
+        .bind(&request.name)
+        .bind(&request.color)
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| duplicate_name(e, request.name.as_deref().unwrap_or_default()))?;
+
+        tag.ok_or_else(|| AppError::NotFound(format!("Tag with id {} not found", id)))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        let result = sqlx::query("DELETE FROM tags WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("Tag with id {} not found", id)));
+        }
+
+        Ok(())
+    }
+}
+
+/// Report a violated unique constraint on `tags.name` as a validation error.
+pub(super) fn duplicate_name(err: sqlx::Error, name: &str) -> AppError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::Validation(format!("Tag with name '{}' already exists", name))
+        }
+        _ => AppError::Database(err),
+    }
+}
@@ -0,0 +1,526 @@
+use super::{now, SqliteWebhookRepository};
+use crate::events::ChangeEvent;
+use crate::models::{
+    CreateTicketRequest, ImportTicket, Tag, TagCount, Ticket, TicketFilter, TicketStats,
+    TicketWithTags, UpdateTicketRequest,
+};
+use crate::repositories::TicketStore;
+use crate::utils::error::{AppError, Result};
+use async_trait::async_trait;
+use futures_util::stream::{BoxStream, TryStreamExt};
+use sqlx::sqlite::{Sqlite, SqliteArguments};
+use sqlx::types::Json;
+use sqlx::{FromRow, SqlitePool};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Shortest search term the trigram index can match; shorter terms fall
+/// back to `LIKE`.
+const MIN_FTS_TERM: usize = 3;
+
+/// A ticket row with its tags aggregated into a JSON array by the database.
+#[derive(FromRow)]
+struct TicketWithTagsRow {
+    #[sqlx(flatten)]
+    ticket: Ticket,
+    tags: Json<Vec<Tag>>,
+}
+
+impl From<TicketWithTagsRow> for TicketWithTags {
+    fn from(row: TicketWithTagsRow) -> Self {
+        TicketWithTags {
+            ticket: row.ticket,
+            tags: row.tags.0,
+        }
+    }
+}
+
+/// A tag along with the ticket it is linked to.
+#[derive(FromRow)]
+struct TicketTagRow {
+    ticket_id: Uuid,
+    #[sqlx(flatten)]
+    tag: Tag,
+}
+
+/// Select tickets with their tags sorted by name. Tag ids are emitted as hex
+/// so they survive the trip through JSON.
+const SELECT_WITH_TAGS: &str =
+    "SELECT t.id, t.title, t.description, t.completed, t.created_at, t.updated_at,
+        (SELECT json_group_array(
+                    json_object(
+                        'id', lower(hex(g.id)), 'name', g.name,
+                        'color', g.color, 'created_at', g.created_at
+                    )
+                    ORDER BY g.name
+                )
+         FROM ticket_tags tt
+         JOIN tags g ON g.id = tt.tag_id
+         WHERE tt.ticket_id = t.id) AS tags
+ FROM tickets t";
+
+/// Build the filtered ticket query shared by `find_all` and `stream_all`.
+fn filtered_query(filter: TicketFilter) -> (String, SqliteArguments<'static>) {
+    use sqlx::Arguments;
+
+    let mut query = String::from(SELECT_WITH_TAGS);
+    let mut conditions = Vec::new();
+    let mut args = SqliteArguments::default();
+    let mut bind_count = 0;
+
+    if let Some(tag_id) = filter.tag {
+        bind_count += 1;
+        conditions.push(format!(
+            "EXISTS (SELECT 1 FROM ticket_tags f WHERE f.ticket_id = t.id AND f.tag_id = ?{})",
+            bind_count
+        ));
+        args.add(tag_id).expect("uuid argument");
+    }
+
+    if let Some(search) = filter.search {
+        bind_count += 1;
+        if search.chars().count() >= MIN_FTS_TERM {
+            // Quote the term so FTS5 matches it as a plain substring
+            conditions.push(format!(
+                "t.seq IN (SELECT rowid FROM tickets_fts WHERE tickets_fts MATCH ?{})",
+                bind_count
+            ));
+            args.add(format!("\"{}\"", search.replace('"', "\"\"")))
+                .expect("text argument");
+        } else {
+            conditions.push(format!("t.title LIKE ?{}", bind_count));
+            args.add(format!("%{}%", search)).expect("text argument");
+        }
+    }
+
+    if let Some(completed) = filter.completed {
+        bind_count += 1;
+        conditions.push(format!("t.completed = ?{}", bind_count));
+        args.add(completed).expect("bool argument");
+    }
+
+    if !conditions.is_empty() {
+        query.push_str(" WHERE ");
+        query.push_str(&conditions.join(" AND "));
+    }
+
+    query.push_str(" ORDER BY t.created_at DESC, t.id");
+    (query, args)
+}
+
+#[derive(Clone)]
+pub struct SqliteTicketRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteTicketRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TicketStore for SqliteTicketRepository {
+    async fn find_all(&self, filter: &TicketFilter) -> Result<Vec<TicketWithTags>> {
+        let (query, args) = filtered_query(filter.clone());
+        let rows = sqlx::query_as_with::<Sqlite, TicketWithTagsRow, _>(&query, args)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows.into_iter().map(TicketWithTags::from).collect())
+    }
+
+    async fn find_page(
+        &self,
+        filter: &TicketFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<TicketWithTags>, i64)> {
+        let (query, args) = filtered_query(filter.clone());
+        let total: i64 =
+            sqlx::query_scalar_with(&format!("SELECT COUNT(*) FROM ({})", query), args.clone())
+                .fetch_one(&self.pool)
+                .await?;
+
+        let query = format!("{} LIMIT {} OFFSET {}", query, limit, offset);
+        let rows = sqlx::query_as_with::<Sqlite, TicketWithTagsRow, _>(&query, args)
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok((rows.into_iter().map(TicketWithTags::from).collect(), total))
+    }
+
+    fn stream_all(&self, filter: TicketFilter) -> BoxStream<'static, Result<TicketWithTags>> {
+        let pool = self.pool.clone();
+
+        Box::pin(async_stream::try_stream! {
+            let (query, args) = filtered_query(filter);
+            let mut rows = sqlx::query_as_with::<Sqlite, TicketWithTagsRow, _>(&query, args)
+                .fetch(&pool);
+            while let Some(row) = rows.try_next().await? {
+                yield TicketWithTags::from(row);
+            }
+        })
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TicketWithTags>> {
+        let query = format!("{} WHERE t.id = ?1", SELECT_WITH_TAGS);
+        let row = sqlx::query_as::<_, TicketWithTagsRow>(&query)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.map(TicketWithTags::from))
+    }
+
+    async fn find_tags(&self, ticket_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Tag>>> {
+        if ticket_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let placeholders: Vec<String> = (1..=ticket_ids.len()).map(|i| format!("?{}", i)).collect();
+        let query = format!(
+            "SELECT tt.ticket_id, g.id, g.name, g.color, g.created_at
+             FROM tags g
+             JOIN ticket_tags tt ON g.id = tt.tag_id
+             WHERE tt.ticket_id IN ({})
+             ORDER BY g.name",
+            placeholders.join(", ")
+        );
+        let mut rows = sqlx::query_as::<_, TicketTagRow>(&query);
+        for id in ticket_ids {
+            rows = rows.bind(id);
+        }
+
+        let mut tags: HashMap<Uuid, Vec<Tag>> = HashMap::new();
+        for row in rows.fetch_all(&self.pool).await? {
+            tags.entry(row.ticket_id).or_default().push(row.tag);
+        }
+        Ok(tags)
+    }
+
+    async fn create(&self, request: CreateTicketRequest) -> Result<Ticket> {
+        let mut tx = self.pool.begin().await?;
+
+        let now = now();
+        let ticket = sqlx::query_as::<_, Ticket>(
+            "INSERT INTO tickets (id, title, description, completed, created_at, updated_at)
+             VALUES (?1, ?2, ?3, FALSE, ?4, ?4)
+             RETURNING id, title, description, completed, created_at, updated_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&request.title)
+        .bind(&request.description)
+        .bind(now)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if let Some(tag_ids) = request.tag_ids {
+            for tag_id in tag_ids {
+                sqlx::query(
+                    "INSERT INTO ticket_tags (ticket_id, tag_id)
+                     VALUES (?1, ?2)
+                     ON CONFLICT DO NOTHING",
+                )
+                .bind(ticket.id)
+                .bind(tag_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| missing_tag(e, tag_id))?;
+            }
+        }
+
+        SqliteWebhookRepository::enqueue(
+            &mut tx,
+            &ChangeEvent::TicketCreated {
+                ticket: ticket.clone(),
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(ticket)
+    }
+
+    async fn import(
+        &self,
+        tickets: &[ImportTicket],
+        mut tag_ids: HashMap<String, Uuid>,
+    ) -> Result<(Vec<Ticket>, Vec<Tag>)> {
+        let mut tx = self.pool.begin().await?;
+        let now = now();
+
+        // Create missing tags first, in first-use order
+        let mut created_tags = Vec::new();
+        for name in tickets.iter().flat_map(|t| &t.tags) {
+            if tag_ids.contains_key(name) {
+                continue;
+            }
+            let tag = sqlx::query_as::<_, Tag>(
+                "INSERT INTO tags (id, name, created_at)
+                 VALUES (?1, ?2, ?3)
+                 ON CONFLICT (name) DO NOTHING
+                 RETURNING id, name, color, created_at",
+            )
+            .bind(Uuid::new_v4())
+            .bind(name)
+            .bind(now)
+            .fetch_optional(&mut *tx)
+            .await?;
+            match tag {
+                Some(tag) => {
+                    tag_ids.insert(tag.name.clone(), tag.id);
+                    created_tags.push(tag);
+                }
+                None => {
+                    let id = sqlx::query_scalar::<_, Uuid>("SELECT id FROM tags WHERE name = ?1")
+                        .bind(name)
+                        .fetch_one(&mut *tx)
+                        .await?;
+                    tag_ids.insert(name.clone(), id);
+                }
+            }
+        }
+
+        let mut imported = Vec::with_capacity(tickets.len());
+        for ticket in tickets {
+            let created_at = ticket.created_at.unwrap_or(now);
+            let updated_at = ticket.updated_at.unwrap_or(created_at);
+
+            let row = sqlx::query_as::<_, Ticket>(
+                "INSERT INTO tickets (id, title, description, completed, created_at, updated_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                 RETURNING id, title, description, completed, created_at, updated_at",
+            )
+            .bind(Uuid::new_v4())
+            .bind(&ticket.title)
+            .bind(&ticket.description)
+            .bind(ticket.completed)
+            .bind(created_at)
+            .bind(updated_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for name in &ticket.tags {
+                sqlx::query(
+                    "INSERT INTO ticket_tags (ticket_id, tag_id)
+                     VALUES (?1, ?2)
+                     ON CONFLICT DO NOTHING",
+                )
+                .bind(row.id)
+                .bind(tag_ids[name])
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            SqliteWebhookRepository::enqueue(
+                &mut tx,
+                &ChangeEvent::TicketCreated {
+                    ticket: row.clone(),
+                },
+            )
+            .await?;
+            imported.push(row);
+        }
+
+        tx.commit().await?;
+        Ok((imported, created_tags))
+    }
+
+    async fn update(&self, id: Uuid, request: UpdateTicketRequest) -> Result<Ticket> {
+        let mut tx = self.pool.begin().await?;
+
+        let unchanged =
+            request.title.is_none() && request.description.is_none() && request.completed.is_none();
+        if unchanged {
+            // No updates, return the existing ticket
+            return sqlx::query_as::<_, Ticket>(
+                "SELECT id, title, description, completed, created_at, updated_at
+                 FROM tickets
+                 WHERE id = ?1",
+            )
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Ticket with id {} not found", id)));
+        }
+
+        let ticket = sqlx::query_as::<_, Ticket>(
+            "UPDATE tickets
+             SET title = COALESCE(?1, title),
+                 description = COALESCE(?2, description),
+                 completed = COALESCE(?3, completed),
+                 updated_at = ?4
+             WHERE id = ?5
+             RETURNING id, title, description, completed, created_at, updated_at",
+        )
+        .bind(request.title)
+        .bind(request.description)
+        .bind(request.completed)
+        .bind(now())
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Ticket with id {} not found", id)))?;
+
+        SqliteWebhookRepository::enqueue(
+            &mut tx,
+            &ChangeEvent::TicketUpdated {
+                ticket: ticket.clone(),
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(ticket)
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query("DELETE FROM tickets WHERE id = ?1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Ticket with id {} not found",
+                id
+            )));
+        }
+
+        SqliteWebhookRepository::enqueue(&mut tx, &ChangeEvent::TicketDeleted { id }).await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn toggle_completed(&self, id: Uuid) -> Result<Ticket> {
+        let mut tx = self.pool.begin().await?;
+
+        let ticket = sqlx::query_as::<_, Ticket>(
+            "UPDATE tickets
+             SET completed = NOT completed, updated_at = ?1
+             WHERE id = ?2
+             RETURNING id, title, description, completed, created_at, updated_at",
+        )
+        .bind(now())
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Ticket with id {} not found", id)))?;
+
+        SqliteWebhookRepository::enqueue(
+            &mut tx,
+            &ChangeEvent::TicketUpdated {
+                ticket: ticket.clone(),
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(ticket)
+    }
+
+    async fn add_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM tickets WHERE id = ?1)")
+            .bind(ticket_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if !exists {
+            return Err(AppError::NotFound(format!(
+                "Ticket with id {} not found",
+                ticket_id
+            )));
+        }
+
+        let result = sqlx::query(
+            "INSERT INTO ticket_tags (ticket_id, tag_id)
+             VALUES (?1, ?2)
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(ticket_id)
+        .bind(tag_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| missing_tag(e, tag_id))?;
+
+        // Re-adding an existing tag is a no-op and does not notify webhooks
+        let added = result.rows_affected() > 0;
+        if added {
+            SqliteWebhookRepository::enqueue(
+                &mut tx,
+                &ChangeEvent::TicketTagAdded { ticket_id, tag_id },
+            )
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(added)
+    }
+
+    async fn remove_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query(
+            "DELETE FROM ticket_tags
+             WHERE ticket_id = ?1 AND tag_id = ?2",
+        )
+        .bind(ticket_id)
+        .bind(tag_id)
+        .execute(&mut *tx)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Tag {} not found on ticket {}",
+                tag_id, ticket_id
+            )));
+        }
+
+        SqliteWebhookRepository::enqueue(
+            &mut tx,
+            &ChangeEvent::TicketTagRemoved { ticket_id, tag_id },
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn stats(&self) -> Result<TicketStats> {
+        let (open, completed): (i64, i64) = sqlx::query_as(
+            "SELECT COALESCE(SUM(NOT completed), 0), COALESCE(SUM(completed), 0) FROM tickets",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let by_tag = sqlx::query_as::<_, TagCount>(
+            "SELECT t.id AS tag_id, t.name, COUNT(tt.ticket_id) AS tickets
+             FROM tags t
+             LEFT JOIN ticket_tags tt ON tt.tag_id = t.id
+             GROUP BY t.id, t.name
+             ORDER BY t.name",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(TicketStats {
+            open,
+            completed,
+            by_tag,
+        })
+    }
+}
+
+/// Report a violated `ticket_tags.tag_id` foreign key as a missing tag.
+fn missing_tag(err: sqlx::Error, tag_id: Uuid) -> AppError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+            AppError::NotFound(format!("Tag with id {} not found", tag_id))
+        }
+        _ => AppError::Database(err),
+    }
+}
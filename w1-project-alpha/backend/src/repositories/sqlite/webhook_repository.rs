@@ -0,0 +1,278 @@
+use super::now;
+use crate::events::ChangeEvent;
+use crate::models::{
+    CreateWebhookRequest, PendingDelivery, UpdateWebhookRequest, Webhook, WebhookDelivery,
+};
+use crate::repositories::webhook_repository::{
+    event_payload, validate_events, validate_secret, validate_url,
+};
+use crate::repositories::WebhookStore;
+use crate::telemetry;
+use crate::utils::error::{AppError, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::types::Json;
+use sqlx::{FromRow, SqliteConnection, SqlitePool};
+use std::time::Duration;
+use tracing::Span;
+use uuid::Uuid;
+
+/// A webhook row; SQLite keeps `events` as a JSON array.
+#[derive(FromRow)]
+struct WebhookRow {
+    id: Uuid,
+    url: String,
+    secret: String,
+    events: Json<Vec<String>>,
+    active: bool,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<WebhookRow> for Webhook {
+    fn from(row: WebhookRow) -> Self {
+        Webhook {
+            id: row.id,
+            url: row.url,
+            secret: row.secret,
+            events: row.events.0,
+            active: row.active,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SqliteWebhookRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteWebhookRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue an event for every active webhook subscribed to it, inside the
+    /// caller's transaction, with the current trace context.
+    pub async fn enqueue(conn: &mut SqliteConnection, event: &ChangeEvent) -> Result<()> {
+        let now = now();
+
+        sqlx::query(
+            "INSERT INTO webhook_deliveries
+                 (webhook_id, event, payload, next_attempt_at, created_at, traceparent)
+             SELECT id, ?1, ?2, ?3, ?3, ?4
+             FROM webhooks
+             WHERE active
+               AND (json_array_length(events) = 0
+                    OR EXISTS (SELECT 1 FROM json_each(webhooks.events) WHERE value = ?1))",
+        )
+        .bind(event.name())
+        .bind(event_payload(event, now))
+        .bind(now)
+        .bind(telemetry::traceparent(&Span::current()))
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WebhookStore for SqliteWebhookRepository {
+    async fn find_all(&self) -> Result<Vec<Webhook>> {
+        let rows = sqlx::query_as::<_, WebhookRow>(
+            "SELECT id, url, secret, events, active, created_at, updated_at
+             FROM webhooks
+             ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(Webhook::from).collect())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Webhook>> {
+        let row = sqlx::query_as::<_, WebhookRow>(
+            "SELECT id, url, secret, events, active, created_at, updated_at
+             FROM webhooks
+             WHERE id = ?1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(Webhook::from))
+    }
+
+    async fn create(&self, request: CreateWebhookRequest) -> Result<Webhook> {
+        validate_url(&request.url)?;
+        validate_secret(&request.secret)?;
+        let events = request.events.unwrap_or_default();
+        validate_events(&events)?;
+
+        let row = sqlx::query_as::<_, WebhookRow>(
+            "INSERT INTO webhooks (id, url, secret, events, active, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?6)
+             RETURNING id, url, secret, events, active, created_at, updated_at",
+        )
+        .bind(Uuid::new_v4())
+        .bind(&request.url)
+        .bind(&request.secret)
+        .bind(Json(&events))
+        .bind(request.active.unwrap_or(true))
+        .bind(now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(row.into())
+    }
+
+    async fn update(&self, id: Uuid, request: UpdateWebhookRequest) -> Result<Webhook> {
+        if let Some(ref url) = request.url {
+            validate_url(url)?;
+        }
+        if let Some(ref secret) = request.secret {
+            validate_secret(secret)?;
+        }
+        if let Some(ref events) = request.events {
+            validate_events(events)?;
+        }
+
+        let row = sqlx::query_as::<_, WebhookRow>(
+            "UPDATE webhooks
+             SET url = COALESCE(?1, url),
+                 secret = COALESCE(?2, secret),
+                 events = COALESCE(?3, events),
+                 active = COALESCE(?4, active),
+                 updated_at = ?5
+             WHERE id = ?6
+             RETURNING id, url, secret, events, active, created_at, updated_at",
+        )
+        .bind(request.url)
+        .bind(request.secret)
+        .bind(request.events.map(Json))
+        .bind(request.active)
+        .bind(now())
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(Webhook::from)
+            .ok_or_else(|| AppError::NotFound(format!("Webhook with id {} not found", id)))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = ?1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Webhook with id {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn find_deliveries(&self, webhook_id: Uuid, limit: i64) -> Result<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            "SELECT id, webhook_id, event, payload, status, attempts, next_attempt_at,
+                    last_status_code, last_error, created_at, delivered_at
+             FROM webhook_deliveries
+             WHERE webhook_id = ?1
+             ORDER BY created_at DESC
+             LIMIT ?2",
+        )
+        .bind(webhook_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    /// SQLite has a single writer, so claiming in one write transaction is
+    /// enough to keep concurrent dispatchers apart.
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<PendingDelivery>> {
+        let now = now();
+        let leased_until = now + chrono::Duration::from_std(lease).unwrap_or(chrono::Duration::MAX);
+
+        let mut tx = self.pool.begin().await?;
+
+        // Take the write lock before reading so no other claim can interleave
+        sqlx::query("UPDATE webhook_deliveries SET status = status WHERE 0")
+            .execute(&mut *tx)
+            .await?;
+
+        let deliveries = sqlx::query_as::<_, PendingDelivery>(
+            "SELECT d.id, d.webhook_id, d.event, d.payload, d.attempts, d.traceparent,
+                    w.url, w.secret
+             FROM webhook_deliveries d
+             JOIN webhooks w ON w.id = d.webhook_id
+             WHERE d.status = 'pending' AND d.next_attempt_at <= ?1
+             ORDER BY d.next_attempt_at
+             LIMIT ?2",
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        for delivery in &deliveries {
+            sqlx::query("UPDATE webhook_deliveries SET next_attempt_at = ?1 WHERE id = ?2")
+                .bind(leased_until)
+                .bind(delivery.id)
+                .execute(&mut *tx)
+                .await?;
+        }
+
+        tx.commit().await?;
+        Ok(deliveries)
+    }
+
+    async fn mark_delivered(&self, id: Uuid, status_code: i32) -> Result<()> {
+        sqlx::query(
+            "UPDATE webhook_deliveries
+             SET status = 'delivered', attempts = attempts + 1, last_status_code = ?1,
+                 last_error = NULL, delivered_at = ?2
+             WHERE id = ?3",
+        )
+        .bind(status_code)
+        .bind(now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        status_code: Option<i32>,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE webhook_deliveries
+             SET status = CASE WHEN ?1 IS NULL THEN 'failed' ELSE 'pending' END,
+                 attempts = attempts + 1,
+                 next_attempt_at = COALESCE(?1, next_attempt_at),
+                 last_status_code = ?2,
+                 last_error = ?3
+             WHERE id = ?4",
+        )
+        .bind(retry_at)
+        .bind(status_code)
+        .bind(error)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
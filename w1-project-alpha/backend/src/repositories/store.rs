@@ -0,0 +1,124 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures_util::stream::BoxStream;
+use std::collections::HashMap;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::models::{
+    CreateTagRequest, CreateTicketRequest, CreateWebhookRequest, ImportTicket, PendingDelivery,
+    Tag, Ticket, TicketFilter, TicketStats, TicketWithTags, UpdateTagRequest, UpdateTicketRequest,
+    UpdateWebhookRequest, Webhook, WebhookDelivery,
+};
+use crate::utils::error::Result;
+
+/// Persistence of tickets and their tag links.
+///
+/// Every method is atomic: it either applies completely or not at all.
+/// Tickets are returned newest first, and tags of a ticket sorted by name.
+#[async_trait]
+pub trait TicketStore: Send + Sync {
+    async fn find_all(&self, filter: &TicketFilter) -> Result<Vec<TicketWithTags>>;
+
+    /// `limit` tickets of `find_all` starting at `offset`, and how many
+    /// tickets match the filter in total.
+    async fn find_page(
+        &self,
+        filter: &TicketFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<TicketWithTags>, i64)>;
+
+    /// Like `find_all`, but yields tickets one at a time.
+    fn stream_all(&self, filter: TicketFilter) -> BoxStream<'static, Result<TicketWithTags>>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TicketWithTags>>;
+
+    /// Tags of several tickets at once, keyed by ticket id. Tickets without
+    /// tags, or that do not exist, are left out.
+    async fn find_tags(&self, ticket_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Tag>>>;
+
+    async fn create(&self, request: CreateTicketRequest) -> Result<Ticket>;
+
+    async fn update(&self, id: Uuid, request: UpdateTicketRequest) -> Result<Ticket>;
+
+    async fn delete(&self, id: Uuid) -> Result<()>;
+
+    async fn toggle_completed(&self, id: Uuid) -> Result<Ticket>;
+
+    /// Attach a tag to a ticket. Returns whether it was attached by this
+    /// call, which is false when the ticket already had it.
+    async fn add_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<bool>;
+
+    async fn remove_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()>;
+
+    /// Insert imported tickets, creating every tag name missing from `tag_ids`
+    /// that does not exist by then.
+    ///
+    /// Returns the new tickets in input order and the created tags.
+    async fn import(
+        &self,
+        tickets: &[ImportTicket],
+        tag_ids: HashMap<String, Uuid>,
+    ) -> Result<(Vec<Ticket>, Vec<Tag>)>;
+
+    /// Open and completed ticket counts, and tickets per tag.
+    async fn stats(&self) -> Result<TicketStats>;
+}
+
+/// Persistence of tags. Tags are returned sorted by name.
+#[async_trait]
+pub trait TagStore: Send + Sync {
+    async fn find_all(&self) -> Result<Vec<Tag>>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>>;
+
+    async fn find_by_name(&self, name: &str) -> Result<Option<Tag>>;
+
+    async fn create(&self, request: CreateTagRequest) -> Result<Tag>;
+
+    async fn update(&self, id: Uuid, request: UpdateTagRequest) -> Result<Tag>;
+
+    /// Delete a tag and unlink it from every ticket.
+    async fn delete(&self, id: Uuid) -> Result<()>;
+}
+
+/// Persistence of webhooks and their delivery outbox.
+///
+/// Stores that also implement `TicketStore` queue deliveries in the same
+/// transaction as each ticket mutation.
+#[async_trait]
+pub trait WebhookStore: Send + Sync {
+    async fn find_all(&self) -> Result<Vec<Webhook>>;
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Webhook>>;
+
+    async fn create(&self, request: CreateWebhookRequest) -> Result<Webhook>;
+
+    async fn update(&self, id: Uuid, request: UpdateWebhookRequest) -> Result<Webhook>;
+
+    async fn delete(&self, id: Uuid) -> Result<()>;
+
+    /// Delivery log of a webhook, newest first.
+    async fn find_deliveries(&self, webhook_id: Uuid, limit: i64) -> Result<Vec<WebhookDelivery>>;
+
+    /// Claim up to `limit` due deliveries.
+    ///
+    /// Claimed rows are pushed `lease` into the future so that concurrent
+    /// dispatchers skip them while they are being sent.
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<PendingDelivery>>;
+
+    async fn mark_delivered(&self, id: Uuid, status_code: i32) -> Result<()>;
+
+    /// Record a failed attempt.
+    ///
+    /// The delivery is retried at `retry_at`, or marked `failed` for good when
+    /// `retry_at` is `None`.
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        status_code: Option<i32>,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<()>;
+}
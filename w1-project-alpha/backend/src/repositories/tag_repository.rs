@@ -1,5 +1,7 @@
+use super::TagStore;
 use crate::models::{CreateTagRequest, Tag, UpdateTagRequest};
 use crate::utils::error::{AppError, Result};
+use async_trait::async_trait;
 use chrono::Utc;
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -13,8 +15,11 @@ impl TagRepository {
     pub fn new(pool: PgPool) -> Self {
         Self { pool }
     }
+}
 
-    pub async fn find_all(&self) -> Result<Vec<Tag>> {
+#[async_trait]
+impl TagStore for TagRepository {
+    async fn find_all(&self) -> Result<Vec<Tag>> {
         let tags = sqlx::query_as::<_, Tag>(
             "SELECT id, name, color, created_at
              FROM tags
@@ -26,7 +31,7 @@ impl TagRepository {
         Ok(tags)
     }
 
-    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>> {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Tag>> {
         let tag = sqlx::query_as::<_, Tag>(
             "SELECT id, name, color, created_at
              FROM tags
@@ -39,7 +44,7 @@ impl TagRepository {
         Ok(tag)
     }
 
-    pub async fn find_by_name(&self, name: &str) -> Result<Option<Tag>> {
+    async fn find_by_name(&self, name: &str) -> Result<Option<Tag>> {
         let tag = sqlx::query_as::<_, Tag>(
             "SELECT id, name, color, created_at
              FROM tags
@@ -52,15 +57,7 @@ impl TagRepository {
         Ok(tag)
     }
 
-    pub async fn create(&self, request: CreateTagRequest) -> Result<Tag> {
-        // Check if tag with same name already exists
-        if let Some(_existing) = self.find_by_name(&request.name).await? {
-            return Err(AppError::Validation(format!(
-                "Tag with name '{}' already exists",
-                request.name
-            )));
-        }
-
+    async fn create(&self, request: CreateTagRequest) -> Result<Tag> {
         let now = Utc::now();
         let tag = sqlx::query_as::<_, Tag>(
             "INSERT INTO tags (name, color, created_at)
@@ -71,7 +68,8 @@ impl TagRepository {
         .bind(&request.color)
         .bind(now)
         .fetch_one(&self.pool)
-        .await?;
+        .await
+        .map_err(|e| duplicate_name(e, &request.name))?;
 
         Ok(tag)
     }
@@ -80,18 +78,8 @@ impl TagRepository {
     ///
     /// Only provided fields will be updated. Returns an error if the tag does not exist
     /// or if the new name conflicts with an existing tag.
-    pub async fn update(&self, id: Uuid, request: UpdateTagRequest) -> Result<Tag> {
-        // If updating name, check for duplicates
-        if let Some(ref new_name) = request.name {
-            if let Some(existing_tag) = self.find_by_name(new_name).await? {
-                if existing_tag.id != id {
-                    return Err(AppError::Validation(format!(
-                        "Tag with name '{}' already exists",
-                        new_name
-                    )));
-                }
-            }
-        }
+    async fn update(&self, id: Uuid, request: UpdateTagRequest) -> Result<Tag> {
+        let new_name = request.name.clone();
 
         // Build dynamic update query
         let mut updates = Vec::new();
@@ -132,11 +120,14 @@ impl TagRepository {
 
         sql_query = sql_query.bind(id);
 
-        let tag = sql_query.fetch_optional(&self.pool).await?;
+        let tag = sql_query
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| duplicate_name(e, new_name.as_deref().unwrap_or_default()))?;
         tag.ok_or_else(|| AppError::NotFound(format!("Tag with id {} not found", id)))
     }
 
-    pub async fn delete(&self, id: Uuid) -> Result<()> {
+    async fn delete(&self, id: Uuid) -> Result<()> {
         let result = sqlx::query("DELETE FROM tags WHERE id = $1")
             .bind(id)
             .execute(&self.pool)
@@ -149,3 +140,13 @@ impl TagRepository {
         Ok(())
     }
 }
+
+/// Report a violated unique constraint on `tags.name` as a validation error.
+fn duplicate_name(err: sqlx::Error, name: &str) -> AppError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
+            AppError::Validation(format!("Tag with name '{}' already exists", name))
+        }
+        _ => AppError::Database(err),
+    }
+}
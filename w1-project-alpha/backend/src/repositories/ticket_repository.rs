@@ -1,9 +1,89 @@
-use crate::models::{CreateTicketRequest, Tag, Ticket, TicketWithTags, UpdateTicketRequest};
+use super::{TicketStore, WebhookRepository};
+use crate::events::ChangeEvent;
+use crate::models::{
+    CreateTicketRequest, ImportTicket, Tag, TagCount, Ticket, TicketFilter, TicketStats,
+    TicketWithTags, UpdateTicketRequest,
+};
 use crate::utils::error::{AppError, Result};
+use async_trait::async_trait;
 use chrono::Utc;
-use sqlx::PgPool;
+use futures_util::stream::{BoxStream, TryStreamExt};
+use sqlx::postgres::{PgArguments, Postgres};
+use sqlx::types::Json;
+use sqlx::{Arguments, FromRow, PgPool};
+use std::collections::HashMap;
 use uuid::Uuid;
 
+/// A ticket row with its tags aggregated into a JSON array by the database.
+#[derive(FromRow)]
+struct TicketWithTagsRow {
+    #[sqlx(flatten)]
+    ticket: Ticket,
+    tags: Json<Vec<Tag>>,
+}
+
+/// A tag along with the ticket it is linked to.
+#[derive(FromRow)]
+struct TicketTagRow {
+    ticket_id: Uuid,
+    #[sqlx(flatten)]
+    tag: Tag,
+}
+
+/// Build the filtered ticket query shared by `find_all`, `find_page` and
+/// `stream_all`.
+fn filtered_query(filter: TicketFilter) -> (String, PgArguments) {
+    let TicketFilter {
+        tag: tag_id,
+        search,
+        completed,
+    } = filter;
+
+    // Base query for tickets
+    let mut query = String::from(
+        "SELECT DISTINCT t.id, t.title, t.description, t.completed, t.created_at, t.updated_at
+         FROM tickets t",
+    );
+
+    let mut conditions = Vec::new();
+    let mut args = PgArguments::default();
+    let mut bind_count = 0;
+
+    // Join with ticket_tags if filtering by tag
+    if let Some(tag) = tag_id {
+        query.push_str(" INNER JOIN ticket_tags tt ON t.id = tt.ticket_id");
+        bind_count += 1;
+        conditions.push(format!("tt.tag_id = ${}", bind_count));
+        args.add(tag).expect("uuid argument");
+    }
+
+    // Add search condition (uses ILIKE for case-insensitive search)
+    // The GIN index on title will help optimize this query
+    if let Some(search_term) = search {
+        bind_count += 1;
+        conditions.push(format!("t.title ILIKE ${}", bind_count));
+        args.add(format!("%{}%", search_term))
+            .expect("text argument");
+    }
+
+    // Add completed filter
+    if let Some(is_completed) = completed {
+        bind_count += 1;
+        conditions.push(format!("t.completed = ${}", bind_count));
+        args.add(is_completed).expect("bool argument");
+    }
+
+    // Add WHERE clause if there are conditions
+    if !conditions.is_empty() {
+        query.push_str(" WHERE ");
+        query.push_str(&conditions.join(" AND "));
+    }
+
+    // Order by id as well so that pages do not overlap when timestamps tie
+    query.push_str(" ORDER BY t.created_at DESC, t.id");
+    (query, args)
+}
+
 #[derive(Clone)]
 pub struct TicketRepository {
     pool: PgPool,
@@ -14,82 +94,100 @@ impl TicketRepository {
         Self { pool }
     }
 
+    /// Attach the tags of each ticket, read in a single query.
+    async fn with_tags(&self, tickets: Vec<Ticket>) -> Result<Vec<TicketWithTags>> {
+        let ids: Vec<Uuid> = tickets.iter().map(|ticket| ticket.id).collect();
+        let mut tags = self.find_tags(&ids).await?;
+        Ok(tickets
+            .into_iter()
+            .map(|ticket| TicketWithTags {
+                tags: tags.remove(&ticket.id).unwrap_or_default(),
+                ticket,
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl TicketStore for TicketRepository {
     /// Find all tickets with optional filtering by tag, search term, and completed status.
     ///
-    /// # Performance Note
-    /// This method performs N+1 queries (one for tickets, then one per ticket for tags).
-    /// For large datasets, consider optimizing with a single JOIN query and grouping in application code.
-    pub async fn find_all(
-        &self,
-        tag_id: Option<Uuid>,
-        search: Option<String>,
-        completed: Option<bool>,
-    ) -> Result<Vec<TicketWithTags>> {
-        // Base query for tickets
-        let mut query = String::from(
-            "SELECT DISTINCT t.id, t.title, t.description, t.completed, t.created_at, t.updated_at
-             FROM tickets t",
-        );
-
-        let mut conditions = Vec::new();
-        let mut bind_count = 0;
-
-        // Join with ticket_tags if filtering by tag
-        if tag_id.is_some() {
-            query.push_str(" INNER JOIN ticket_tags tt ON t.id = tt.ticket_id");
-            bind_count += 1;
-            conditions.push(format!("tt.tag_id = ${}", bind_count));
-        }
-
-        // Add search condition (uses ILIKE for case-insensitive search)
-        // The GIN index on title will help optimize this query
-        if search.is_some() {
-            bind_count += 1;
-            conditions.push(format!("t.title ILIKE ${}", bind_count));
-        }
-
-        // Add completed filter
-        if completed.is_some() {
-            bind_count += 1;
-            conditions.push(format!("t.completed = ${}", bind_count));
-        }
-
-        // Add WHERE clause if there are conditions
-        if !conditions.is_empty() {
-            query.push_str(" WHERE ");
-            query.push_str(&conditions.join(" AND "));
-        }
-
-        query.push_str(" ORDER BY t.created_at DESC");
+    /// Two queries: one for the tickets, then one for the tags of all of them.
+    async fn find_all(&self, filter: &TicketFilter) -> Result<Vec<TicketWithTags>> {
+        let (query, args) = filtered_query(filter.clone());
+        let tickets = sqlx::query_as_with::<Postgres, Ticket, _>(&query, args)
+            .fetch_all(&self.pool)
+            .await?;
 
-        // Build and execute query
-        let mut sql_query = sqlx::query_as::<_, Ticket>(&query);
+        self.with_tags(tickets).await
+    }
 
-        if let Some(tag) = tag_id {
-            sql_query = sql_query.bind(tag);
-        }
-        if let Some(search_term) = search {
-            sql_query = sql_query.bind(format!("%{}%", search_term));
-        }
-        if let Some(is_completed) = completed {
-            sql_query = sql_query.bind(is_completed);
-        }
+    async fn find_page(
+        &self,
+        filter: &TicketFilter,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<TicketWithTags>, i64)> {
+        let (query, args) = filtered_query(filter.clone());
+        let total: i64 = sqlx::query_scalar_with(
+            &format!("SELECT COUNT(*) FROM ({}) AS matching", query),
+            args.clone(),
+        )
+        .fetch_one(&self.pool)
+        .await?;
 
-        let tickets = sql_query.fetch_all(&self.pool).await?;
+        let query = format!("{} LIMIT {} OFFSET {}", query, limit, offset);
+        let tickets = sqlx::query_as_with::<Postgres, Ticket, _>(&query, args)
+            .fetch_all(&self.pool)
+            .await?;
 
-        // Fetch tags for each ticket
-        // Note: This results in N+1 queries. For better performance with large datasets,
-        // consider fetching all tags in a single query and grouping by ticket_id.
-        let mut tickets_with_tags = Vec::new();
-        for ticket in tickets {
-            let tags = self.get_ticket_tags(ticket.id).await?;
-            tickets_with_tags.push(TicketWithTags { ticket, tags });
-        }
+        Ok((self.with_tags(tickets).await?, total))
+    }
 
-        Ok(tickets_with_tags)
+    /// Stream all tickets matching the same filters as `find_all`.
+    ///
+    /// Rows are read from a database cursor one at a time, and tags are
+    /// aggregated in the same query, so memory use does not grow with the
+    /// number of tickets.
+    fn stream_all(&self, filter: TicketFilter) -> BoxStream<'static, Result<TicketWithTags>> {
+        let pool = self.pool.clone();
+
+        Box::pin(async_stream::try_stream! {
+            let (matching, args) = filtered_query(filter);
+            // Tags are read per ticket so that a tag filter keeps every tag of
+            // a matching ticket
+            let query = format!(
+                "SELECT m.id, m.title, m.description, m.completed, m.created_at, m.updated_at,
+                        COALESCE(
+                            (SELECT json_agg(
+                                        json_build_object(
+                                            'id', g.id, 'name', g.name,
+                                            'color', g.color, 'created_at', g.created_at
+                                        )
+                                        ORDER BY g.name
+                                    )
+                             FROM ticket_tags tt
+                             JOIN tags g ON g.id = tt.tag_id
+                             WHERE tt.ticket_id = m.id),
+                            '[]'
+                        ) AS tags
+                 FROM ({}) AS m
+                 ORDER BY m.created_at DESC, m.id",
+                matching
+            );
+
+            let mut rows = sqlx::query_as_with::<Postgres, TicketWithTagsRow, _>(&query, args)
+                .fetch(&pool);
+            while let Some(row) = rows.try_next().await? {
+                yield TicketWithTags {
+                    ticket: row.ticket,
+                    tags: row.tags.0,
+                };
+            }
+        })
     }
 
-    pub async fn find_by_id(&self, id: Uuid) -> Result<Option<TicketWithTags>> {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<TicketWithTags>> {
         let ticket = sqlx::query_as::<_, Ticket>(
             "SELECT id, title, description, completed, created_at, updated_at
              FROM tickets
@@ -100,15 +198,35 @@ impl TicketRepository {
         .await?;
 
         match ticket {
-            Some(ticket) => {
-                let tags = self.get_ticket_tags(ticket.id).await?;
-                Ok(Some(TicketWithTags { ticket, tags }))
-            }
+            Some(ticket) => Ok(self.with_tags(vec![ticket]).await?.pop()),
             None => Ok(None),
         }
     }
 
-    pub async fn create(&self, request: CreateTicketRequest) -> Result<Ticket> {
+    /// The join uses the index on `ticket_tags`.
+    async fn find_tags(&self, ticket_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Tag>>> {
+        if ticket_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let rows = sqlx::query_as::<_, TicketTagRow>(
+            "SELECT tt.ticket_id, t.id, t.name, t.color, t.created_at
+             FROM tags t
+             INNER JOIN ticket_tags tt ON t.id = tt.tag_id
+             WHERE tt.ticket_id = ANY($1)
+             ORDER BY t.name",
+        )
+        .bind(ticket_ids)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut tags: HashMap<Uuid, Vec<Tag>> = HashMap::new();
+        for row in rows {
+            tags.entry(row.ticket_id).or_default().push(row.tag);
+        }
+        Ok(tags)
+    }
+
+    async fn create(&self, request: CreateTicketRequest) -> Result<Ticket> {
         let mut tx = self.pool.begin().await?;
 
         let now = Utc::now();
@@ -136,19 +254,118 @@ impl TicketRepository {
                 .bind(ticket.id)
                 .bind(tag_id)
                 .execute(&mut *tx)
-                .await?;
+                .await
+                .map_err(|e| missing_tag(e, tag_id))?;
             }
         }
 
+        WebhookRepository::enqueue(
+            &mut tx,
+            &ChangeEvent::TicketCreated {
+                ticket: ticket.clone(),
+            },
+        )
+        .await?;
+
         tx.commit().await?;
         Ok(ticket)
     }
 
+    /// Insert imported tickets in a single transaction.
+    ///
+    /// `tag_ids` maps already existing tag names to their ids; any other tag
+    /// name used by a ticket is created, unless a concurrent request created
+    /// it first. Returns the new tickets in input order and the tags that
+    /// were created. Webhooks are notified of the tickets only, as for tags
+    /// created through the API.
+    async fn import(
+        &self,
+        tickets: &[ImportTicket],
+        mut tag_ids: HashMap<String, Uuid>,
+    ) -> Result<(Vec<Ticket>, Vec<Tag>)> {
+        let mut tx = self.pool.begin().await?;
+        let now = Utc::now();
+
+        // Create missing tags first, in first-use order
+        let mut created_tags = Vec::new();
+        for name in tickets.iter().flat_map(|t| &t.tags) {
+            if tag_ids.contains_key(name) {
+                continue;
+            }
+            let tag = sqlx::query_as::<_, Tag>(
+                "INSERT INTO tags (name, created_at)
+                 VALUES ($1, $2)
+                 ON CONFLICT (name) DO NOTHING
+                 RETURNING id, name, color, created_at",
+            )
+            .bind(name)
+            .bind(now)
+            .fetch_optional(&mut *tx)
+            .await?;
+            match tag {
+                Some(tag) => {
+                    tag_ids.insert(tag.name.clone(), tag.id);
+                    created_tags.push(tag);
+                }
+                None => {
+                    let id = sqlx::query_scalar::<_, Uuid>("SELECT id FROM tags WHERE name = $1")
+                        .bind(name)
+                        .fetch_one(&mut *tx)
+                        .await?;
+                    tag_ids.insert(name.clone(), id);
+                }
+            }
+        }
+
+        let mut imported = Vec::with_capacity(tickets.len());
+        for ticket in tickets {
+            let created_at = ticket.created_at.unwrap_or(now);
+            let updated_at = ticket.updated_at.unwrap_or(created_at);
+
+            let row = sqlx::query_as::<_, Ticket>(
+                "INSERT INTO tickets (title, description, completed, created_at, updated_at)
+                 VALUES ($1, $2, $3, $4, $5)
+                 RETURNING id, title, description, completed, created_at, updated_at",
+            )
+            .bind(&ticket.title)
+            .bind(&ticket.description)
+            .bind(ticket.completed)
+            .bind(created_at)
+            .bind(updated_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for name in &ticket.tags {
+                sqlx::query(
+                    "INSERT INTO ticket_tags (ticket_id, tag_id)
+                     VALUES ($1, $2)
+                     ON CONFLICT DO NOTHING",
+                )
+                .bind(row.id)
+                .bind(tag_ids[name])
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            WebhookRepository::enqueue(
+                &mut tx,
+                &ChangeEvent::TicketCreated {
+                    ticket: row.clone(),
+                },
+            )
+            .await?;
+            imported.push(row);
+        }
+
+        tx.commit().await?;
+        Ok((imported, created_tags))
+    }
+
     /// Update a ticket with partial data.
     ///
     /// Only provided fields will be updated. The `updated_at` field is always updated.
     /// Returns an error if the ticket does not exist.
-    pub async fn update(&self, id: Uuid, request: UpdateTicketRequest) -> Result<Ticket> {
+    async fn update(&self, id: Uuid, request: UpdateTicketRequest) -> Result<Ticket> {
         // Build dynamic update query
         let mut updates = Vec::new();
         let mut bind_count = 1;
@@ -202,14 +419,31 @@ impl TicketRepository {
         sql_query = sql_query.bind(now);
         sql_query = sql_query.bind(id);
 
-        let ticket = sql_query.fetch_optional(&self.pool).await?;
-        ticket.ok_or_else(|| AppError::NotFound(format!("Ticket with id {} not found", id)))
+        let mut tx = self.pool.begin().await?;
+
+        let ticket = sql_query
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Ticket with id {} not found", id)))?;
+
+        WebhookRepository::enqueue(
+            &mut tx,
+            &ChangeEvent::TicketUpdated {
+                ticket: ticket.clone(),
+            },
+        )
+        .await?;
+
+        tx.commit().await?;
+        Ok(ticket)
     }
 
-    pub async fn delete(&self, id: Uuid) -> Result<()> {
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
         let result = sqlx::query("DELETE FROM tickets WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await?;
 
         if result.rows_affected() == 0 {
@@ -219,10 +453,15 @@ impl TicketRepository {
             )));
         }
 
+        WebhookRepository::enqueue(&mut tx, &ChangeEvent::TicketDeleted { id }).await?;
+
+        tx.commit().await?;
         Ok(())
     }
 
-    pub async fn toggle_completed(&self, id: Uuid) -> Result<Ticket> {
+    async fn toggle_completed(&self, id: Uuid) -> Result<Ticket> {
+        let mut tx = self.pool.begin().await?;
+
         let now = Utc::now();
         let ticket = sqlx::query_as::<_, Ticket>(
             "UPDATE tickets
@@ -232,43 +471,69 @@ impl TicketRepository {
         )
         .bind(now)
         .bind(id)
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Ticket with id {} not found", id)))?;
+
+        WebhookRepository::enqueue(
+            &mut tx,
+            &ChangeEvent::TicketUpdated {
+                ticket: ticket.clone(),
+            },
+        )
         .await?;
 
-        ticket.ok_or_else(|| AppError::NotFound(format!("Ticket with id {} not found", id)))
+        tx.commit().await?;
+        Ok(ticket)
     }
 
-    pub async fn add_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()> {
+    async fn add_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
         // Check if ticket exists
-        let ticket = self.find_by_id(ticket_id).await?;
-        if ticket.is_none() {
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS(SELECT 1 FROM tickets WHERE id = $1)")
+            .bind(ticket_id)
+            .fetch_one(&mut *tx)
+            .await?;
+        if !exists {
             return Err(AppError::NotFound(format!(
                 "Ticket with id {} not found",
                 ticket_id
             )));
         }
 
-        sqlx::query(
+        let result = sqlx::query(
             "INSERT INTO ticket_tags (ticket_id, tag_id)
              VALUES ($1, $2)
              ON CONFLICT DO NOTHING",
         )
         .bind(ticket_id)
         .bind(tag_id)
-        .execute(&self.pool)
-        .await?;
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| missing_tag(e, tag_id))?;
+
+        // Re-adding an existing tag is a no-op and does not notify webhooks
+        let added = result.rows_affected() > 0;
+        if added {
+            WebhookRepository::enqueue(&mut tx, &ChangeEvent::TicketTagAdded { ticket_id, tag_id })
+                .await?;
+        }
 
-        Ok(())
+        tx.commit().await?;
+        Ok(added)
     }
 
-    pub async fn remove_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()> {
+    async fn remove_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
         let result = sqlx::query(
             "DELETE FROM ticket_tags
              WHERE ticket_id = $1 AND tag_id = $2",
         )
         .bind(ticket_id)
         .bind(tag_id)
-        .execute(&self.pool)
+        .execute(&mut *tx)
         .await?;
 
         if result.rows_affected() == 0 {
@@ -278,25 +543,48 @@ impl TicketRepository {
             )));
         }
 
+        WebhookRepository::enqueue(
+            &mut tx,
+            &ChangeEvent::TicketTagRemoved { ticket_id, tag_id },
+        )
+        .await?;
+
+        tx.commit().await?;
         Ok(())
     }
 
-    /// Helper method to get tags for a ticket.
-    ///
-    /// This method is used internally to fetch tags for tickets.
-    /// The query uses an INNER JOIN which is efficient due to the index on ticket_tags.
-    async fn get_ticket_tags(&self, ticket_id: Uuid) -> Result<Vec<Tag>> {
-        let tags = sqlx::query_as::<_, Tag>(
-            "SELECT t.id, t.name, t.color, t.created_at
+    async fn stats(&self) -> Result<TicketStats> {
+        let (open, completed): (i64, i64) = sqlx::query_as(
+            "SELECT COUNT(*) FILTER (WHERE NOT completed), COUNT(*) FILTER (WHERE completed)
+             FROM tickets",
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let by_tag = sqlx::query_as::<_, TagCount>(
+            "SELECT t.id AS tag_id, t.name, COUNT(tt.ticket_id) AS tickets
              FROM tags t
-             INNER JOIN ticket_tags tt ON t.id = tt.tag_id
-             WHERE tt.ticket_id = $1
+             LEFT JOIN ticket_tags tt ON tt.tag_id = t.id
+             GROUP BY t.id, t.name
              ORDER BY t.name",
         )
-        .bind(ticket_id)
         .fetch_all(&self.pool)
         .await?;
 
-        Ok(tags)
+        Ok(TicketStats {
+            open,
+            completed,
+            by_tag,
+        })
+    }
+}
+
+/// Report a violated `ticket_tags.tag_id` foreign key as a missing tag.
+fn missing_tag(err: sqlx::Error, tag_id: Uuid) -> AppError {
+    match &err {
+        sqlx::Error::Database(db_err) if db_err.is_foreign_key_violation() => {
+            AppError::NotFound(format!("Tag with id {} not found", tag_id))
+        }
+        _ => AppError::Database(err),
     }
 }
@@ -0,0 +1,283 @@
+use super::WebhookStore;
+use crate::events::ChangeEvent;
+use crate::models::{
+    CreateWebhookRequest, PendingDelivery, UpdateWebhookRequest, Webhook, WebhookDelivery,
+    WEBHOOK_EVENTS,
+};
+use crate::telemetry;
+use crate::utils::error::{AppError, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sqlx::{PgConnection, PgPool};
+use std::time::Duration;
+use tracing::Span;
+use uuid::Uuid;
+
+#[derive(Clone)]
+pub struct WebhookRepository {
+    pool: PgPool,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Queue an event for every active webhook subscribed to it.
+    ///
+    /// Takes the caller's connection so the outbox rows are written in the same
+    /// transaction as the mutation that produced the event. The current trace
+    /// context is stored with each row so the delivery joins the same trace.
+    pub async fn enqueue(conn: &mut PgConnection, event: &ChangeEvent) -> Result<()> {
+        let now = Utc::now();
+        let payload = event_payload(event, now);
+
+        sqlx::query(
+            "INSERT INTO webhook_deliveries
+                 (webhook_id, event, payload, next_attempt_at, created_at, traceparent)
+             SELECT id, $1, $2, $3, $3, $4
+             FROM webhooks
+             WHERE active AND (cardinality(events) = 0 OR $1 = ANY(events))",
+        )
+        .bind(event.name())
+        .bind(payload)
+        .bind(now)
+        .bind(telemetry::traceparent(&Span::current()))
+        .execute(conn)
+        .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl WebhookStore for WebhookRepository {
+    async fn find_all(&self) -> Result<Vec<Webhook>> {
+        let webhooks = sqlx::query_as::<_, Webhook>(
+            "SELECT id, url, secret, events, active, created_at, updated_at
+             FROM webhooks
+             ORDER BY created_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(webhooks)
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Webhook>> {
+        let webhook = sqlx::query_as::<_, Webhook>(
+            "SELECT id, url, secret, events, active, created_at, updated_at
+             FROM webhooks
+             WHERE id = $1",
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    async fn create(&self, request: CreateWebhookRequest) -> Result<Webhook> {
+        validate_url(&request.url)?;
+        validate_secret(&request.secret)?;
+        let events = request.events.unwrap_or_default();
+        validate_events(&events)?;
+
+        let now = Utc::now();
+        let webhook = sqlx::query_as::<_, Webhook>(
+            "INSERT INTO webhooks (url, secret, events, active, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5, $5)
+             RETURNING id, url, secret, events, active, created_at, updated_at",
+        )
+        .bind(&request.url)
+        .bind(&request.secret)
+        .bind(&events)
+        .bind(request.active.unwrap_or(true))
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(webhook)
+    }
+
+    /// Update a webhook with partial data.
+    ///
+    /// Only provided fields will be updated. Returns an error if the webhook does not exist.
+    async fn update(&self, id: Uuid, request: UpdateWebhookRequest) -> Result<Webhook> {
+        if let Some(ref url) = request.url {
+            validate_url(url)?;
+        }
+        if let Some(ref secret) = request.secret {
+            validate_secret(secret)?;
+        }
+        if let Some(ref events) = request.events {
+            validate_events(events)?;
+        }
+
+        let webhook = sqlx::query_as::<_, Webhook>(
+            "UPDATE webhooks
+             SET url = COALESCE($1, url),
+                 secret = COALESCE($2, secret),
+                 events = COALESCE($3, events),
+                 active = COALESCE($4, active),
+                 updated_at = $5
+             WHERE id = $6
+             RETURNING id, url, secret, events, active, created_at, updated_at",
+        )
+        .bind(request.url)
+        .bind(request.secret)
+        .bind(request.events)
+        .bind(request.active)
+        .bind(Utc::now())
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        webhook.ok_or_else(|| AppError::NotFound(format!("Webhook with id {} not found", id)))
+    }
+
+    async fn delete(&self, id: Uuid) -> Result<()> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Webhook with id {} not found",
+                id
+            )));
+        }
+
+        Ok(())
+    }
+
+    async fn find_deliveries(&self, webhook_id: Uuid, limit: i64) -> Result<Vec<WebhookDelivery>> {
+        let deliveries = sqlx::query_as::<_, WebhookDelivery>(
+            "SELECT id, webhook_id, event, payload, status, attempts, next_attempt_at,
+                    last_status_code, last_error, created_at, delivered_at
+             FROM webhook_deliveries
+             WHERE webhook_id = $1
+             ORDER BY created_at DESC
+             LIMIT $2",
+        )
+        .bind(webhook_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    async fn claim_due(&self, limit: i64, lease: Duration) -> Result<Vec<PendingDelivery>> {
+        let deliveries = sqlx::query_as::<_, PendingDelivery>(
+            "WITH due AS (
+                 SELECT id
+                 FROM webhook_deliveries
+                 WHERE status = 'pending' AND next_attempt_at <= NOW()
+                 ORDER BY next_attempt_at
+                 LIMIT $1
+                 FOR UPDATE SKIP LOCKED
+             )
+             UPDATE webhook_deliveries d
+             SET next_attempt_at = NOW() + make_interval(secs => $2)
+             FROM due, webhooks w
+             WHERE d.id = due.id AND w.id = d.webhook_id
+             RETURNING d.id, d.webhook_id, d.event, d.payload, d.attempts, d.traceparent,
+                       w.url, w.secret",
+        )
+        .bind(limit)
+        .bind(lease.as_secs_f64())
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(deliveries)
+    }
+
+    async fn mark_delivered(&self, id: Uuid, status_code: i32) -> Result<()> {
+        sqlx::query(
+            "UPDATE webhook_deliveries
+             SET status = 'delivered', attempts = attempts + 1, last_status_code = $1,
+                 last_error = NULL, delivered_at = $2
+             WHERE id = $3",
+        )
+        .bind(status_code)
+        .bind(Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn mark_failed(
+        &self,
+        id: Uuid,
+        status_code: Option<i32>,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        sqlx::query(
+            "UPDATE webhook_deliveries
+             SET status = CASE WHEN $1::timestamptz IS NULL THEN 'failed' ELSE 'pending' END,
+                 attempts = attempts + 1,
+                 next_attempt_at = COALESCE($1, next_attempt_at),
+                 last_status_code = $2,
+                 last_error = $3
+             WHERE id = $4",
+        )
+        .bind(retry_at)
+        .bind(status_code)
+        .bind(error)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
+
+/// Body of a webhook delivery: the event name, when it happened and the event data.
+pub(crate) fn event_payload(event: &ChangeEvent, occurred_at: DateTime<Utc>) -> serde_json::Value {
+    json!({
+        "event": event.name(),
+        "occurred_at": occurred_at,
+        "data": event,
+    })
+}
+
+pub(crate) fn validate_url(url: &str) -> Result<()> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| AppError::Validation(format!("Invalid webhook url '{}': {}", url, e)))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(AppError::Validation(format!(
+            "Webhook url '{}' must use http or https",
+            url
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_secret(secret: &str) -> Result<()> {
+    if secret.trim().is_empty() {
+        return Err(AppError::Validation(
+            "Webhook secret must not be empty".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_events(events: &[String]) -> Result<()> {
+    if let Some(unknown) = events
+        .iter()
+        .find(|event| !WEBHOOK_EVENTS.contains(&event.as_str()))
+    {
+        return Err(AppError::Validation(format!(
+            "Unknown webhook event '{}', expected one of: {}",
+            unknown,
+            WEBHOOK_EVENTS.join(", ")
+        )));
+    }
+    Ok(())
+}
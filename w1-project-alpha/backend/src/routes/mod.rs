@@ -1,33 +1,281 @@
-// Note: delete and put are used in route method chaining, but compiler may not detect it
-#[allow(unused_imports)]
-use axum::routing::{delete, put};
-
 use axum::{
-    routing::{get, patch, post},
+    body::Body,
+    extract::{DefaultBodyLimit, MatchedPath},
+    handler::Handler,
+    http::{Method, Request, StatusCode},
+    middleware::{from_fn, from_fn_with_state},
+    response::{IntoResponse, Response},
+    routing::{get, on, MethodFilter, MethodRouter, Route},
     Router,
 };
 
+use std::convert::Infallible;
+use std::time::Duration;
+use tower::{Layer, Service};
+use tower_http::trace::{DefaultOnResponse, OnResponse, TraceLayer};
+use tracing::Span;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::config::Config;
+use crate::graphql::{self, AlphaSchema, GRAPHQL_PATH};
+use crate::grpc::grpc_routes;
 use crate::handlers::*;
-use crate::repositories::Repositories;
+use crate::metrics::UNMATCHED_ROUTE;
+use crate::middleware::{cors_layer, negotiate_version, request_id, track_requests, RequestId};
+use crate::openapi::{self, SPEC_PATH, UI_PATH};
+use crate::state::AppState;
+use crate::telemetry;
 
-pub fn create_routes(repositories: Repositories) -> Router {
-    Router::new()
-        // Ticket routes
-        .route("/api/tickets", get(get_tickets).post(create_ticket))
-        .route(
-            "/api/tickets/{id}",
-            get(get_ticket).put(update_ticket).delete(delete_ticket),
+/// The API routes wrapped in the middleware stack, shared by the server
+/// binary and the integration tests. The gRPC services are included unless
+/// they are disabled or have a port of their own.
+///
+/// The probe routes are merged after the layers so that orchestrator polling
+/// and scrapes skip request logging, request metrics and anything else added
+/// to the API stack.
+pub fn create_app(state: AppState, config: &Config) -> Router {
+    let mut routes = create_routes(state.clone()).merge(docs_routes());
+    if config.grpc.shares_http_port() {
+        routes = routes.merge(grpc_routes(&state));
+    }
+    routes
+        // Unmatched requests must also pass through the layers below; merging
+        // would otherwise leave them to the bare default fallback
+        .fallback(|| async { StatusCode::NOT_FOUND })
+        .layer(cors_layer(&config.cors))
+        .layer(from_fn_with_state(state.metrics.clone(), track_requests))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(request_span)
+                .on_response(record_response),
         )
-        .route("/api/tickets/{id}/toggle", patch(toggle_ticket_completed))
-        .route(
-            "/api/tickets/{ticket_id}/tags/{tag_id}",
-            post(add_tag_to_ticket).delete(remove_tag_from_ticket),
+        .layer(from_fn(request_id))
+        .merge(probe_routes(state))
+}
+
+/// The gRPC services on a listener of their own, with request IDs, request
+/// logging and request metrics but no CORS, as browsers cannot call them.
+pub fn create_grpc_app(state: AppState) -> Router {
+    grpc_routes(&state)
+        .layer(from_fn_with_state(state.metrics.clone(), track_requests))
+        .layer(
+            TraceLayer::new_for_http()
+                .make_span_with(request_span)
+                .on_response(record_response),
         )
-        // Tag routes
-        .route("/api/tags", get(get_tags).post(create_tag))
-        .route(
-            "/api/tags/{id}",
-            get(get_tag).put(update_tag).delete(delete_tag),
+        .layer(from_fn(request_id))
+}
+
+/// The span every API request is logged in, carrying its request ID. The
+/// `otel.*` and `http.*` fields name it when exported, and an incoming
+/// `traceparent` makes it part of the caller's trace.
+fn request_span(request: &Request<Body>) -> Span {
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.as_str())
+        .unwrap_or_default();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(MatchedPath::as_str)
+        .unwrap_or(UNMATCHED_ROUTE);
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        request_id,
+        otel.name = %format!("{} {}", request.method(), route),
+        otel.kind = "server",
+        http.route = route,
+        http.response.status_code = tracing::field::Empty,
+    );
+    telemetry::set_remote_parent(&span, request.headers());
+    span
+}
+
+fn record_response(response: &Response, latency: Duration, span: &Span) {
+    // Recorded as i64 so it is exported as an integer attribute
+    span.record(
+        "http.response.status_code",
+        i64::from(response.status().as_u16()),
+    );
+    DefaultOnResponse::default().on_response(response, latency, span);
+}
+
+/// The OpenAPI document and a Swagger UI for browsing it.
+pub fn docs_routes() -> Router {
+    SwaggerUi::new(UI_PATH)
+        .url(SPEC_PATH, openapi::spec())
+        .into()
+}
+
+/// Liveness, readiness, build information and metrics for orchestrators.
+pub fn probe_routes(state: AppState) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/version", get(version))
+        .route("/metrics", get(metrics))
+        .with_state(state)
+}
+
+/// The API under `/api/v1` and `/api/v2`, the unversioned `/api` prefix,
+/// which serves v1 (or v2 when asked for in `Accept`) and is deprecated, and
+/// the GraphQL endpoint.
+pub fn create_routes(state: AppState) -> Router {
+    let v2 = into_router(v2_endpoints()).with_state(state.clone());
+    Router::new()
+        .merge(into_router(graphql_endpoints()).with_state(graphql::schema(&state)))
+        .nest("/api/v1", into_router(v1_endpoints()))
+        .nest("/api/v2", into_router(v2_endpoints()))
+        .nest(
+            "/api",
+            into_router(v1_endpoints()).layer(from_fn_with_state(v2, negotiate_version)),
         )
-        .with_state(repositories)
+        .with_state(state)
+}
+
+/// Every `(method, path)` that [`create_routes`] serves, built from the same
+/// endpoint tables as the router. The deprecated `/api` alias is left out as
+/// it repeats `/api/v1`.
+pub fn registered_routes() -> Vec<(Method, String)> {
+    let versioned = [("/api/v1", v1_endpoints()), ("/api/v2", v2_endpoints())]
+        .into_iter()
+        .flat_map(|(prefix, endpoints)| {
+            endpoints
+                .into_iter()
+                .map(move |e| (e.method, format!("{}{}", prefix, e.path)))
+        });
+    graphql_endpoints()
+        .into_iter()
+        .map(|e| (e.method, e.path.to_string()))
+        .chain(versioned)
+        .collect()
+}
+
+/// One route: a method and path, and the handler serving it.
+struct Endpoint<S> {
+    method: Method,
+    path: &'static str,
+    handler: MethodRouter<S>,
+}
+
+impl<S: Clone + Send + Sync + 'static> Endpoint<S> {
+    fn new<H, T>(method: Method, path: &'static str, handler: H) -> Self
+    where
+        H: Handler<T, S>,
+        T: 'static,
+    {
+        let filter = MethodFilter::try_from(method.clone()).expect("supported method");
+        Self {
+            method,
+            path,
+            handler: on(filter, handler),
+        }
+    }
+
+    fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + Sync + 'static,
+        L::Service: Service<Request<Body>> + Clone + Send + Sync + 'static,
+        <L::Service as Service<Request<Body>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request<Body>>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request<Body>>>::Future: Send + 'static,
+    {
+        self.handler = self.handler.layer(layer);
+        self
+    }
+}
+
+/// Routes the endpoints; those sharing a path share one method router.
+fn into_router<S: Clone + Send + Sync + 'static>(endpoints: Vec<Endpoint<S>>) -> Router<S> {
+    endpoints
+        .into_iter()
+        .fold(Router::new(), |router, e| router.route(e.path, e.handler))
+}
+
+/// GraphQL queries and mutations, and GraphiQL on `GET`.
+fn graphql_endpoints() -> Vec<Endpoint<AlphaSchema>> {
+    vec![
+        Endpoint::new(Method::GET, GRAPHQL_PATH, graphiql),
+        Endpoint::new(Method::POST, GRAPHQL_PATH, execute_graphql),
+    ]
+}
+
+/// API v1: the original request and response shapes.
+fn v1_endpoints() -> Vec<Endpoint<AppState>> {
+    let mut endpoints = vec![
+        Endpoint::new(Method::GET, "/tickets", get_tickets),
+        Endpoint::new(Method::POST, "/tickets", create_ticket),
+        Endpoint::new(Method::GET, "/tickets/{id}", get_ticket),
+        Endpoint::new(Method::PUT, "/tickets/{id}", update_ticket),
+        Endpoint::new(Method::DELETE, "/tickets/{id}", delete_ticket),
+        Endpoint::new(
+            Method::PATCH,
+            "/tickets/{id}/toggle",
+            toggle_ticket_completed,
+        ),
+    ];
+    endpoints.extend(shared_endpoints());
+    endpoints
+}
+
+/// API v2: paginated ticket lists and a ticket `status` instead of
+/// `completed`.
+fn v2_endpoints() -> Vec<Endpoint<AppState>> {
+    let mut endpoints = vec![
+        Endpoint::new(Method::GET, "/tickets", v2::get_tickets),
+        Endpoint::new(Method::POST, "/tickets", v2::create_ticket),
+        Endpoint::new(Method::GET, "/tickets/{id}", v2::get_ticket),
+        Endpoint::new(Method::PUT, "/tickets/{id}", v2::update_ticket),
+        Endpoint::new(Method::DELETE, "/tickets/{id}", delete_ticket),
+        Endpoint::new(
+            Method::PATCH,
+            "/tickets/{id}/toggle",
+            v2::toggle_ticket_completed,
+        ),
+    ];
+    endpoints.extend(shared_endpoints());
+    endpoints
+}
+
+/// Routes that are the same in every API version.
+fn shared_endpoints() -> Vec<Endpoint<AppState>> {
+    vec![
+        // Ticket routes
+        Endpoint::new(Method::GET, "/tickets/export", export_tickets),
+        Endpoint::new(Method::POST, "/tickets/import", import_tickets)
+            .layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT)),
+        Endpoint::new(Method::GET, "/tickets/{id}/presence", get_ticket_presence),
+        Endpoint::new(
+            Method::POST,
+            "/tickets/{ticket_id}/tags/{tag_id}",
+            add_tag_to_ticket,
+        ),
+        Endpoint::new(
+            Method::DELETE,
+            "/tickets/{ticket_id}/tags/{tag_id}",
+            remove_tag_from_ticket,
+        ),
+        // Tag routes
+        Endpoint::new(Method::GET, "/tags", get_tags),
+        Endpoint::new(Method::POST, "/tags", create_tag),
+        Endpoint::new(Method::GET, "/tags/{id}", get_tag),
+        Endpoint::new(Method::PUT, "/tags/{id}", update_tag),
+        Endpoint::new(Method::DELETE, "/tags/{id}", delete_tag),
+        // Webhook routes
+        Endpoint::new(Method::GET, "/webhooks", get_webhooks),
+        Endpoint::new(Method::POST, "/webhooks", create_webhook),
+        Endpoint::new(Method::GET, "/webhooks/{id}", get_webhook),
+        Endpoint::new(Method::PUT, "/webhooks/{id}", update_webhook),
+        Endpoint::new(Method::DELETE, "/webhooks/{id}", delete_webhook),
+        Endpoint::new(
+            Method::GET,
+            "/webhooks/{id}/deliveries",
+            get_webhook_deliveries,
+        ),
+        // Collaboration socket (change events, presence, typing hints)
+        Endpoint::new(Method::GET, "/ws", collab_ws),
+    ]
 }
@@ -1 +1,29 @@
-// Services module - will be implemented in Phase 2
+use crate::events::EventHub;
+use crate::repositories::Repositories;
+
+pub mod tag_service;
+pub mod ticket_service;
+pub mod validation;
+
+pub use tag_service::TagService;
+pub use ticket_service::TicketService;
+
+/// The domain services handed to handlers.
+#[derive(Clone)]
+pub struct Services {
+    pub ticket: TicketService,
+    pub tag: TagService,
+}
+
+impl Services {
+    pub fn new(repositories: &Repositories, events: EventHub) -> Self {
+        Self {
+            ticket: TicketService::new(
+                repositories.ticket.clone(),
+                repositories.tag.clone(),
+                events.clone(),
+            ),
+            tag: TagService::new(repositories.tag.clone(), events),
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::validation::{validate_color, validate_tag_name};
+use crate::events::{ChangeEvent, EventHub};
+use crate::models::{CreateTagRequest, Tag, UpdateTagRequest};
+use crate::repositories::TagStore;
+use crate::utils::error::{AppError, Result};
+
+/// Tag use cases: name and color rules, unique names and change notifications.
+#[derive(Clone)]
+pub struct TagService {
+    tags: Arc<dyn TagStore>,
+    events: EventHub,
+}
+
+impl TagService {
+    pub fn new(tags: Arc<dyn TagStore>, events: EventHub) -> Self {
+        Self { tags, events }
+    }
+
+    pub async fn list(&self) -> Result<Vec<Tag>> {
+        self.tags.find_all().await
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<Tag> {
+        self.tags
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Tag with id {} not found", id)))
+    }
+
+    pub async fn create(&self, mut request: CreateTagRequest) -> Result<Tag> {
+        request.name = request.name.trim().to_string();
+        validate_tag_name(&request.name).map_err(AppError::Validation)?;
+        if let Some(ref color) = request.color {
+            validate_color(color).map_err(AppError::Validation)?;
+        }
+        self.ensure_unique(&request.name, None).await?;
+
+        let tag = self.tags.create(request).await?;
+        self.events
+            .publish(ChangeEvent::TagCreated { tag: tag.clone() });
+        Ok(tag)
+    }
+
+    pub async fn update(&self, id: Uuid, mut request: UpdateTagRequest) -> Result<Tag> {
+        if let Some(name) = request.name.take() {
+            let name = name.trim().to_string();
+            validate_tag_name(&name).map_err(AppError::Validation)?;
+            self.ensure_unique(&name, Some(id)).await?;
+            request.name = Some(name);
+        }
+        if let Some(ref color) = request.color {
+            validate_color(color).map_err(AppError::Validation)?;
+        }
+
+        let tag = self.tags.update(id, request).await?;
+        self.events
+            .publish(ChangeEvent::TagUpdated { tag: tag.clone() });
+        Ok(tag)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<()> {
+        self.tags.delete(id).await?;
+        self.events.publish(ChangeEvent::TagDeleted { id });
+        Ok(())
+    }
+
+    /// The store enforces unique names too; checking first gives the usual
+    /// message without relying on a constraint error.
+    async fn ensure_unique(&self, name: &str, id: Option<Uuid>) -> Result<()> {
+        match self.tags.find_by_name(name).await? {
+            Some(existing) if Some(existing.id) != id => Err(AppError::Validation(format!(
+                "Tag with name '{}' already exists",
+                name
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repositories::MemoryStore;
+
+    fn service() -> TagService {
+        TagService::new(Arc::new(MemoryStore::new()), EventHub::default())
+    }
+
+    fn create_request(name: &str, color: Option<&str>) -> CreateTagRequest {
+        CreateTagRequest {
+            name: name.to_string(),
+            color: color.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_validates_name_and_color() {
+        let service = service();
+
+        for request in [
+            create_request(" ", None),
+            create_request(&"x".repeat(51), None),
+            create_request("bug", Some("red")),
+        ] {
+            let err = service.create(request).await.unwrap_err();
+            assert!(matches!(err, AppError::Validation(_)));
+        }
+
+        let tag = service
+            .create(create_request(" bug ", Some("#f00")))
+            .await
+            .unwrap();
+        assert_eq!(tag.name, "bug");
+    }
+
+    #[tokio::test]
+    async fn names_are_unique() {
+        let service = service();
+        let bug = service.create(create_request("bug", None)).await.unwrap();
+        let feature = service
+            .create(create_request("feature", None))
+            .await
+            .unwrap();
+
+        let err = service
+            .create(create_request("bug", None))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+
+        let rename = |name: &str| UpdateTagRequest {
+            name: Some(name.to_string()),
+            color: None,
+        };
+        let err = service.update(feature.id, rename("bug")).await.unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+
+        // Keeping its own name is not a conflict
+        service.update(bug.id, rename("bug")).await.unwrap();
+    }
+}
@@ -0,0 +1,372 @@
+use futures_util::stream::BoxStream;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::validation::validate_title;
+use crate::events::{ChangeEvent, EventHub};
+use crate::models::{
+    CreateTicketRequest, ImportReport, Page, PageParams, Ticket, TicketFilter, TicketWithTags,
+    UpdateTicketRequest,
+};
+use crate::repositories::{TagStore, TicketStore};
+use crate::utils::error::{AppError, Result};
+use crate::utils::import::{self, ImportFormat};
+
+/// Ticket use cases: validation, tag checks and change notifications.
+///
+/// Every mutation is a single store call, so it is applied atomically; the
+/// change event is published only after it succeeded.
+#[derive(Clone)]
+pub struct TicketService {
+    tickets: Arc<dyn TicketStore>,
+    tags: Arc<dyn TagStore>,
+    events: EventHub,
+}
+
+impl TicketService {
+    pub fn new(tickets: Arc<dyn TicketStore>, tags: Arc<dyn TagStore>, events: EventHub) -> Self {
+        Self {
+            tickets,
+            tags,
+            events,
+        }
+    }
+
+    pub async fn list(&self, filter: &TicketFilter) -> Result<Vec<TicketWithTags>> {
+        self.tickets.find_all(filter).await
+    }
+
+    pub async fn list_page(
+        &self,
+        filter: &TicketFilter,
+        params: PageParams,
+    ) -> Result<Page<TicketWithTags>> {
+        params.validate().map_err(AppError::Validation)?;
+        let (tickets, total) = self
+            .tickets
+            .find_page(filter, params.limit(), params.offset())
+            .await?;
+        Ok(Page::new(tickets, params, total))
+    }
+
+    /// `limit` tickets matching `filter` starting at `offset`, and how many
+    /// match in total; for cursor pagination, where pages need not line up.
+    pub async fn list_range(
+        &self,
+        filter: &TicketFilter,
+        offset: i64,
+        limit: i64,
+    ) -> Result<(Vec<TicketWithTags>, i64)> {
+        self.tickets.find_page(filter, limit, offset).await
+    }
+
+    /// Tickets matching `filter`, read lazily for exports.
+    pub fn stream(&self, filter: TicketFilter) -> BoxStream<'static, Result<TicketWithTags>> {
+        self.tickets.stream_all(filter)
+    }
+
+    pub async fn get(&self, id: Uuid) -> Result<TicketWithTags> {
+        self.tickets
+            .find_by_id(id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Ticket with id {} not found", id)))
+    }
+
+    pub async fn create(&self, mut request: CreateTicketRequest) -> Result<Ticket> {
+        request.title = request.title.trim().to_string();
+        validate_title(&request.title).map_err(AppError::Validation)?;
+
+        if let Some(tag_ids) = request.tag_ids.take() {
+            let mut unique = Vec::with_capacity(tag_ids.len());
+            for tag_id in tag_ids {
+                if unique.contains(&tag_id) {
+                    continue;
+                }
+                if self.tags.find_by_id(tag_id).await?.is_none() {
+                    return Err(AppError::Validation(format!(
+                        "Tag with id {} does not exist",
+                        tag_id
+                    )));
+                }
+                unique.push(tag_id);
+            }
+            request.tag_ids = Some(unique);
+        }
+
+        let ticket = self.tickets.create(request).await?;
+        self.events.publish(ChangeEvent::TicketCreated {
+            ticket: ticket.clone(),
+        });
+        Ok(ticket)
+    }
+
+    pub async fn update(&self, id: Uuid, mut request: UpdateTicketRequest) -> Result<Ticket> {
+        if let Some(title) = request.title.take() {
+            let title = title.trim().to_string();
+            validate_title(&title).map_err(AppError::Validation)?;
+            request.title = Some(title);
+        }
+
+        let ticket = self.tickets.update(id, request).await?;
+        self.events.publish(ChangeEvent::TicketUpdated {
+            ticket: ticket.clone(),
+        });
+        Ok(ticket)
+    }
+
+    pub async fn delete(&self, id: Uuid) -> Result<()> {
+        self.tickets.delete(id).await?;
+        self.events.publish(ChangeEvent::TicketDeleted { id });
+        Ok(())
+    }
+
+    pub async fn toggle_completed(&self, id: Uuid) -> Result<Ticket> {
+        let ticket = self.tickets.toggle_completed(id).await?;
+        self.events.publish(ChangeEvent::TicketUpdated {
+            ticket: ticket.clone(),
+        });
+        Ok(ticket)
+    }
+
+    pub async fn add_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()> {
+        if self.tags.find_by_id(tag_id).await?.is_none() {
+            return Err(AppError::NotFound(format!(
+                "Tag with id {} not found",
+                tag_id
+            )));
+        }
+
+        // Re-adding a tag changes nothing, so nothing is published
+        if self.tickets.add_tag(ticket_id, tag_id).await? {
+            self.events
+                .publish(ChangeEvent::TicketTagAdded { ticket_id, tag_id });
+        }
+        Ok(())
+    }
+
+    pub async fn remove_tag(&self, ticket_id: Uuid, tag_id: Uuid) -> Result<()> {
+        self.tickets.remove_tag(ticket_id, tag_id).await?;
+        self.events
+            .publish(ChangeEvent::TicketTagRemoved { ticket_id, tag_id });
+        Ok(())
+    }
+
+    /// Import tickets from a CSV or JSON body.
+    ///
+    /// Invalid rows are reported and skipped; all valid rows, and any tags they
+    /// need, are written at once and then published as change events. With
+    /// `dry_run` nothing is written or published.
+    pub async fn import(
+        &self,
+        format: ImportFormat,
+        body: &[u8],
+        dry_run: bool,
+    ) -> Result<ImportReport> {
+        let (tickets, errors) = import::parse(format, body)?;
+
+        // Resolve tag names; names that are not found will be created
+        let mut tag_ids = HashMap::new();
+        let mut missing_tags = Vec::new();
+        let mut seen = HashSet::new();
+        for name in tickets.iter().flat_map(|ticket| &ticket.tags) {
+            if !seen.insert(name.as_str()) {
+                continue;
+            }
+            match self.tags.find_by_name(name).await? {
+                Some(tag) => {
+                    tag_ids.insert(tag.name, tag.id);
+                }
+                None => missing_tags.push(name.clone()),
+            }
+        }
+
+        let (ticket_ids, created_tags) = if dry_run || tickets.is_empty() {
+            (Vec::new(), missing_tags)
+        } else {
+            let (imported, created) = self.tickets.import(&tickets, tag_ids).await?;
+            for tag in &created {
+                self.events
+                    .publish(ChangeEvent::TagCreated { tag: tag.clone() });
+            }
+            let ticket_ids = imported.iter().map(|ticket| ticket.id).collect();
+            for ticket in imported {
+                self.events.publish(ChangeEvent::TicketCreated { ticket });
+            }
+            (
+                ticket_ids,
+                created.into_iter().map(|tag| tag.name).collect(),
+            )
+        };
+
+        Ok(ImportReport {
+            dry_run,
+            total: tickets.len() + errors.len(),
+            imported: tickets.len(),
+            failed: errors.len(),
+            created_tags,
+            ticket_ids,
+            errors,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::ServerMessage;
+    use crate::models::CreateTagRequest;
+    use crate::repositories::MemoryStore;
+
+    fn service() -> (TicketService, MemoryStore) {
+        let store = MemoryStore::new();
+        let service = TicketService::new(
+            Arc::new(store.clone()),
+            Arc::new(store.clone()),
+            EventHub::default(),
+        );
+        (service, store)
+    }
+
+    fn create_request(title: &str, tag_ids: Option<Vec<Uuid>>) -> CreateTicketRequest {
+        CreateTicketRequest {
+            title: title.to_string(),
+            description: None,
+            tag_ids,
+        }
+    }
+
+    #[tokio::test]
+    async fn create_rejects_blank_title() {
+        let (service, _) = service();
+
+        let err = service
+            .create(create_request("  ", None))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::Validation(_)));
+        assert!(service
+            .list(&TicketFilter::default())
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_rejects_unknown_tags_and_dedupes_known_ones() {
+        let (service, store) = service();
+        let tag = TagStore::create(
+            &store,
+            CreateTagRequest {
+                name: "bug".to_string(),
+                color: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let err = service
+            .create(create_request("A", Some(vec![Uuid::new_v4()])))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Validation(_)));
+
+        let ticket = service
+            .create(create_request(" B ", Some(vec![tag.id, tag.id])))
+            .await
+            .unwrap();
+        let created = service.get(ticket.id).await.unwrap();
+        assert_eq!(created.ticket.title, "B");
+        assert_eq!(created.tags.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn create_publishes_change_event() {
+        let (service, _) = service();
+        let mut rx = service.events.subscribe();
+
+        let ticket = service.create(create_request("A", None)).await.unwrap();
+
+        match rx.try_recv().unwrap().message {
+            ServerMessage::Change(ChangeEvent::TicketCreated { ticket: published }) => {
+                assert_eq!(published.id, ticket.id)
+            }
+            other => panic!("unexpected message {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn import_publishes_created_tags_and_tickets() {
+        let (service, _) = service();
+        let mut rx = service.events.subscribe();
+        let csv = b"title,tags\nA,ops\nB,\n";
+
+        service.import(ImportFormat::Csv, csv, true).await.unwrap();
+        assert!(rx.try_recv().is_err());
+
+        let report = service.import(ImportFormat::Csv, csv, false).await.unwrap();
+        let mut events = Vec::new();
+        while let Ok(received) = rx.try_recv() {
+            events.push(received.message);
+        }
+        assert_eq!(events.len(), 3);
+        assert!(matches!(
+            &events[0],
+            ServerMessage::Change(ChangeEvent::TagCreated { tag }) if tag.name == "ops"
+        ));
+        for (event, id) in events[1..].iter().zip(&report.ticket_ids) {
+            assert!(matches!(
+                event,
+                ServerMessage::Change(ChangeEvent::TicketCreated { ticket }) if ticket.id == *id
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn add_tag_publishes_only_new_links() {
+        let (service, store) = service();
+        let tag = TagStore::create(
+            &store,
+            CreateTagRequest {
+                name: "bug".to_string(),
+                color: None,
+            },
+        )
+        .await
+        .unwrap();
+        let ticket = service.create(create_request("A", None)).await.unwrap();
+        let mut rx = service.events.subscribe();
+
+        service.add_tag(ticket.id, tag.id).await.unwrap();
+        service.add_tag(ticket.id, tag.id).await.unwrap();
+
+        assert!(matches!(
+            rx.try_recv().unwrap().message,
+            ServerMessage::Change(ChangeEvent::TicketTagAdded { .. })
+        ));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn add_tag_requires_existing_tag() {
+        let (service, _) = service();
+        let ticket = service.create(create_request("A", None)).await.unwrap();
+
+        let err = service
+            .add_tag(ticket.id, Uuid::new_v4())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn get_missing_ticket_is_not_found() {
+        let (service, _) = service();
+
+        let err = service.get(Uuid::new_v4()).await.unwrap_err();
+
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+}
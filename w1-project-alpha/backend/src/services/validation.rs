@@ -0,0 +1,76 @@
+//! Field rules shared by the services and the import parser.
+//!
+//! Validators return a plain message so callers can either fail the request
+//! or collect the message per row.
+
+/// Longest title accepted by the `tickets.title` column.
+pub const MAX_TITLE_LENGTH: usize = 255;
+/// Longest name accepted by the `tags.name` column.
+pub const MAX_TAG_LENGTH: usize = 50;
+
+pub fn validate_title(title: &str) -> Result<(), String> {
+    if title.trim().is_empty() {
+        return Err("title must not be empty".to_string());
+    }
+    if title.chars().count() > MAX_TITLE_LENGTH {
+        return Err(format!(
+            "title must be at most {} characters",
+            MAX_TITLE_LENGTH
+        ));
+    }
+    Ok(())
+}
+
+pub fn validate_tag_name(name: &str) -> Result<(), String> {
+    if name.trim().is_empty() {
+        return Err("tag names must not be empty".to_string());
+    }
+    if name.chars().count() > MAX_TAG_LENGTH {
+        return Err(format!(
+            "tag '{}' must be at most {} characters",
+            name, MAX_TAG_LENGTH
+        ));
+    }
+    Ok(())
+}
+
+/// Colors are CSS hex colors, `#RGB` or `#RRGGBB`.
+pub fn validate_color(color: &str) -> Result<(), String> {
+    let valid = color.strip_prefix('#').is_some_and(|hex| {
+        matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+    });
+    if !valid {
+        return Err(format!("color '{}' must look like #RGB or #RRGGBB", color));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn title_rules() {
+        assert!(validate_title("Fix login").is_ok());
+        assert!(validate_title("   ").is_err());
+        assert!(validate_title(&"x".repeat(MAX_TITLE_LENGTH)).is_ok());
+        assert!(validate_title(&"x".repeat(MAX_TITLE_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn tag_name_rules() {
+        assert!(validate_tag_name("ui; mobile & web").is_ok());
+        assert!(validate_tag_name(" ").is_err());
+        assert!(validate_tag_name("ui, mobile").is_ok());
+        assert!(validate_tag_name(&"x".repeat(MAX_TAG_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn color_rules() {
+        assert!(validate_color("#fff").is_ok());
+        assert!(validate_color("#FF5733").is_ok());
+        assert!(validate_color("FF5733").is_err());
+        assert!(validate_color("#FF57").is_err());
+        assert!(validate_color("#GGGGGG").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use axum::Router;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use crate::db::Database;
+use tokio::net::TcpListener;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// A shutdown and the one deadline all of its steps share.
+///
+/// The deadline is `drain_timeout` after shutdown starts. Draining requests,
+/// flushing webhooks and closing the pool each wait only for the time left,
+/// so the whole shutdown fits in the timeout an orchestrator's grace period
+/// is sized to.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    token: CancellationToken,
+    drain_timeout: Duration,
+    deadline: Arc<OnceLock<Instant>>,
+}
+
+impl Shutdown {
+    pub fn new(drain_timeout: Duration) -> Self {
+        Self {
+            token: CancellationToken::new(),
+            drain_timeout,
+            deadline: Arc::new(OnceLock::new()),
+        }
+    }
+
+    /// Cancelled when shutdown starts; for background tasks to stop on.
+    pub fn token(&self) -> CancellationToken {
+        self.token.clone()
+    }
+
+    /// Start shutting down, fixing the deadline from now.
+    pub fn start(&self) {
+        self.deadline();
+        self.token.cancel();
+    }
+
+    /// When shutdown must be complete. Fixed on the first call, which
+    /// [`start`](Self::start) makes.
+    pub fn deadline(&self) -> Instant {
+        *self
+            .deadline
+            .get_or_init(|| Instant::now() + self.drain_timeout)
+    }
+
+    /// Time left before the deadline.
+    pub fn remaining(&self) -> Duration {
+        self.deadline().saturating_duration_since(Instant::now())
+    }
+
+    pub fn drain_timeout(&self) -> Duration {
+        self.drain_timeout
+    }
+}
+
+/// Resolve on Ctrl+C (SIGINT) or, on Unix, SIGTERM.
+pub async fn signal() {
+    let ctrl_c = async {
+        if let Err(err) = tokio::signal::ctrl_c().await {
+            tracing::error!("Failed to listen for Ctrl+C: {}", err);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(err) => {
+                tracing::error!("Failed to listen for SIGTERM: {}", err);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// Serve `app` until `shutdown` starts, then stop accepting connections and
+/// give in-flight requests until the shutdown deadline to finish. After that
+/// it returns without waiting for them; they end when the process exits.
+pub async fn serve(listener: TcpListener, app: Router, shutdown: Shutdown) -> std::io::Result<()> {
+    let token = shutdown.token();
+    let server = axum::serve(listener, app).with_graceful_shutdown(token.clone().cancelled_owned());
+    let mut server = tokio::spawn(async move { server.await });
+
+    tokio::select! {
+        result = &mut server => return result.map_err(std::io::Error::other)?,
+        _ = token.cancelled() => {}
+    }
+
+    tracing::info!(
+        "Shutting down, draining in-flight requests for up to {:?}",
+        shutdown.remaining()
+    );
+    match tokio::time::timeout_at(shutdown.deadline(), &mut server).await {
+        Ok(result) => result.map_err(std::io::Error::other)?,
+        Err(_) => {
+            tracing::warn!("Drain timeout elapsed, abandoning remaining connections");
+            server.abort();
+            Ok(())
+        }
+    }
+}
+
+/// Close the database pool, waiting until the shutdown deadline at most for
+/// connections that abandoned requests still hold; closing otherwise waits
+/// for every one of them to be returned.
+pub async fn close_database(database: &Database, shutdown: &Shutdown) {
+    if tokio::time::timeout_at(shutdown.deadline(), database.close())
+        .await
+        .is_err()
+    {
+        tracing::warn!(
+            "Database connections still in use at the shutdown deadline, exiting without closing them"
+        );
+    }
+}
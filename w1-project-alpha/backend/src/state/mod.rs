@@ -0,0 +1,71 @@
+use axum::extract::FromRef;
+
+use crate::db::Database;
+use crate::events::EventHub;
+use crate::metrics::Metrics;
+use crate::repositories::Repositories;
+use crate::services::{Services, TagService, TicketService};
+
+/// Shared state handed to every handler.
+///
+/// Handlers extract only the part they need (`State<TicketService>`,
+/// `State<EventHub>`, ...) through the `FromRef` impls below.
+#[derive(Clone)]
+pub struct AppState {
+    pub database: Database,
+    pub repositories: Repositories,
+    pub services: Services,
+    pub events: EventHub,
+    pub metrics: Metrics,
+}
+
+impl AppState {
+    pub fn new(database: Database) -> Self {
+        let metrics = Metrics::new();
+        let repositories = database.repositories().metered(&metrics);
+        let events = EventHub::default();
+        Self {
+            services: Services::new(&repositories, events.clone()),
+            database,
+            repositories,
+            events,
+            metrics,
+        }
+    }
+}
+
+impl FromRef<AppState> for Database {
+    fn from_ref(state: &AppState) -> Self {
+        state.database.clone()
+    }
+}
+
+impl FromRef<AppState> for Repositories {
+    fn from_ref(state: &AppState) -> Self {
+        state.repositories.clone()
+    }
+}
+
+impl FromRef<AppState> for EventHub {
+    fn from_ref(state: &AppState) -> Self {
+        state.events.clone()
+    }
+}
+
+impl FromRef<AppState> for Metrics {
+    fn from_ref(state: &AppState) -> Self {
+        state.metrics.clone()
+    }
+}
+
+impl FromRef<AppState> for TicketService {
+    fn from_ref(state: &AppState) -> Self {
+        state.services.ticket.clone()
+    }
+}
+
+impl FromRef<AppState> for TagService {
+    fn from_ref(state: &AppState) -> Self {
+        state.services.tag.clone()
+    }
+}
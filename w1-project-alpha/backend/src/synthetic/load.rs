@@ -0,0 +1,236 @@
+//! Bulk writes of generated data, one transaction per batch of tickets.
+
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use super::{Generator, SyntheticTicket};
+use crate::db::Database;
+use crate::models::Tag;
+use crate::utils::error::Result;
+
+/// Tickets written per transaction.
+pub const DEFAULT_BATCH_SIZE: usize = 5_000;
+
+/// Rows actually inserted. Rows that already exist, such as those of an
+/// earlier run with the same seed, are skipped and not counted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LoadReport {
+    pub tags: u64,
+    pub tickets: u64,
+    pub ticket_tags: u64,
+}
+
+/// Write the generator's tags and tickets. Tags whose name already exists
+/// are reused rather than duplicated. `progress` is called after every
+/// committed batch with the number of tickets generated so far.
+pub async fn load(
+    database: &Database,
+    mut generator: Generator,
+    batch_size: usize,
+    mut progress: impl FnMut(usize),
+) -> Result<LoadReport> {
+    let batch_size = batch_size.max(1);
+    let mut report = LoadReport::default();
+    let (inserted, existing) = match database {
+        Database::Postgres(pool) => postgres::insert_tags(pool, generator.tags()).await?,
+        #[cfg(feature = "sqlite")]
+        Database::Sqlite(pool) => sqlite::insert_tags(pool, generator.tags()).await?,
+    };
+    report.tags = inserted;
+    // Generated tag id -> id of the tag with that name in the database
+    let tag_ids: HashMap<Uuid, Uuid> = generator
+        .tags()
+        .iter()
+        .filter_map(|tag| Some((tag.id, *existing.get(&tag.name)?)))
+        .collect();
+
+    let mut generated = 0;
+    loop {
+        let mut batch: Vec<SyntheticTicket> = generator.by_ref().take(batch_size).collect();
+        if batch.is_empty() {
+            break;
+        }
+        for ticket in &mut batch {
+            for id in &mut ticket.tag_ids {
+                *id = tag_ids[id];
+            }
+        }
+        let (tickets, ticket_tags) = match database {
+            Database::Postgres(pool) => postgres::insert_tickets(pool, &batch).await?,
+            #[cfg(feature = "sqlite")]
+            Database::Sqlite(pool) => sqlite::insert_tickets(pool, &batch).await?,
+        };
+        report.tickets += tickets;
+        report.ticket_tags += ticket_tags;
+        generated += batch.len();
+        progress(generated);
+    }
+    Ok(report)
+}
+
+mod postgres {
+    use super::*;
+
+    /// Insert the tags that are new and return the ids of all of them by
+    /// name.
+    pub(super) async fn insert_tags(
+        pool: &PgPool,
+        tags: &[Tag],
+    ) -> Result<(u64, HashMap<String, Uuid>)> {
+        let ids: Vec<Uuid> = tags.iter().map(|tag| tag.id).collect();
+        let names: Vec<&str> = tags.iter().map(|tag| tag.name.as_str()).collect();
+        let colors: Vec<Option<&str>> = tags.iter().map(|tag| tag.color.as_deref()).collect();
+        let created: Vec<_> = tags.iter().map(|tag| tag.created_at).collect();
+
+        let mut tx = pool.begin().await?;
+        let inserted = sqlx::query(
+            "INSERT INTO tags (id, name, color, created_at)
+             SELECT * FROM UNNEST($1::uuid[], $2::varchar[], $3::varchar[], $4::timestamptz[])
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(&ids)
+        .bind(&names)
+        .bind(&colors)
+        .bind(&created)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        let existing: Vec<(String, Uuid)> =
+            sqlx::query_as("SELECT name, id FROM tags WHERE name = ANY($1)")
+                .bind(&names)
+                .fetch_all(&mut *tx)
+                .await?;
+        tx.commit().await?;
+        Ok((inserted, existing.into_iter().collect()))
+    }
+
+    pub(super) async fn insert_tickets(
+        pool: &PgPool,
+        batch: &[SyntheticTicket],
+    ) -> Result<(u64, u64)> {
+        let tickets: Vec<_> = batch.iter().map(|t| &t.ticket).collect();
+        let ids: Vec<Uuid> = tickets.iter().map(|t| t.id).collect();
+        let titles: Vec<&str> = tickets.iter().map(|t| t.title.as_str()).collect();
+        let descriptions: Vec<Option<&str>> =
+            tickets.iter().map(|t| t.description.as_deref()).collect();
+        let completed: Vec<bool> = tickets.iter().map(|t| t.completed).collect();
+        let created: Vec<_> = tickets.iter().map(|t| t.created_at).collect();
+        let updated: Vec<_> = tickets.iter().map(|t| t.updated_at).collect();
+        let (link_tickets, link_tags): (Vec<Uuid>, Vec<Uuid>) = batch
+            .iter()
+            .flat_map(|t| t.tag_ids.iter().map(|tag| (t.ticket.id, *tag)))
+            .unzip();
+
+        let mut tx = pool.begin().await?;
+        let inserted = sqlx::query(
+            "INSERT INTO tickets (id, title, description, completed, created_at, updated_at)
+             SELECT * FROM UNNEST($1::uuid[], $2::varchar[], $3::text[], $4::bool[],
+                                  $5::timestamptz[], $6::timestamptz[])
+             ON CONFLICT (id) DO NOTHING",
+        )
+        .bind(&ids)
+        .bind(&titles)
+        .bind(&descriptions)
+        .bind(&completed)
+        .bind(&created)
+        .bind(&updated)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        let linked = sqlx::query(
+            "INSERT INTO ticket_tags (ticket_id, tag_id)
+             SELECT * FROM UNNEST($1::uuid[], $2::uuid[])
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(&link_tickets)
+        .bind(&link_tags)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        tx.commit().await?;
+        Ok((inserted, linked))
+    }
+}
+
+#[cfg(feature = "sqlite")]
+mod sqlite {
+    use super::*;
+    use sqlx::{QueryBuilder, Sqlite, SqlitePool};
+
+    /// Rows per statement, well below SQLite's limit on bound parameters.
+    const ROWS_PER_STATEMENT: usize = 1_000;
+
+    pub(super) async fn insert_tags(
+        pool: &SqlitePool,
+        tags: &[Tag],
+    ) -> Result<(u64, HashMap<String, Uuid>)> {
+        let mut tx = pool.begin().await?;
+        let mut inserted = 0;
+        for chunk in tags.chunks(ROWS_PER_STATEMENT) {
+            let mut query: QueryBuilder<Sqlite> =
+                QueryBuilder::new("INSERT INTO tags (id, name, color, created_at) ");
+            query.push_values(chunk, |mut row, tag| {
+                row.push_bind(tag.id)
+                    .push_bind(&tag.name)
+                    .push_bind(&tag.color)
+                    .push_bind(tag.created_at);
+            });
+            query.push(" ON CONFLICT DO NOTHING");
+            inserted += query.build().execute(&mut *tx).await?.rows_affected();
+        }
+        // Names are looked up one by one: the list can exceed the parameter
+        // limit and there are rarely more than a few hundred tags
+        let mut existing = HashMap::with_capacity(tags.len());
+        for tag in tags {
+            let id: Uuid = sqlx::query_scalar("SELECT id FROM tags WHERE name = ?1")
+                .bind(&tag.name)
+                .fetch_one(&mut *tx)
+                .await?;
+            existing.insert(tag.name.clone(), id);
+        }
+        tx.commit().await?;
+        Ok((inserted, existing))
+    }
+
+    pub(super) async fn insert_tickets(
+        pool: &SqlitePool,
+        batch: &[SyntheticTicket],
+    ) -> Result<(u64, u64)> {
+        let links: Vec<(Uuid, Uuid)> = batch
+            .iter()
+            .flat_map(|t| t.tag_ids.iter().map(|tag| (t.ticket.id, *tag)))
+            .collect();
+
+        let mut tx = pool.begin().await?;
+        let mut inserted = 0;
+        for chunk in batch.chunks(ROWS_PER_STATEMENT) {
+            let mut query: QueryBuilder<Sqlite> = QueryBuilder::new(
+                "INSERT INTO tickets (id, title, description, completed, created_at, updated_at) ",
+            );
+            query.push_values(chunk, |mut row, t| {
+                row.push_bind(t.ticket.id)
+                    .push_bind(&t.ticket.title)
+                    .push_bind(&t.ticket.description)
+                    .push_bind(t.ticket.completed)
+                    .push_bind(t.ticket.created_at)
+                    .push_bind(t.ticket.updated_at);
+            });
+            query.push(" ON CONFLICT DO NOTHING");
+            inserted += query.build().execute(&mut *tx).await?.rows_affected();
+        }
+        let mut linked = 0;
+        for chunk in links.chunks(ROWS_PER_STATEMENT) {
+            let mut query: QueryBuilder<Sqlite> =
+                QueryBuilder::new("INSERT INTO ticket_tags (ticket_id, tag_id) ");
+            query.push_values(chunk, |mut row, (ticket, tag)| {
+                row.push_bind(*ticket).push_bind(*tag);
+            });
+            query.push(" ON CONFLICT DO NOTHING");
+            linked += query.build().execute(&mut *tx).await?.rows_affected();
+        }
+        tx.commit().await?;
+        Ok((inserted, linked))
+    }
+}
@@ -0,0 +1,423 @@
+//! Synthetic tickets and tags for load tests and demo environments.
+//!
+//! Everything is drawn from one seeded generator, including the ids, so the
+//! same [`SyntheticConfig`] always produces the same rows. Loading them
+//! again is a no-op, and a performance problem found with a generated data
+//! set can be reproduced from its seed.
+//!
+//! ```no_run
+//! # async fn run(database: project_alpha_backend::Database) -> project_alpha_backend::utils::error::Result<()> {
+//! use project_alpha_backend::synthetic::{load, Generator, SyntheticConfig, DEFAULT_BATCH_SIZE};
+//!
+//! let config = SyntheticConfig {
+//!     tickets: 1_000_000,
+//!     ..SyntheticConfig::default()
+//! };
+//! let report = load(&database, Generator::new(config)?, DEFAULT_BATCH_SIZE, |_| {}).await?;
+//! println!("{} tickets", report.tickets);
+//! # Ok(())
+//! # }
+//! ```
+
+use chrono::{DateTime, Datelike, Duration, DurationRound, TimeDelta, Utc, Weekday};
+use uuid::Uuid;
+
+use crate::models::{Tag, Ticket};
+use crate::utils::error::{AppError, Result};
+
+mod load;
+mod words;
+
+pub use load::{load, LoadReport, DEFAULT_BATCH_SIZE};
+
+/// Ticket creation speeds up over time: the density of creation times grows
+/// like `t^GROWTH` from the start of the range to its end.
+const GROWTH: f64 = 0.7;
+
+/// Chance of a ticket having 0, 1, 2, ... tags.
+const TAG_COUNT_WEIGHTS: [f64; 6] = [0.15, 0.40, 0.28, 0.11, 0.04, 0.02];
+
+/// Days in a generated "month".
+const DAYS_PER_MONTH: i64 = 30;
+
+#[derive(Debug, Clone)]
+pub struct SyntheticConfig {
+    /// Seed of the generator; equal configs give equal data.
+    pub seed: u64,
+    pub tickets: usize,
+    pub tags: usize,
+    /// Share of tickets that are completed, from 0.0 to 1.0. Older tickets
+    /// are more likely to be done than recent ones.
+    pub completed_ratio: f64,
+    /// Exponent of the Zipf distribution of tag use: the tag of rank `k` is
+    /// used about `1 / k^tag_skew` as often as the most popular one.
+    pub tag_skew: f64,
+    pub max_tags_per_ticket: usize,
+    /// Tickets are created over this many months before `until`.
+    pub months: u32,
+    /// End of the time range. Part of the config, rather than the current
+    /// time, so that a seed reproduces the same timestamps later.
+    pub until: DateTime<Utc>,
+}
+
+impl Default for SyntheticConfig {
+    /// A thousand tickets over the past year, up to the start of today.
+    fn default() -> Self {
+        let now = Utc::now();
+        Self {
+            seed: 1,
+            tickets: 1_000,
+            tags: 30,
+            completed_ratio: 0.6,
+            tag_skew: 1.1,
+            max_tags_per_ticket: 5,
+            months: 12,
+            until: now.duration_trunc(TimeDelta::days(1)).unwrap_or(now),
+        }
+    }
+}
+
+impl SyntheticConfig {
+    fn validate(&self) -> Result<()> {
+        let error = |message: &str| Err(AppError::Validation(message.to_string()));
+        if !(0.0..=1.0).contains(&self.completed_ratio) {
+            return error("completed ratio must be between 0 and 1");
+        }
+        if !(self.tag_skew >= 0.0 && self.tag_skew.is_finite()) {
+            return error("tag skew must be 0 or more");
+        }
+        if self.months == 0 {
+            return error("months must be at least 1");
+        }
+        if self.tickets > 0 && self.tags == 0 && self.max_tags_per_ticket > 0 {
+            return error("tickets can only be tagged if there are tags; set max tags to 0");
+        }
+        Ok(())
+    }
+}
+
+/// A generated ticket and the ids of its tags.
+#[derive(Debug, Clone)]
+pub struct SyntheticTicket {
+    pub ticket: Ticket,
+    pub tag_ids: Vec<Uuid>,
+}
+
+/// Produces the tags up front and then the tickets one by one, so that any
+/// number of tickets can be streamed into a database.
+pub struct Generator {
+    config: SyntheticConfig,
+    rng: Rng,
+    tags: Vec<Tag>,
+    /// Cumulative Zipf weights of the tags, ending at 1.0.
+    tag_cdf: Vec<f64>,
+    remaining: usize,
+}
+
+impl Generator {
+    pub fn new(config: SyntheticConfig) -> Result<Self> {
+        config.validate()?;
+        let mut rng = Rng(config.seed);
+
+        let first_ticket = config.until - Duration::days(DAYS_PER_MONTH * i64::from(config.months));
+        let tags: Vec<Tag> = (0..config.tags)
+            .map(|rank| Tag {
+                id: rng.uuid(),
+                name: words::tag_name(rank),
+                color: Some(format!("#{:06X}", rng.next_u64() & 0xFF_FFFF)),
+                created_at: first_ticket - Duration::seconds(rng.below(30 * 86_400) as i64),
+            })
+            .collect();
+
+        let weights: Vec<f64> = (1..=tags.len())
+            .map(|rank| 1.0 / (rank as f64).powf(config.tag_skew))
+            .collect();
+        let total: f64 = weights.iter().sum();
+        let tag_cdf = weights
+            .iter()
+            .scan(0.0, |sum, weight| {
+                *sum += weight / total;
+                Some(*sum)
+            })
+            .collect();
+
+        Ok(Self {
+            remaining: config.tickets,
+            config,
+            rng,
+            tags,
+            tag_cdf,
+        })
+    }
+
+    /// Tags by popularity, the most used first.
+    pub fn tags(&self) -> &[Tag] {
+        &self.tags
+    }
+
+    fn ticket(&mut self) -> SyntheticTicket {
+        let config = &self.config;
+        let rng = &mut self.rng;
+        let span_days = DAYS_PER_MONTH * i64::from(config.months);
+        let start = config.until - Duration::days(span_days);
+
+        // Position in the range, 0 at the start and 1 at `until`
+        let position = rng.unit().powf(1.0 / (GROWTH + 1.0));
+        let mut day = ((position * span_days as f64) as i64).min(span_days - 1);
+        // Fewer tickets are filed on weekends; move most of them to Friday
+        let weekday = (start + Duration::days(day)).weekday();
+        if matches!(weekday, Weekday::Sat | Weekday::Sun) && rng.chance(0.6) {
+            day = (day - weekday.num_days_from_monday() as i64 + 4).max(0);
+        }
+        // Mostly during working hours
+        let seconds = if rng.chance(0.8) {
+            8 * 3_600 + rng.below(10 * 3_600)
+        } else {
+            rng.below(86_400)
+        };
+        let created_at = start + Duration::days(day) + Duration::seconds(seconds as i64);
+        let latest = config.until - Duration::seconds(1);
+
+        // Scaled so that the mean over all tickets is `completed_ratio`
+        let age = 1.0 - position;
+        let mean_age = 1.0 / (GROWTH + 2.0);
+        let completed =
+            rng.chance((config.completed_ratio * (0.5 + age) / (0.5 + mean_age)).min(1.0));
+
+        // Resolution and edit delays are log-normal around two days
+        let updated_at = if completed || rng.chance(0.4) {
+            let hours = (48f64.ln() + 1.5 * rng.normal()).exp().min(24.0 * 365.0);
+            (created_at + Duration::seconds((hours * 3_600.0) as i64)).min(latest)
+        } else {
+            created_at
+        }
+        .max(created_at);
+
+        let count = rng
+            .weighted(&TAG_COUNT_WEIGHTS)
+            .min(config.max_tags_per_ticket)
+            .min(self.tags.len());
+        let mut tag_ids: Vec<Uuid> = Vec::with_capacity(count);
+        // Popular tags are drawn again and again; give up on a few rare
+        // duplicates rather than loop for long
+        for _ in 0..count * 20 {
+            if tag_ids.len() == count {
+                break;
+            }
+            let target = rng.unit();
+            let index = self
+                .tag_cdf
+                .partition_point(|&sum| sum <= target)
+                .min(self.tags.len() - 1);
+            let id = self.tags[index].id;
+            if !tag_ids.contains(&id) {
+                tag_ids.push(id);
+            }
+        }
+
+        SyntheticTicket {
+            ticket: Ticket {
+                id: rng.uuid(),
+                title: words::title(rng),
+                description: words::description(rng),
+                completed,
+                created_at,
+                updated_at,
+            },
+            tag_ids,
+        }
+    }
+}
+
+impl Iterator for Generator {
+    type Item = SyntheticTicket;
+
+    fn next(&mut self) -> Option<SyntheticTicket> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        Some(self.ticket())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// SplitMix64: small, fast and fixed, so a seed keeps producing the same
+/// data whatever versions of other crates are in the lock file.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn unit(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in `0..n`; `n` must not be 0.
+    fn below(&mut self, n: usize) -> usize {
+        ((self.unit() * n as f64) as usize).min(n - 1)
+    }
+
+    fn chance(&mut self, probability: f64) -> bool {
+        self.unit() < probability
+    }
+
+    /// Standard normal, by the Box-Muller transform.
+    fn normal(&mut self) -> f64 {
+        let u = 1.0 - self.unit();
+        let v = self.unit();
+        (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+    }
+
+    /// An index drawn with the given relative weights.
+    fn weighted(&mut self, weights: &[f64]) -> usize {
+        let mut target = self.unit() * weights.iter().sum::<f64>();
+        for (index, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                return index;
+            }
+            target -= weight;
+        }
+        weights.len() - 1
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.below(items.len())]
+    }
+
+    fn uuid(&mut self) -> Uuid {
+        let high = self.next_u64();
+        let low = self.next_u64();
+        uuid::Builder::from_random_bytes((u128::from(high) << 64 | u128::from(low)).to_be_bytes())
+            .into_uuid()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::validation::{validate_tag_name, validate_title};
+    use chrono::TimeZone;
+
+    fn config(seed: u64, tickets: usize) -> SyntheticConfig {
+        SyntheticConfig {
+            seed,
+            tickets,
+            until: Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap(),
+            ..SyntheticConfig::default()
+        }
+    }
+
+    /// 比较用的全部字段
+    fn fields(ticket: &SyntheticTicket) -> impl PartialEq + std::fmt::Debug {
+        let t = &ticket.ticket;
+        (
+            t.id,
+            t.title.clone(),
+            t.description.clone(),
+            t.completed,
+            t.created_at,
+            t.updated_at,
+            ticket.tag_ids.clone(),
+        )
+    }
+
+    #[test]
+    fn same_seed_gives_same_data() {
+        let first: Vec<_> = Generator::new(config(7, 200))
+            .unwrap()
+            .map(|t| fields(&t))
+            .collect();
+        let again: Vec<_> = Generator::new(config(7, 200))
+            .unwrap()
+            .map(|t| fields(&t))
+            .collect();
+        assert_eq!(first, again);
+
+        let tags = |seed| {
+            let generator = Generator::new(config(seed, 0)).unwrap();
+            generator
+                .tags()
+                .iter()
+                .map(|tag| (tag.id, tag.name.clone(), tag.color.clone(), tag.created_at))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(tags(7), tags(7));
+        assert_ne!(tags(7), tags(8));
+
+        let other = Generator::new(config(8, 1)).unwrap().next().unwrap();
+        assert_ne!(first[0], fields(&other));
+    }
+
+    #[test]
+    fn data_is_valid_and_shaped_as_configured() {
+        let config = config(3, 10_000);
+        let until = config.until;
+        let start = until - Duration::days(360);
+        let generator = Generator::new(config).unwrap();
+        let tags = generator.tags().to_vec();
+        for tag in &tags {
+            validate_tag_name(&tag.name).unwrap();
+        }
+
+        let tickets: Vec<_> = generator.collect();
+        let mut uses = vec![0usize; tags.len()];
+        for ticket in &tickets {
+            validate_title(&ticket.ticket.title).unwrap();
+            assert!(ticket.ticket.created_at >= start && ticket.ticket.created_at < until);
+            assert!(ticket.ticket.updated_at >= ticket.ticket.created_at);
+            assert!(ticket.ticket.updated_at < until);
+            assert!(ticket.tag_ids.len() <= 5);
+            for id in &ticket.tag_ids {
+                uses[tags.iter().position(|tag| tag.id == *id).unwrap()] += 1;
+            }
+        }
+
+        let completed = tickets.iter().filter(|t| t.ticket.completed).count();
+        assert!((5_700..=6_300).contains(&completed), "{}", completed);
+
+        // Zipf：排名越靠前的标签用得越多
+        assert!(uses[0] > uses[1] && uses[1] > uses[5] && uses[5] > uses[29]);
+        assert!(uses[0] > 4 * uses[29], "{:?}", uses);
+
+        // 越近期创建的工单越多
+        let recent = tickets
+            .iter()
+            .filter(|t| t.ticket.created_at >= until - Duration::days(180))
+            .count();
+        assert!(recent > 6_000, "{}", recent);
+
+        let longest = tickets
+            .iter()
+            .filter_map(|t| t.ticket.description.as_ref())
+            .map(String::len)
+            .max()
+            .unwrap();
+        assert!(longest > 5_000, "{}", longest);
+    }
+
+    #[test]
+    fn invalid_configs_are_rejected() {
+        let mut config = config(1, 10);
+        config.completed_ratio = 1.5;
+        assert!(matches!(
+            Generator::new(config.clone()),
+            Err(AppError::Validation(_))
+        ));
+        config.completed_ratio = 0.5;
+        config.tags = 0;
+        assert!(Generator::new(config.clone()).is_err());
+        config.max_tags_per_ticket = 0;
+        assert_eq!(Generator::new(config).unwrap().count(), 10);
+    }
+}
@@ -0,0 +1,184 @@
+//! Vocabulary for generated tag names, titles and descriptions.
+
+use super::Rng;
+
+/// Tag names in order of popularity; later ranks are built from
+/// [`TAG_PREFIXES`] and [`AREAS`].
+const TAGS: &[&str] = &[
+    "bug",
+    "feature",
+    "frontend",
+    "backend",
+    "urgent",
+    "ui",
+    "performance",
+    "documentation",
+    "api",
+    "database",
+    "security",
+    "refactor",
+    "testing",
+    "design",
+    "infrastructure",
+    "mobile",
+    "accessibility",
+    "tech-debt",
+    "customer",
+    "regression",
+    "good-first-issue",
+    "needs-info",
+    "blocked",
+    "question",
+    "wontfix",
+    "duplicate",
+    "ux",
+    "analytics",
+    "billing",
+    "i18n",
+];
+
+const TAG_PREFIXES: &[&str] = &["team", "area", "component", "customer"];
+
+const AREAS: &[&str] = &[
+    "search",
+    "login",
+    "checkout",
+    "notifications",
+    "dashboard",
+    "reports",
+    "settings",
+    "onboarding",
+    "exports",
+    "imports",
+    "webhooks",
+    "payments",
+    "permissions",
+    "profile",
+    "calendar",
+    "inbox",
+    "sync",
+    "audit-log",
+    "sessions",
+    "uploads",
+];
+
+const VERBS: &[&str] = &[
+    "Fix",
+    "Add",
+    "Improve",
+    "Refactor",
+    "Investigate",
+    "Remove",
+    "Update",
+    "Document",
+    "Speed up",
+    "Support",
+    "Migrate",
+    "Clean up",
+    "Validate",
+    "Cache",
+    "Redesign",
+];
+
+const OBJECTS: &[&str] = &[
+    "error handling",
+    "the empty state",
+    "pagination",
+    "the date picker",
+    "retry logic",
+    "CSV export",
+    "the sidebar",
+    "password reset",
+    "email templates",
+    "rate limiting",
+    "the query planner hints",
+    "keyboard shortcuts",
+    "dark mode",
+    "timezone handling",
+    "bulk actions",
+    "the loading spinner",
+    "form validation",
+    "the audit trail",
+    "SSO configuration",
+    "image thumbnails",
+];
+
+const QUALIFIERS: &[&str] = &[
+    "on slow networks",
+    "for large accounts",
+    "after the last release",
+    "on Safari",
+    "when the list is empty",
+    "for new users",
+    "under load",
+    "in the mobile app",
+    "behind the feature flag",
+    "for admins",
+];
+
+const SENTENCES: &[&str] = &[
+    "Several customers reported this through support last week.",
+    "The problem only shows up when more than a few hundred items are loaded.",
+    "Steps to reproduce: open the page, apply two filters and refresh.",
+    "Expected the previous selection to be kept, but it is reset instead.",
+    "This looks related to the change that moved the request to the background worker.",
+    "Logs show a timeout from the database after about thirty seconds.",
+    "We should add a regression test before touching this again.",
+    "The design team attached mockups in the shared folder.",
+    "A workaround is to reload the page, which most users will not think of.",
+    "Acceptance criteria: the behaviour matches the web app on every platform.",
+    "The fix needs a migration, so it should go out with the next scheduled release.",
+    "Metrics from production suggest that roughly one in fifty sessions is affected.",
+    "Nobody has been able to reproduce it locally so far.",
+    "The current implementation predates the new permission model.",
+    "It would be good to measure the impact before and after the change.",
+    "Error message seen by the user: something went wrong, please try again.",
+    "The API returns the right data; the problem is in how it is rendered.",
+    "Please coordinate with the mobile team, since they consume the same endpoint.",
+    "The old behaviour was intentional, but the reasons no longer apply.",
+    "Once this lands we can remove the temporary flag and its dead code.",
+];
+
+pub(super) fn tag_name(rank: usize) -> String {
+    if let Some(name) = TAGS.get(rank) {
+        return name.to_string();
+    }
+    let combined = rank - TAGS.len();
+    match TAG_PREFIXES.get(combined / AREAS.len()) {
+        Some(prefix) => format!("{}-{}", prefix, AREAS[combined % AREAS.len()]),
+        None => format!("label-{}", rank + 1),
+    }
+}
+
+pub(super) fn title(rng: &mut Rng) -> String {
+    let mut title = format!("{} {}", rng.pick(VERBS), rng.pick(OBJECTS));
+    if rng.chance(0.6) {
+        title.push(' ');
+        title.push_str(rng.pick(QUALIFIERS));
+    }
+    if rng.chance(0.3) {
+        title = format!("[{}] {}", rng.pick(AREAS), title);
+    }
+    title
+}
+
+/// No description for some tickets, a few sentences for most and many
+/// paragraphs for a long tail.
+pub(super) fn description(rng: &mut Rng) -> Option<String> {
+    let paragraphs = match rng.unit() {
+        x if x < 0.08 => return None,
+        x if x < 0.78 => 1 + rng.below(2),
+        x if x < 0.96 => 3 + rng.below(4),
+        _ => 10 + rng.below(60),
+    };
+    let text = (0..paragraphs)
+        .map(|_| {
+            (0..3 + rng.below(5))
+                .map(|_| rng.pick(SENTENCES))
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    Some(text)
+}
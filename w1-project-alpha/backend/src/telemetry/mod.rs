@@ -0,0 +1,124 @@
+use axum::http::{HeaderMap, HeaderValue};
+use tracing::Span;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+use crate::config::{LogConfig, LogFormat, OtelConfig};
+use crate::utils::error::Result;
+
+#[cfg(feature = "otel")]
+pub mod otel;
+
+/// Filter used when `RUST_LOG` is not set.
+const DEFAULT_FILTER: &str = "project_alpha_backend=debug,tower_http=debug";
+
+/// Header carrying the W3C trace context.
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+/// Keeps the span exporter alive; call [`Telemetry::shutdown`] before
+/// exiting so buffered spans are sent.
+#[must_use]
+pub struct Telemetry {
+    #[cfg(feature = "otel")]
+    tracer_provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Telemetry {
+    pub fn shutdown(self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = self.tracer_provider {
+            if let Err(e) = provider.shutdown() {
+                tracing::warn!("Failed to flush spans: {}", e);
+            }
+        }
+    }
+}
+
+/// Install the global tracing subscriber. The log level filter comes from
+/// `RUST_LOG`; with `LogFormat::Json` every event is one JSON object
+/// carrying the fields of its spans, such as `request_id`. When
+/// `otel.endpoint` is set and the `otel` feature is enabled, spans are
+/// exported over OTLP as well, independently of the log level.
+pub fn init(log: &LogConfig, otel: &OtelConfig) -> Result<Telemetry> {
+    // A per-layer filter, so that the log level does not limit span export
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+
+    let (text, json) = match log.format {
+        LogFormat::Text => (
+            Some(tracing_subscriber::fmt::layer().with_filter(filter)),
+            None,
+        ),
+        LogFormat::Json => (
+            None,
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .flatten_event(true)
+                    .with_current_span(true)
+                    .with_span_list(true)
+                    .with_filter(filter),
+            ),
+        ),
+    };
+    let registry = tracing_subscriber::registry().with(text).with(json);
+
+    #[cfg(feature = "otel")]
+    {
+        let tracer_provider = if otel.enabled() {
+            Some(otel::tracer_provider(otel)?)
+        } else {
+            None
+        };
+        registry
+            .with(tracer_provider.as_ref().map(otel::layer))
+            .init();
+        Ok(Telemetry { tracer_provider })
+    }
+
+    #[cfg(not(feature = "otel"))]
+    {
+        registry.init();
+        if otel.enabled() {
+            tracing::warn!("otel.endpoint is set, but this build lacks the otel feature");
+        }
+        Ok(Telemetry {})
+    }
+}
+
+/// Continue the trace named by the `traceparent` (and `tracestate`) headers
+/// of an incoming request in `span`. Does nothing without the `otel` feature.
+pub fn set_remote_parent(span: &Span, headers: &HeaderMap) {
+    #[cfg(feature = "otel")]
+    otel::set_remote_parent(span, headers);
+    #[cfg(not(feature = "otel"))]
+    let _ = (span, headers);
+}
+
+/// Like [`set_remote_parent`], for a stored `traceparent` value.
+pub fn set_parent_from_traceparent(span: &Span, traceparent: &str) {
+    if let Ok(value) = HeaderValue::from_str(traceparent) {
+        let mut headers = HeaderMap::new();
+        headers.insert(TRACEPARENT_HEADER, value);
+        set_remote_parent(span, &headers);
+    }
+}
+
+/// The W3C trace context headers that make a downstream request part of
+/// `span`'s trace. Empty when the span is not exported.
+pub fn trace_headers(span: &Span) -> HeaderMap {
+    #[allow(unused_mut)]
+    let mut headers = HeaderMap::new();
+    #[cfg(feature = "otel")]
+    otel::inject(span, &mut headers);
+    #[cfg(not(feature = "otel"))]
+    let _ = span;
+    headers
+}
+
+/// The `traceparent` of `span`, for work that continues the trace later.
+pub fn traceparent(span: &Span) -> Option<String> {
+    trace_headers(span)
+        .get(TRACEPARENT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
@@ -0,0 +1,23 @@
+use std::time::Duration;
+
+/// Delay before the next attempt, after `attempts` failed attempts.
+pub fn backoff_delay(attempts: i32, base: Duration, max: Duration) -> Duration {
+    let exponent = attempts.saturating_sub(1).clamp(0, 31) as u32;
+    base.saturating_mul(2u32.saturating_pow(exponent)).min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let base = Duration::from_secs(5);
+        let max = Duration::from_secs(60);
+        assert_eq!(backoff_delay(1, base, max), Duration::from_secs(5));
+        assert_eq!(backoff_delay(2, base, max), Duration::from_secs(10));
+        assert_eq!(backoff_delay(4, base, max), Duration::from_secs(40));
+        assert_eq!(backoff_delay(5, base, max), max);
+        assert_eq!(backoff_delay(100, base, max), max);
+    }
+}
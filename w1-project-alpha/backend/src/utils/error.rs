@@ -3,9 +3,12 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
-use serde_json::json;
 use thiserror::Error;
 
+use crate::middleware::RequestId;
+
+pub use crate::models::ErrorResponse;
+
 #[derive(Error, Debug)]
 pub enum AppError {
     #[error("Database error: {0}")]
@@ -22,9 +25,11 @@ pub enum AppError {
     Internal(String),
 }
 
-impl IntoResponse for AppError {
-    fn into_response(self) -> Response {
-        let (status, error_message) = match &self {
+impl AppError {
+    /// The status and the message shown to clients. Database and internal
+    /// errors are logged here and replaced by a generic message.
+    pub fn status_and_message(&self) -> (StatusCode, String) {
+        match self {
             AppError::Database(err) => {
                 tracing::error!("Database error: {:?}", err);
                 (StatusCode::INTERNAL_SERVER_ERROR, "数据库错误".to_string())
@@ -38,12 +43,20 @@ impl IntoResponse for AppError {
                     "内部服务器错误".to_string(),
                 )
             }
-        };
+        }
+    }
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        let (status, error_message) = self.status_and_message();
 
-        let body = Json(json!({
-            "error": error_message,
-            "status": status.as_u16()
-        }));
+        // Lets a client quote the ID that the server-side log lines carry
+        let body = Json(ErrorResponse {
+            error: error_message,
+            status: status.as_u16(),
+            request_id: RequestId::current().map(|id| id.0),
+        });
 
         (status, body).into_response()
     }
@@ -0,0 +1,112 @@
+use crate::models::TicketWithTags;
+use crate::utils::error::{AppError, Result};
+use axum::body::Bytes;
+use futures_util::stream::{BoxStream, TryStreamExt};
+
+pub use crate::models::ExportFormat;
+
+/// Column order of CSV exports; imports accept the same header.
+pub const CSV_COLUMNS: [&str; 7] = [
+    "id",
+    "title",
+    "description",
+    "completed",
+    "created_at",
+    "updated_at",
+    "tags",
+];
+
+/// Separator between tag names in the CSV `tags` column.
+pub const TAG_SEPARATOR: u8 = b',';
+
+/// Encode a stream of tickets chunk by chunk in the requested format.
+pub fn encode(
+    format: ExportFormat,
+    mut tickets: BoxStream<'static, Result<TicketWithTags>>,
+) -> BoxStream<'static, Result<Bytes>> {
+    Box::pin(async_stream::try_stream! {
+        match format {
+            ExportFormat::Csv => yield Bytes::from(csv_header()?),
+            ExportFormat::Json => yield Bytes::from_static(b"["),
+            ExportFormat::Ndjson => {}
+        }
+
+        let mut first = true;
+        while let Some(ticket) = tickets.try_next().await? {
+            let chunk = match format {
+                ExportFormat::Csv => csv_record(&ticket)?,
+                ExportFormat::Json => {
+                    let mut buf = if first { Vec::new() } else { b",".to_vec() };
+                    serde_json::to_writer(&mut buf, &ticket).map_err(json_error)?;
+                    buf
+                }
+                ExportFormat::Ndjson => {
+                    let mut buf = serde_json::to_vec(&ticket).map_err(json_error)?;
+                    buf.push(b'\n');
+                    buf
+                }
+            };
+            first = false;
+            yield Bytes::from(chunk);
+        }
+
+        if format == ExportFormat::Json {
+            yield Bytes::from_static(b"]");
+        }
+    })
+}
+
+fn csv_header() -> Result<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(CSV_COLUMNS).map_err(csv_error)?;
+    writer.into_inner().map_err(|e| csv_error(e.into_error()))
+}
+
+/// One CSV line for a ticket, with tag names flattened into the last column.
+fn csv_record(ticket: &TicketWithTags) -> Result<Vec<u8>> {
+    let tags = tags_cell(ticket)?;
+
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer
+        .write_record([
+            ticket.ticket.id.to_string().as_str(),
+            ticket.ticket.title.as_str(),
+            ticket.ticket.description.as_deref().unwrap_or(""),
+            if ticket.ticket.completed {
+                "true"
+            } else {
+                "false"
+            },
+            ticket.ticket.created_at.to_rfc3339().as_str(),
+            ticket.ticket.updated_at.to_rfc3339().as_str(),
+            tags.as_str(),
+        ])
+        .map_err(csv_error)?;
+    writer.into_inner().map_err(|e| csv_error(e.into_error()))
+}
+
+/// The `tags` cell: the tag names written as a CSV record of their own, so
+/// that a name containing the separator or a quote is quoted and the import
+/// reads it back unchanged. Other names are simply joined by the separator.
+fn tags_cell(ticket: &TicketWithTags) -> Result<String> {
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(TAG_SEPARATOR)
+        .terminator(csv::Terminator::Any(b'\n'))
+        .from_writer(Vec::new());
+    if !ticket.tags.is_empty() {
+        writer
+            .write_record(ticket.tags.iter().map(|tag| tag.name.as_str()))
+            .map_err(csv_error)?;
+    }
+    let mut cell = writer.into_inner().map_err(|e| csv_error(e.into_error()))?;
+    cell.pop_if(|byte| *byte == b'\n');
+    String::from_utf8(cell).map_err(csv_error)
+}
+
+fn csv_error(err: impl std::fmt::Display) -> AppError {
+    AppError::Internal(format!("Failed to write CSV: {}", err))
+}
+
+fn json_error(err: serde_json::Error) -> AppError {
+    AppError::Internal(format!("Failed to write JSON: {}", err))
+}
@@ -0,0 +1,230 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+
+use crate::models::{ImportRowError, ImportTicket};
+use crate::services::validation::{validate_tag_name, validate_title};
+use crate::utils::error::{AppError, Result};
+use crate::utils::export::TAG_SEPARATOR;
+
+pub use crate::models::ImportFormat;
+
+/// A row as read from the input, before validation.
+#[derive(Debug, Default)]
+struct RawRow {
+    title: Option<String>,
+    description: Option<String>,
+    completed: Option<bool>,
+    tags: Vec<String>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+/// CSV columns use the export header; `id` and unknown columns are ignored.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CsvRow {
+    title: Option<String>,
+    description: Option<String>,
+    completed: Option<String>,
+    tags: Option<String>,
+    created_at: Option<String>,
+    updated_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum JsonTags {
+    List(Vec<String>),
+    Joined(String),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct JsonRow {
+    title: Option<String>,
+    description: Option<String>,
+    completed: Option<bool>,
+    tags: Option<JsonTags>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+}
+
+/// Parse and validate an import body.
+///
+/// Fails only when the body as a whole cannot be read; problems with single
+/// rows are returned alongside the valid rows.
+pub fn parse(
+    format: ImportFormat,
+    body: &[u8],
+) -> Result<(Vec<ImportTicket>, Vec<ImportRowError>)> {
+    let raw_rows = match format {
+        ImportFormat::Csv => read_csv(body)?,
+        ImportFormat::Json => read_json(body)?,
+    };
+
+    let mut valid = Vec::new();
+    let mut errors = Vec::new();
+    for (index, raw) in raw_rows.into_iter().enumerate() {
+        let row = index + 1;
+        match raw.and_then(validate) {
+            Ok(ticket) => valid.push(ticket),
+            Err(row_errors) => errors.push(ImportRowError {
+                row,
+                errors: row_errors,
+            }),
+        }
+    }
+
+    Ok((valid, errors))
+}
+
+fn read_csv(body: &[u8]) -> Result<Vec<std::result::Result<RawRow, Vec<String>>>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::Fields)
+        .from_reader(body);
+
+    let headers = reader
+        .headers()
+        .map_err(|e| AppError::Validation(format!("Invalid CSV header: {}", e)))?;
+    if !headers.iter().any(|h| h == "title") {
+        return Err(AppError::Validation(
+            "CSV header must contain a 'title' column".to_string(),
+        ));
+    }
+
+    Ok(reader
+        .deserialize::<CsvRow>()
+        .map(|record| {
+            let record = record.map_err(|e| vec![format!("invalid CSV record: {}", e)])?;
+            csv_row(record)
+        })
+        .collect())
+}
+
+fn csv_row(record: CsvRow) -> std::result::Result<RawRow, Vec<String>> {
+    let mut errors = Vec::new();
+
+    let completed = match non_empty(record.completed) {
+        None => None,
+        Some(value) => match value.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" => Some(true),
+            "false" | "0" | "no" => Some(false),
+            _ => {
+                errors.push(format!("completed must be true or false, got '{}'", value));
+                None
+            }
+        },
+    };
+
+    let mut timestamp = |field: &str, value: Option<String>| {
+        non_empty(value).and_then(|value| match DateTime::parse_from_rfc3339(&value) {
+            Ok(parsed) => Some(parsed.with_timezone(&Utc)),
+            Err(e) => {
+                errors.push(format!("{} must be an RFC 3339 timestamp: {}", field, e));
+                None
+            }
+        })
+    };
+    let created_at = timestamp("created_at", record.created_at);
+    let updated_at = timestamp("updated_at", record.updated_at);
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(RawRow {
+        title: record.title,
+        description: non_empty(record.description),
+        completed,
+        tags: split_tags(record.tags.as_deref().unwrap_or("")),
+        created_at,
+        updated_at,
+    })
+}
+
+fn read_json(body: &[u8]) -> Result<Vec<std::result::Result<RawRow, Vec<String>>>> {
+    let values: Vec<serde_json::Value> = serde_json::from_slice(body).map_err(|e| {
+        AppError::Validation(format!("Body must be a JSON array of tickets: {}", e))
+    })?;
+
+    Ok(values
+        .into_iter()
+        .map(|value| {
+            let row: JsonRow =
+                serde_json::from_value(value).map_err(|e| vec![format!("invalid row: {}", e)])?;
+            let tags = match row.tags {
+                Some(JsonTags::List(tags)) => tags,
+                Some(JsonTags::Joined(tags)) => split_tags(&tags),
+                None => Vec::new(),
+            };
+            Ok(RawRow {
+                title: row.title,
+                description: row.description,
+                completed: row.completed,
+                tags,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            })
+        })
+        .collect())
+}
+
+fn validate(raw: RawRow) -> std::result::Result<ImportTicket, Vec<String>> {
+    let mut errors = Vec::new();
+
+    let title = raw.title.unwrap_or_default().trim().to_string();
+    if let Err(error) = validate_title(&title) {
+        errors.push(error);
+    }
+
+    let mut tags: Vec<String> = Vec::new();
+    for tag in raw.tags {
+        let tag = tag.trim().to_string();
+        if let Err(error) = validate_tag_name(&tag) {
+            errors.push(error);
+        } else if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+
+    if let (Some(created_at), Some(updated_at)) = (raw.created_at, raw.updated_at) {
+        if updated_at < created_at {
+            errors.push("updated_at must not be before created_at".to_string());
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(ImportTicket {
+        title,
+        description: raw.description,
+        completed: raw.completed.unwrap_or(false),
+        tags,
+        created_at: raw.created_at,
+        updated_at: raw.updated_at,
+    })
+}
+
+/// Tag names from a `tags` cell, which holds them as one CSV record as
+/// written by the export. Hand-written lists such as `bug, ui` read the same.
+fn split_tags(tags: &str) -> Vec<String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .delimiter(TAG_SEPARATOR)
+        .from_reader(tags.as_bytes());
+    let Some(Ok(record)) = reader.records().next() else {
+        return Vec::new();
+    };
+    record
+        .iter()
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
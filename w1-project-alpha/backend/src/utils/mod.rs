@@ -1 +1,4 @@
+pub mod backoff;
 pub mod error;
+pub mod export;
+pub mod import;
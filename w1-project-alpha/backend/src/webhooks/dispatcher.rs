@@ -0,0 +1,204 @@
+use chrono::Utc;
+use futures_util::stream::{self, StreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::{Instrument, Span};
+
+use super::signature::{sign, DELIVERY_HEADER, EVENT_HEADER, SIGNATURE_HEADER};
+use crate::models::PendingDelivery;
+use crate::repositories::WebhookStore;
+use crate::telemetry;
+use crate::utils::backoff::backoff_delay;
+use crate::utils::error::{AppError, Result};
+
+#[derive(Debug, Clone)]
+pub struct DispatcherConfig {
+    /// How long to sleep when there is nothing to deliver.
+    pub poll_interval: Duration,
+    /// Maximum deliveries claimed per round.
+    pub batch_size: i64,
+    /// Deliveries of a round sent at the same time, so one slow receiver
+    /// does not hold up the others.
+    pub concurrency: usize,
+    /// Attempts before a delivery is marked `failed`.
+    pub max_attempts: i32,
+    /// Delay before the first retry; doubled on each further attempt.
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+    pub request_timeout: Duration,
+    /// How long a claimed delivery stays hidden from other dispatchers.
+    pub lease: Duration,
+}
+
+impl Default for DispatcherConfig {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            batch_size: 50,
+            concurrency: 8,
+            max_attempts: 8,
+            base_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(3600),
+            request_timeout: Duration::from_secs(10),
+            lease: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Background worker that drains the webhook outbox.
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    repository: Arc<dyn WebhookStore>,
+    client: reqwest::Client,
+    config: DispatcherConfig,
+}
+
+impl WebhookDispatcher {
+    pub fn new(repository: Arc<dyn WebhookStore>, config: DispatcherConfig) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout)
+            .user_agent(concat!(
+                "project-alpha-webhooks/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .map_err(|e| AppError::Internal(format!("Failed to build webhook client: {}", e)))?;
+
+        Ok(Self {
+            repository,
+            client,
+            config,
+        })
+    }
+
+    /// Deliver every due delivery once. Returns how many were attempted.
+    ///
+    /// A delivery whose outcome cannot be recorded is logged and skipped; it
+    /// is retried when its lease expires, without holding up the rest.
+    pub async fn run_once(&self) -> Result<usize> {
+        let deliveries = self
+            .repository
+            .claim_due(self.config.batch_size, self.config.lease)
+            .await?;
+        let count = deliveries.len();
+
+        stream::iter(deliveries)
+            .for_each_concurrent(self.config.concurrency.max(1), |delivery| async move {
+                let id = delivery.id;
+                if let Err(err) = self.deliver(delivery).await {
+                    tracing::error!("Webhook delivery {} could not be recorded: {}", id, err);
+                }
+            })
+            .await;
+
+        Ok(count)
+    }
+
+    /// Poll the outbox until `shutdown` is cancelled, then deliver whatever
+    /// is already due before returning.
+    pub async fn run(self, shutdown: CancellationToken) {
+        while !shutdown.is_cancelled() {
+            let idle = match self.run_once().await {
+                Ok(0) => true,
+                Ok(_) => false,
+                Err(err) => {
+                    tracing::error!("Webhook dispatch failed: {}", err);
+                    true
+                }
+            };
+            if idle {
+                tokio::select! {
+                    _ = tokio::time::sleep(self.config.poll_interval) => {}
+                    _ = shutdown.cancelled() => {}
+                }
+            }
+        }
+
+        // Failed attempts are rescheduled into the future, so this terminates
+        loop {
+            match self.run_once().await {
+                Ok(0) => break,
+                Ok(count) => tracing::info!("Flushed {} webhook deliveries", count),
+                Err(err) => {
+                    tracing::error!("Webhook flush failed: {}", err);
+                    break;
+                }
+            }
+        }
+        tracing::info!("Webhook dispatcher stopped");
+    }
+
+    /// Attempt one delivery in a span that continues the trace of the request
+    /// which produced the event, and pass that trace on to the receiver.
+    async fn deliver(&self, delivery: PendingDelivery) -> Result<()> {
+        let span = tracing::info_span!(
+            "webhook.deliver",
+            otel.kind = "client",
+            event = %delivery.event,
+            delivery_id = %delivery.id,
+            url.full = %delivery.url,
+            http.response.status_code = tracing::field::Empty,
+        );
+        if let Some(traceparent) = &delivery.traceparent {
+            telemetry::set_parent_from_traceparent(&span, traceparent);
+        }
+        self.attempt(delivery, &span).instrument(span.clone()).await
+    }
+
+    async fn attempt(&self, delivery: PendingDelivery, span: &Span) -> Result<()> {
+        let body = serde_json::to_vec(&delivery.payload)
+            .map_err(|e| AppError::Internal(format!("Failed to encode payload: {}", e)))?;
+
+        let response = self
+            .client
+            .post(&delivery.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(SIGNATURE_HEADER, sign(&delivery.secret, &body))
+            .header(EVENT_HEADER, &delivery.event)
+            .header(DELIVERY_HEADER, delivery.id.to_string())
+            .headers(telemetry::trace_headers(span))
+            .body(body)
+            .send()
+            .await;
+        if let Ok(response) = &response {
+            span.record(
+                "http.response.status_code",
+                i64::from(response.status().as_u16()),
+            );
+        }
+
+        let (status_code, error) = match response {
+            Ok(response) if response.status().is_success() => {
+                tracing::debug!("Delivered {} to {}", delivery.event, delivery.url);
+                return self
+                    .repository
+                    .mark_delivered(delivery.id, i32::from(response.status().as_u16()))
+                    .await;
+            }
+            Ok(response) => (
+                Some(i32::from(response.status().as_u16())),
+                format!("HTTP {}", response.status()),
+            ),
+            Err(err) => (None, err.to_string()),
+        };
+
+        let attempts = delivery.attempts + 1;
+        let retry_at = (attempts < self.config.max_attempts).then(|| {
+            let delay = backoff_delay(attempts, self.config.base_backoff, self.config.max_backoff);
+            Utc::now() + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX)
+        });
+
+        tracing::warn!(
+            "Webhook delivery {} to {} failed (attempt {}): {}",
+            delivery.id,
+            delivery.url,
+            attempts,
+            error
+        );
+
+        self.repository
+            .mark_failed(delivery.id, status_code, &error, retry_at)
+            .await
+    }
+}
@@ -0,0 +1,5 @@
+pub mod dispatcher;
+pub mod signature;
+
+pub use dispatcher::*;
+pub use signature::*;
@@ -0,0 +1,226 @@
+//! 管理子命令测试：对临时 SQLite 文件运行 migrate、seed、check（需要 `--features sqlite`）
+#![cfg(feature = "sqlite")]
+
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// 测试结束后自动删除的临时目录，其中的 alpha.db 作为数据库
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new() -> Self {
+        let path = std::env::temp_dir().join(format!("alpha-admin-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+
+    fn database_url(&self) -> String {
+        format!("sqlite://{}", self.0.join("alpha.db").display())
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.0).ok();
+    }
+}
+
+fn run(database_url: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_project-alpha-backend"))
+        .arg("--database-url")
+        .arg(database_url)
+        .args(args)
+        .current_dir(std::env::temp_dir())
+        .env_clear()
+        .env("RUST_LOG", "error")
+        .output()
+        .expect("Failed to run server binary")
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{:?}", output);
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+/// migrate status 输出中某个版本的状态列（列之间至少隔两个空格）
+fn state_of(status: &str, version: &str) -> String {
+    status
+        .lines()
+        .find(|line| line.starts_with(version))
+        .unwrap_or_else(|| panic!("no migration {} in\n{}", version, status))
+        .split("  ")
+        .map(str::trim)
+        .filter(|column| !column.is_empty())
+        .nth(2)
+        .unwrap()
+        .to_string()
+}
+
+#[test]
+fn test_migrate_up_down_and_status() {
+    let dir = TempDir::new();
+    let url = dir.database_url();
+
+    let status = stdout(&run(&url, &["migrate", "status"]));
+    assert!(
+        status.starts_with("VERSION         DESCRIPTION"),
+        "{}",
+        status
+    );
+    assert_eq!(state_of(&status, "20231201000001"), "pending");
+
+    let up = stdout(&run(&url, &["migrate", "up"]));
+    assert_eq!(
+        up,
+        "Applied 20231201000001 initial schema\n\
+         Applied 20231201000002 webhooks\n\
+         Applied 20231201000003 webhook trace context\n"
+    );
+    assert_eq!(
+        stdout(&run(&url, &["migrate", "up"])),
+        "Schema is up to date\n"
+    );
+    let status = stdout(&run(&url, &["migrate", "status"]));
+    assert_eq!(state_of(&status, "20231201000003"), "applied");
+
+    // 默认只回滚最近一个，--to 保留该版本及更早的迁移
+    assert_eq!(
+        stdout(&run(&url, &["migrate", "down"])),
+        "Reverted 20231201000003 webhook trace context\n"
+    );
+    let status = stdout(&run(&url, &["migrate", "status"]));
+    assert_eq!(state_of(&status, "20231201000002"), "applied");
+    assert_eq!(state_of(&status, "20231201000003"), "pending");
+
+    assert_eq!(
+        stdout(&run(&url, &["migrate", "down", "--to", "0"])),
+        "Reverted 20231201000002 webhooks\n\
+         Reverted 20231201000001 initial schema\n"
+    );
+    assert_eq!(
+        stdout(&run(&url, &["migrate", "down"])),
+        "Nothing to revert\n"
+    );
+
+    // 回滚后可以重新迁移
+    stdout(&run(&url, &["migrate", "up"]));
+    let check = stdout(&run(&url, &["check"]));
+    assert!(check.contains("database    ok (sqlite"), "{}", check);
+    assert!(check.contains("migrations  up to date"), "{}", check);
+}
+
+#[test]
+fn test_seed_requires_migrations_and_a_script_for_sqlite() {
+    let dir = TempDir::new();
+    let url = dir.database_url();
+
+    let output = run(&url, &["seed"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("written for Postgres"), "{}", stderr);
+
+    let script = dir.0.join("seed.sql");
+    std::fs::write(
+        &script,
+        "INSERT INTO tags (id, name, color, created_at) \
+         VALUES (randomblob(16), 'ops', NULL, '2024-01-01T00:00:00Z');",
+    )
+    .unwrap();
+    let script = script.to_str().unwrap();
+
+    let output = run(&url, &["seed", "--file", script]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("run `migrate up` first"), "{}", stderr);
+
+    stdout(&run(&url, &["migrate", "up"]));
+    let seeded = stdout(&run(&url, &["seed", "--file", script]));
+    assert_eq!(seeded, format!("Seeded the database from {}\n", script));
+
+    // 迁移文件在应用后被修改：状态显示为 modified，且拒绝写入示例数据
+    tokio::runtime::Runtime::new().unwrap().block_on(async {
+        let pool = sqlx::SqlitePool::connect(&url).await.unwrap();
+        sqlx::query("UPDATE _sqlx_migrations SET checksum = x'00' WHERE version = 20231201000002")
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool.close().await;
+    });
+    let status = stdout(&run(&url, &["migrate", "status"]));
+    assert_eq!(state_of(&status, "20231201000002"), "modified");
+    let output = run(&url, &["seed", "--file", script]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.contains("Migration 20231201000002 webhooks is modified"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_check_fails_without_database() {
+    // 端口 1 上没有服务，连接立即被拒绝
+    let output = run(
+        "postgres://postgres@127.0.0.1:1/alpha",
+        &["--set", "database.acquire_timeout_secs=1", "check"],
+    );
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.starts_with("config      ok (defaults and environment)\n"));
+    assert!(stdout.contains("database    failed"));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        stderr.starts_with("error: Cannot connect to the database"),
+        "{}",
+        stderr
+    );
+}
+
+#[test]
+fn test_seed_generate_is_reproducible() {
+    let dir = TempDir::new();
+    let url = dir.database_url();
+    stdout(&run(&url, &["migrate", "up"]));
+
+    let args = [
+        "seed",
+        "--generate",
+        "--tickets",
+        "1200",
+        "--tags",
+        "40",
+        "--seed",
+        "42",
+        "--until",
+        "2024-06-01",
+        "--batch-size",
+        "500",
+    ];
+    let first = stdout(&run(&url, &args));
+    assert!(
+        first.starts_with("Inserted 40 tags, 1200 tickets and "),
+        "{}",
+        first
+    );
+    assert!(
+        first.contains(
+            "Reproduce with: seed --generate --seed 42 --tickets 1200 --tags 40 \
+             --completed-ratio 0.6 --months 12 --until 2024-06-01"
+        ),
+        "{}",
+        first
+    );
+
+    // 相同参数生成相同的行，已存在的全部跳过
+    let again = stdout(&run(&url, &args));
+    assert!(
+        again.starts_with("Inserted 0 tags, 0 tickets and 0 ticket tags"),
+        "{}",
+        again
+    );
+
+    // 生成参数只能与 --generate 一起使用
+    let output = run(&url, &["seed", "--tickets", "10"]);
+    assert_eq!(output.status.code(), Some(2));
+}
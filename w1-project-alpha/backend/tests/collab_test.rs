@@ -0,0 +1,170 @@
+mod common;
+
+use common::{TestClient, TestServer};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::TcpStream;
+use tokio::time::{sleep, timeout, Duration};
+use tokio_tungstenite::{connect_async, tungstenite::Message, MaybeTlsStream, WebSocketStream};
+
+type Socket = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+/// 连接协作通道，并等待服务端完成订阅
+async fn connect(server: &TestServer, user: &str) -> Socket {
+    let (socket, _) = connect_async(server.ws_url(&format!("/api/ws?user={}", user)))
+        .await
+        .expect("WebSocket connect failed");
+    sleep(Duration::from_millis(50)).await;
+    socket
+}
+
+async fn send(socket: &mut Socket, message: Value) {
+    socket
+        .send(Message::Text(message.to_string().into()))
+        .await
+        .expect("WebSocket send failed");
+}
+
+/// 读取下一条 JSON 消息（2 秒超时）
+async fn recv(socket: &mut Socket) -> Value {
+    loop {
+        let message = timeout(Duration::from_secs(2), socket.next())
+            .await
+            .expect("Timed out waiting for message")
+            .expect("Socket closed")
+            .expect("WebSocket error");
+        if let Message::Text(text) = message {
+            return serde_json::from_str(&text).expect("Invalid JSON message");
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_presence_and_typing() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client
+        .post("/api/tickets", json!({ "title": "Shared ticket" }))
+        .await;
+    let ticket: Value = resp.json().await.unwrap();
+    let ticket_id = ticket["id"].as_str().unwrap().to_string();
+
+    let mut alice = connect(&server, "alice").await;
+    let mut bob = connect(&server, "bob").await;
+
+    // 1. alice 查看 ticket，双方都收到在线列表
+    send(
+        &mut alice,
+        json!({ "type": "join", "ticket_id": ticket_id, "mode": "viewing" }),
+    )
+    .await;
+    for socket in [&mut alice, &mut bob] {
+        let message = recv(socket).await;
+        assert_eq!(message["type"], "presence");
+        assert_eq!(message["ticket_id"], ticket_id);
+        assert_eq!(
+            message["users"],
+            json!([{ "user": "alice", "mode": "viewing" }])
+        );
+    }
+
+    // 2. bob 在描述中输入，视为正在编辑
+    send(
+        &mut bob,
+        json!({ "type": "typing", "ticket_id": ticket_id, "field": "description" }),
+    )
+    .await;
+    let message = recv(&mut alice).await;
+    assert_eq!(message["type"], "presence");
+    assert_eq!(
+        message["users"],
+        json!([
+            { "user": "alice", "mode": "viewing" },
+            { "user": "bob", "mode": "editing" }
+        ])
+    );
+    let message = recv(&mut alice).await;
+    assert_eq!(message["type"], "typing");
+    assert_eq!(message["user"], "bob");
+    assert_eq!(message["field"], "description");
+
+    let message = recv(&mut bob).await;
+    assert_eq!(message["type"], "presence");
+
+    // 3. REST 接口返回同样的在线列表
+    let resp = client
+        .get(&format!("/api/tickets/{}/presence", ticket_id))
+        .await;
+    assert_eq!(resp.status(), 200);
+    let users: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(users.len(), 2);
+
+    // 4. 数据变更事件会推送到连接上；bob 不会收到自己的输入提示
+    client
+        .patch(&format!("/api/tickets/{}/toggle", ticket_id))
+        .await;
+    let message = recv(&mut bob).await;
+    assert_eq!(message["type"], "change");
+    assert_eq!(message["event"], "ticket_updated");
+    assert_eq!(message["ticket"]["id"], ticket_id);
+    assert_eq!(message["ticket"]["completed"], true);
+    let message = recv(&mut alice).await;
+    assert_eq!(message["event"], "ticket_updated");
+
+    // 5. bob 断开后 alice 收到更新后的在线列表
+    bob.close(None).await.unwrap();
+    let message = recv(&mut alice).await;
+    assert_eq!(message["type"], "presence");
+    assert_eq!(
+        message["users"],
+        json!([{ "user": "alice", "mode": "viewing" }])
+    );
+
+    // 6. alice 离开后在线列表为空
+    send(
+        &mut alice,
+        json!({ "type": "leave", "ticket_id": ticket_id }),
+    )
+    .await;
+    let message = recv(&mut alice).await;
+    assert_eq!(message["users"], json!([]));
+}
+
+#[tokio::test]
+async fn test_collab_requires_user() {
+    let server = TestServer::start().await;
+
+    let result = connect_async(server.ws_url("/api/ws?user=%20")).await;
+    assert!(result.is_err());
+
+    let result = connect_async(server.ws_url("/api/ws")).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_shutdown_flushes_and_closes_sockets() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    let mut socket = connect(&server, "alice").await;
+
+    // 关闭前发布的事件仍会送达，随后服务端以 1001 (Going Away) 关闭连接
+    client
+        .post("/api/tickets", json!({ "title": "Last words" }))
+        .await;
+    server.state.events.close();
+
+    let message = recv(&mut socket).await;
+    assert_eq!(message["event"], "ticket_created");
+    assert_eq!(message["ticket"]["title"], "Last words");
+
+    let message = timeout(Duration::from_secs(2), socket.next())
+        .await
+        .expect("Timed out waiting for close")
+        .expect("Socket closed without a close frame")
+        .expect("WebSocket error");
+    let Message::Close(Some(frame)) = message else {
+        panic!("expected a close frame, got {:?}", message);
+    };
+    assert_eq!(u16::from(frame.code), 1001);
+}
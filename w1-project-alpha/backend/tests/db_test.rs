@@ -0,0 +1,33 @@
+//! 数据库连接池配置测试
+mod common;
+
+use project_alpha_backend::{config::DatabaseConfig, Database};
+
+#[tokio::test]
+async fn test_pool_follows_config() {
+    // 只读取会话设置，直接连接 TEST_DATABASE_URL
+    let mut config = DatabaseConfig::new(common::database_url());
+    config.max_connections = 3;
+    config.statement_timeout_ms = 200;
+
+    // 不启用 sqlite feature 时只有 Postgres 一个变体
+    #[allow(irrefutable_let_patterns)]
+    let Database::Postgres(pool) = Database::connect(&config).await.unwrap() else {
+        panic!("expected a Postgres pool");
+    };
+    assert_eq!(pool.options().get_max_connections(), 3);
+
+    // 每个连接都带上了 statement_timeout
+    let (timeout,): (String,) = sqlx::query_as("SHOW statement_timeout")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(timeout, "200ms");
+
+    let err = sqlx::query("SELECT pg_sleep(1)")
+        .execute(&pool)
+        .await
+        .unwrap_err();
+    let code = err.as_database_error().and_then(|e| e.code());
+    assert_eq!(code.as_deref(), Some("57014")); // query_canceled
+}
@@ -0,0 +1,161 @@
+mod common;
+
+use common::{TestClient, TestServer};
+use serde_json::{json, Value};
+
+/// 创建测试数据：两个标签、三个 ticket（其中一个已完成）
+async fn seed(client: &TestClient) -> (String, String) {
+    let resp = client
+        .post("/api/tags", json!({ "name": "bug", "color": "#FF0000" }))
+        .await;
+    let bug: Value = resp.json().await.unwrap();
+    let bug_id = bug["id"].as_str().unwrap().to_string();
+
+    let resp = client
+        .post("/api/tags", json!({ "name": "urgent", "color": null }))
+        .await;
+    let urgent: Value = resp.json().await.unwrap();
+    let urgent_id = urgent["id"].as_str().unwrap().to_string();
+
+    let resp = client
+        .post(
+            "/api/tickets",
+            json!({
+                "title": "Fix \"login\", then logout",
+                "description": "Line one\nLine two",
+                "tag_ids": [bug_id, urgent_id]
+            }),
+        )
+        .await;
+    let ticket: Value = resp.json().await.unwrap();
+    let login_id = ticket["id"].as_str().unwrap().to_string();
+
+    client
+        .post(
+            "/api/tickets",
+            json!({ "title": "Write docs", "description": null, "tag_ids": [urgent_id] }),
+        )
+        .await;
+
+    let resp = client
+        .post("/api/tickets", json!({ "title": "Old task" }))
+        .await;
+    let ticket: Value = resp.json().await.unwrap();
+    client
+        .patch(&format!(
+            "/api/tickets/{}/toggle",
+            ticket["id"].as_str().unwrap()
+        ))
+        .await;
+
+    (bug_id, login_id)
+}
+
+#[tokio::test]
+async fn test_export_csv() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    let (_, login_id) = seed(&client).await;
+
+    let resp = client.get("/api/tickets/export?format=csv").await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["content-type"], "text/csv; charset=utf-8");
+    let disposition = resp.headers()["content-disposition"].to_str().unwrap();
+    assert!(disposition.starts_with("attachment; filename=\"tickets-"));
+    assert!(disposition.ends_with(".csv\""));
+
+    let body = resp.text().await.unwrap();
+    let mut reader = csv::Reader::from_reader(body.as_bytes());
+    assert_eq!(
+        reader.headers().unwrap(),
+        vec![
+            "id",
+            "title",
+            "description",
+            "completed",
+            "created_at",
+            "updated_at",
+            "tags"
+        ]
+    );
+
+    let records: Vec<csv::StringRecord> = reader.records().map(|r| r.unwrap()).collect();
+    assert_eq!(records.len(), 3);
+
+    // 最新创建的排在前面
+    assert_eq!(&records[0][1], "Old task");
+    assert_eq!(&records[0][3], "true");
+    assert_eq!(&records[0][6], "");
+
+    // 引号、逗号与换行被正确转义，标签展平为一列
+    let login = &records[2];
+    assert_eq!(&login[0], login_id);
+    assert_eq!(&login[1], "Fix \"login\", then logout");
+    assert_eq!(&login[2], "Line one\nLine two");
+    assert_eq!(&login[3], "false");
+    assert_eq!(&login[6], "bug,urgent");
+}
+
+#[tokio::test]
+async fn test_export_json_with_filters() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    let (bug_id, login_id) = seed(&client).await;
+
+    // 1. 默认格式为 JSON，形状与列表接口一致
+    let resp = client.get("/api/tickets/export").await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["content-type"], "application/json");
+    let exported: Vec<Value> = resp.json().await.unwrap();
+    let listed: Vec<Value> = client.get("/api/tickets").await.json().await.unwrap();
+    assert_eq!(exported, listed);
+
+    // 2. 按标签筛选时仍导出 ticket 的全部标签
+    let resp = client
+        .get(&format!("/api/tickets/export?format=json&tag={}", bug_id))
+        .await;
+    let tickets: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(tickets.len(), 1);
+    assert_eq!(tickets[0]["id"], login_id);
+    assert_eq!(tickets[0]["tags"].as_array().unwrap().len(), 2);
+
+    // 3. 组合筛选
+    let resp = client
+        .get("/api/tickets/export?format=json&completed=false&search=DOCS")
+        .await;
+    let tickets: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(tickets.len(), 1);
+    assert_eq!(tickets[0]["title"], "Write docs");
+
+    // 4. 无匹配时返回空数组
+    let resp = client
+        .get("/api/tickets/export?format=json&search=nothing-matches")
+        .await;
+    let tickets: Vec<Value> = resp.json().await.unwrap();
+    assert!(tickets.is_empty());
+}
+
+#[tokio::test]
+async fn test_export_ndjson() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    seed(&client).await;
+
+    let resp = client
+        .get("/api/tickets/export?format=ndjson&completed=false")
+        .await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["content-type"], "application/x-ndjson");
+
+    let body = resp.text().await.unwrap();
+    let lines: Vec<Value> = body
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines.iter().all(|t| t["completed"] == false));
+
+    // 不支持的格式
+    let resp = client.get("/api/tickets/export?format=xml").await;
+    assert_eq!(resp.status(), 400);
+}
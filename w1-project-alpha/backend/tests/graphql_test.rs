@@ -0,0 +1,321 @@
+//! GraphQL 接口测试：查询、Relay 分页、DataLoader 批量加载、变更与 GraphiQL
+mod common;
+
+use common::{TestClient, TestServer};
+use serde_json::{json, Value};
+
+/// 执行 GraphQL 请求，返回整个响应体
+async fn graphql(client: &TestClient, query: &str, variables: Value) -> Value {
+    let resp = client
+        .post(
+            "/graphql",
+            json!({ "query": query, "variables": variables }),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    resp.json().await.unwrap()
+}
+
+/// 执行 GraphQL 请求，断言没有错误并返回 data
+async fn data(client: &TestClient, query: &str, variables: Value) -> Value {
+    let body = graphql(client, query, variables).await;
+    assert!(body.get("errors").is_none(), "unexpected errors: {}", body);
+    body["data"].clone()
+}
+
+/// 某个仓储操作被调用的次数
+fn query_count(server: &TestServer, repository: &str, operation: &str) -> f64 {
+    let name = format!(
+        r#"db_query_duration_seconds_count{{operation="{}",repository="{}"}}"#,
+        operation, repository
+    );
+    server
+        .state
+        .metrics
+        .render()
+        .unwrap()
+        .lines()
+        .find_map(|line| line.strip_prefix(name.as_str()))
+        .map_or(0.0, |value| value.trim().parse().unwrap())
+}
+
+const CREATE_TICKET: &str = "
+    mutation ($input: CreateTicketInput!) {
+        createTicket(input: $input) { id title completed tags { name } }
+    }";
+
+const CREATE_TAG: &str = "
+    mutation ($name: String!) {
+        createTag(input: { name: $name }) { id name }
+    }";
+
+#[tokio::test]
+async fn test_queries_and_mutations() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let bug = data(&client, CREATE_TAG, json!({ "name": "bug" })).await;
+    let bug_id = bug["createTag"]["id"].as_str().unwrap().to_string();
+
+    // 变更复用服务层：标题被裁剪，返回的标签由 DataLoader 加载
+    let created = data(
+        &client,
+        CREATE_TICKET,
+        json!({ "input": { "title": "  Crash on save ", "tagIds": [bug_id] } }),
+    )
+    .await;
+    let ticket = &created["createTicket"];
+    assert_eq!(ticket["title"], "Crash on save");
+    assert_eq!(ticket["tags"], json!([{ "name": "bug" }]));
+    let id = ticket["id"].as_str().unwrap().to_string();
+
+    let toggled = data(
+        &client,
+        "mutation ($id: UUID!) { toggleTicketCompleted(id: $id) { completed } }",
+        json!({ "id": id }),
+    )
+    .await;
+    assert_eq!(toggled["toggleTicketCompleted"]["completed"], true);
+
+    // 同一个 ticket 也能通过 REST 读到
+    let resp = client.get(&format!("/api/v1/tickets/{}", id)).await;
+    let rest: Value = resp.json().await.unwrap();
+    assert_eq!(rest["completed"], true);
+
+    let found = data(
+        &client,
+        "query ($id: UUID!, $missing: UUID!) {
+            ticket(id: $id) { title tags { name ticketCount } }
+            missing: ticket(id: $missing) { id }
+        }",
+        json!({ "id": id, "missing": uuid::Uuid::new_v4() }),
+    )
+    .await;
+    assert_eq!(found["ticket"]["title"], "Crash on save");
+    assert_eq!(found["ticket"]["tags"][0]["ticketCount"], 1);
+    assert_eq!(found["missing"], Value::Null);
+
+    let removed = data(
+        &client,
+        "mutation ($id: UUID!, $tag: UUID!) {
+            removeTagFromTicket(ticketId: $id, tagId: $tag) { tags { name } }
+        }",
+        json!({ "id": id, "tag": bug_id }),
+    )
+    .await;
+    assert_eq!(removed["removeTagFromTicket"]["tags"], json!([]));
+
+    let deleted = data(
+        &client,
+        "mutation ($id: UUID!) { deleteTicket(id: $id) }",
+        json!({ "id": id }),
+    )
+    .await;
+    assert_eq!(deleted["deleteTicket"], id.as_str());
+}
+
+#[tokio::test]
+async fn test_errors_carry_codes() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let body = graphql(&client, CREATE_TICKET, json!({ "input": { "title": " " } })).await;
+    let error = &body["errors"][0];
+    assert_eq!(error["extensions"]["code"], "BAD_USER_INPUT");
+    assert_eq!(error["message"], "title must not be empty");
+    assert!(error["extensions"]["requestId"].is_string());
+    assert_eq!(body["data"], Value::Null);
+
+    let body = graphql(
+        &client,
+        "mutation ($id: UUID!) { deleteTicket(id: $id) }",
+        json!({ "id": uuid::Uuid::new_v4() }),
+    )
+    .await;
+    assert_eq!(body["errors"][0]["extensions"]["code"], "NOT_FOUND");
+
+    let body = graphql(&client, "{ tickets(first: 101) { totalCount } }", json!({})).await;
+    assert_eq!(body["errors"][0]["extensions"]["code"], "BAD_USER_INPUT");
+
+    let body = graphql(
+        &client,
+        "{ tickets(first: 1, last: 1) { totalCount } }",
+        json!({}),
+    )
+    .await;
+    assert_eq!(body["errors"][0]["extensions"]["code"], "BAD_USER_INPUT");
+}
+
+#[tokio::test]
+async fn test_relay_pagination() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    for i in 1..=5 {
+        data(
+            &client,
+            CREATE_TICKET,
+            json!({ "input": { "title": format!("Ticket {}", i) } }),
+        )
+        .await;
+    }
+
+    let page = |args: &str| {
+        format!(
+            "{{ tickets({}) {{
+                totalCount
+                pageInfo {{ hasPreviousPage hasNextPage startCursor endCursor }}
+                edges {{ cursor node {{ title }} }}
+            }} }}",
+            args
+        )
+    };
+    let titles = |connection: &Value| -> Vec<String> {
+        connection["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|edge| edge["node"]["title"].as_str().unwrap().to_string())
+            .collect()
+    };
+
+    // 向前翻页，新的在前
+    let first = data(&client, &page("first: 2"), json!({})).await["tickets"].clone();
+    assert_eq!(first["totalCount"], 5);
+    assert_eq!(titles(&first), ["Ticket 5", "Ticket 4"]);
+    assert_eq!(first["pageInfo"]["hasPreviousPage"], false);
+    assert_eq!(first["pageInfo"]["hasNextPage"], true);
+
+    let cursor = first["pageInfo"]["endCursor"].as_str().unwrap();
+    let second = data(
+        &client,
+        &page(&format!("first: 2, after: \"{}\"", cursor)),
+        json!({}),
+    )
+    .await["tickets"]
+        .clone();
+    assert_eq!(titles(&second), ["Ticket 3", "Ticket 2"]);
+    assert_eq!(second["pageInfo"]["hasPreviousPage"], true);
+
+    let cursor = second["pageInfo"]["endCursor"].as_str().unwrap();
+    let third = data(
+        &client,
+        &page(&format!("first: 2, after: \"{}\"", cursor)),
+        json!({}),
+    )
+    .await["tickets"]
+        .clone();
+    assert_eq!(titles(&third), ["Ticket 1"]);
+    assert_eq!(third["pageInfo"]["hasNextPage"], false);
+
+    // 从末尾向回翻页
+    let last = data(&client, &page("last: 2"), json!({})).await["tickets"].clone();
+    assert_eq!(titles(&last), ["Ticket 2", "Ticket 1"]);
+    assert_eq!(last["pageInfo"]["hasPreviousPage"], true);
+    assert_eq!(last["pageInfo"]["hasNextPage"], false);
+
+    let cursor = last["pageInfo"]["startCursor"].as_str().unwrap();
+    let before = data(
+        &client,
+        &page(&format!("last: 2, before: \"{}\"", cursor)),
+        json!({}),
+    )
+    .await["tickets"]
+        .clone();
+    assert_eq!(titles(&before), ["Ticket 4", "Ticket 3"]);
+
+    // 过滤条件同样适用
+    let search = data(&client, &page("search: \"ticket 3\""), json!({})).await["tickets"].clone();
+    assert_eq!(search["totalCount"], 1);
+    assert_eq!(titles(&search), ["Ticket 3"]);
+
+    let body = graphql(&client, &page("after: \"not-a-cursor\""), json!({})).await;
+    assert!(body["errors"].is_array());
+}
+
+#[tokio::test]
+async fn test_nested_queries_are_batched() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let mut tag_ids = Vec::new();
+    for name in ["bug", "docs", "ui"] {
+        let tag = data(&client, CREATE_TAG, json!({ "name": name })).await;
+        tag_ids.push(tag["createTag"]["id"].as_str().unwrap().to_string());
+    }
+    for i in 0..4 {
+        data(
+            &client,
+            CREATE_TICKET,
+            json!({ "input": { "title": format!("Ticket {}", i), "tagIds": tag_ids[..=i % 3] } }),
+        )
+        .await;
+    }
+
+    let stats_before = query_count(&server, "ticket", "stats");
+    let tags_before = query_count(&server, "ticket", "find_tags");
+    let pages_before = query_count(&server, "ticket", "find_page");
+
+    let result = data(
+        &client,
+        "{
+            tags { name ticketCount tickets(first: 10) { totalCount edges { node { tags { name } } } } }
+            tickets(first: 10) { edges { node { title tags { name } } } }
+        }",
+        json!({}),
+    )
+    .await;
+
+    let counts: Vec<(&str, i64)> = result["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|tag| {
+            (
+                tag["name"].as_str().unwrap(),
+                tag["ticketCount"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(counts, [("bug", 4), ("docs", 2), ("ui", 1)]);
+    assert_eq!(result["tags"][2]["tickets"]["totalCount"], 1);
+    assert_eq!(
+        result["tags"][2]["tickets"]["edges"][0]["node"]["tags"],
+        json!([{ "name": "bug" }, { "name": "docs" }, { "name": "ui" }])
+    );
+    assert_eq!(result["tickets"]["edges"].as_array().unwrap().len(), 4);
+
+    // 三个标签的计数合并为一次查询；列表中的标签随 ticket 一起读出
+    assert_eq!(query_count(&server, "ticket", "stats") - stats_before, 1.0);
+    assert_eq!(
+        query_count(&server, "ticket", "find_tags") - tags_before,
+        0.0
+    );
+    assert_eq!(
+        query_count(&server, "ticket", "find_page") - pages_before,
+        4.0
+    );
+}
+
+#[tokio::test]
+async fn test_graphiql_is_served() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client.get("/graphql").await;
+    assert_eq!(resp.status(), 200);
+    let html = resp.text().await.unwrap();
+    assert!(html.contains("graphiql"));
+    assert!(html.contains("/graphql"));
+
+    // 过深的查询被拒绝
+    let nested = format!(
+        "{{ tags {{ {} }} }}",
+        "tickets { edges { node { tags { ".repeat(3) + "name" + &" } } } }".repeat(3)
+    );
+    let body = graphql(&client, &nested, json!({})).await;
+    assert!(body["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("too deep"));
+}
@@ -0,0 +1,322 @@
+//! gRPC 接口测试：与 REST 共用端口、错误码、WatchTickets 推送与独立端口
+mod common;
+
+use common::{TestClient, TestServer};
+use futures_util::StreamExt;
+use project_alpha_backend::create_grpc_app;
+use project_alpha_backend::grpc::pb::{
+    self, tag_service_client::TagServiceClient, ticket_event::Event,
+    ticket_service_client::TicketServiceClient,
+};
+use serde_json::{json, Value};
+use tokio::time::{timeout, Duration};
+use tonic::transport::Channel;
+use tonic::{Code, Streaming};
+
+async fn ticket_client(server: &TestServer) -> TicketServiceClient<Channel> {
+    TicketServiceClient::connect(server.base_url.clone())
+        .await
+        .expect("gRPC connect failed")
+}
+
+async fn tag_client(server: &TestServer) -> TagServiceClient<Channel> {
+    TagServiceClient::connect(server.base_url.clone())
+        .await
+        .expect("gRPC connect failed")
+}
+
+async fn create_ticket(
+    client: &mut TicketServiceClient<Channel>,
+    title: &str,
+    tag_ids: Vec<String>,
+) -> pb::Ticket {
+    client
+        .create_ticket(pb::CreateTicketRequest {
+            title: title.to_string(),
+            description: None,
+            tag_ids,
+        })
+        .await
+        .expect("CreateTicket failed")
+        .into_inner()
+}
+
+/// 读取下一个事件（2 秒超时）
+async fn next_event(stream: &mut Streaming<pb::TicketEvent>) -> Event {
+    timeout(Duration::from_secs(2), stream.next())
+        .await
+        .expect("Timed out waiting for event")
+        .expect("Stream ended")
+        .expect("Stream error")
+        .event
+        .expect("Event without payload")
+}
+
+#[tokio::test]
+async fn test_ticket_crud_shares_rest_semantics() {
+    let server = TestServer::start().await;
+    let rest = TestClient::new(server.base_url.clone());
+    let mut tickets = ticket_client(&server).await;
+    let mut tags = tag_client(&server).await;
+
+    let bug = tags
+        .create_tag(pb::CreateTagRequest {
+            name: " bug ".to_string(),
+            color: Some("#ff0000".to_string()),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(bug.name, "bug");
+
+    // 与 REST 相同的校验：标题被裁剪，创建时附加的标签随 ticket 返回
+    let ticket = create_ticket(&mut tickets, "  Crash on save ", vec![bug.id.clone()]).await;
+    assert_eq!(ticket.title, "Crash on save");
+    assert_eq!(ticket.tags.len(), 1);
+    assert_eq!(ticket.tags[0].name, "bug");
+    assert!(ticket.created_at.is_some());
+
+    // 通过 REST 能读到同一个 ticket
+    let resp = rest.get(&format!("/api/v1/tickets/{}", ticket.id)).await;
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["title"], "Crash on save");
+
+    let updated = tickets
+        .update_ticket(pb::UpdateTicketRequest {
+            id: ticket.id.clone(),
+            title: None,
+            description: Some("Steps attached".to_string()),
+            completed: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(updated.title, "Crash on save");
+    assert_eq!(updated.description.as_deref(), Some("Steps attached"));
+
+    let toggled = tickets
+        .toggle_ticket_completed(pb::ToggleTicketCompletedRequest {
+            id: ticket.id.clone(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(toggled.completed);
+
+    create_ticket(&mut tickets, "Second", vec![]).await;
+    create_ticket(&mut tickets, "Third", vec![]).await;
+
+    let page = tickets
+        .list_tickets(pb::ListTicketsRequest {
+            per_page: 2,
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(page.total, 3);
+    assert_eq!(page.total_pages, 2);
+    assert_eq!(page.page, 1);
+    let titles: Vec<&str> = page.tickets.iter().map(|t| t.title.as_str()).collect();
+    assert_eq!(titles, ["Third", "Second"]);
+
+    let filtered = tickets
+        .list_tickets(pb::ListTicketsRequest {
+            tag: Some(bug.id.clone()),
+            completed: Some(true),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(filtered.total, 1);
+    assert_eq!(filtered.per_page, 20);
+    assert_eq!(filtered.tickets[0].id, ticket.id);
+
+    tickets
+        .remove_ticket_tag(pb::TicketTagRequest {
+            ticket_id: ticket.id.clone(),
+            tag_id: bug.id.clone(),
+        })
+        .await
+        .unwrap();
+    let fetched = tickets
+        .get_ticket(pb::GetTicketRequest {
+            id: ticket.id.clone(),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(fetched.tags.is_empty());
+
+    tickets
+        .delete_ticket(pb::DeleteTicketRequest {
+            id: ticket.id.clone(),
+        })
+        .await
+        .unwrap();
+    let resp = rest.get(&format!("/api/v1/tickets/{}", ticket.id)).await;
+    assert_eq!(resp.status(), 404);
+
+    // 删除标签后列表中不再出现
+    tags.delete_tag(pb::DeleteTagRequest { id: bug.id })
+        .await
+        .unwrap();
+    let listed = tags
+        .list_tags(pb::ListTagsRequest {})
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(listed.tags.is_empty());
+}
+
+#[tokio::test]
+async fn test_errors_carry_grpc_codes() {
+    let server = TestServer::start().await;
+    let mut tickets = ticket_client(&server).await;
+    let mut tags = tag_client(&server).await;
+
+    let status = tickets
+        .create_ticket(pb::CreateTicketRequest {
+            title: " ".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.message(), "title must not be empty");
+
+    let status = tickets
+        .get_ticket(pb::GetTicketRequest {
+            id: uuid::Uuid::new_v4().to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::NotFound);
+
+    let status = tickets
+        .get_ticket(pb::GetTicketRequest {
+            id: "42".to_string(),
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let status = tickets
+        .list_tickets(pb::ListTicketsRequest {
+            per_page: 101,
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    tags.create_tag(pb::CreateTagRequest {
+        name: "bug".to_string(),
+        color: None,
+    })
+    .await
+    .unwrap();
+    let status = tags
+        .create_tag(pb::CreateTagRequest {
+            name: "bug".to_string(),
+            color: None,
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+    assert_eq!(status.message(), "Tag with name 'bug' already exists");
+}
+
+#[tokio::test]
+async fn test_watch_tickets_streams_changes() {
+    let server = TestServer::start().await;
+    let rest = TestClient::new(server.base_url.clone());
+    let mut tickets = ticket_client(&server).await;
+
+    let mut all = tickets
+        .watch_tickets(pb::WatchTicketsRequest::default())
+        .await
+        .unwrap()
+        .into_inner();
+
+    // 通过 REST 产生的变更同样会推送
+    let resp = rest
+        .post("/api/v1/tickets", json!({ "title": "Watched" }))
+        .await;
+    let created: Value = resp.json().await.unwrap();
+    let id = created["id"].as_str().unwrap().to_string();
+    let Event::Created(ticket) = next_event(&mut all).await else {
+        panic!("expected a created event");
+    };
+    assert_eq!(ticket.id, id);
+    assert_eq!(ticket.title, "Watched");
+
+    let mut one = tickets
+        .watch_tickets(pb::WatchTicketsRequest {
+            ticket_id: Some(id.clone()),
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    // 只关注单个 ticket 的订阅收不到其他 ticket 的变更
+    let other = create_ticket(&mut tickets, "Other", vec![]).await;
+    tickets
+        .toggle_ticket_completed(pb::ToggleTicketCompletedRequest { id: id.clone() })
+        .await
+        .unwrap();
+    let Event::Updated(ticket) = next_event(&mut one).await else {
+        panic!("expected an updated event");
+    };
+    assert!(ticket.completed);
+
+    assert!(matches!(next_event(&mut all).await, Event::Created(t) if t.id == other.id));
+    assert!(matches!(next_event(&mut all).await, Event::Updated(t) if t.id == id));
+
+    tickets
+        .delete_ticket(pb::DeleteTicketRequest { id: id.clone() })
+        .await
+        .unwrap();
+    assert_eq!(next_event(&mut one).await, Event::Deleted(id));
+
+    // 服务器关闭时流随之结束
+    server.state.events.close();
+    let end = timeout(Duration::from_secs(2), one.next())
+        .await
+        .expect("Timed out waiting for the stream to end");
+    assert!(end.is_none(), "{:?}", end);
+}
+
+#[tokio::test]
+async fn test_grpc_on_its_own_port() {
+    let server = TestServer::start().await;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let app = create_grpc_app(server.state.clone());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+    let mut tags = TagServiceClient::connect(format!("http://{}", addr))
+        .await
+        .unwrap();
+    let tag = tags
+        .create_tag(pb::CreateTagRequest {
+            name: "ops".to_string(),
+            color: None,
+        })
+        .await
+        .unwrap()
+        .into_inner();
+
+    // 与 HTTP 端口上的服务共享同一份数据
+    let rest = TestClient::new(server.base_url.clone());
+    let resp = rest.get(&format!("/api/v1/tags/{}", tag.id)).await;
+    assert_eq!(resp.status(), 200);
+
+    // 独立端口上只有 gRPC 服务
+    let resp = reqwest::get(format!("http://{}/api/v1/tags", addr))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+}
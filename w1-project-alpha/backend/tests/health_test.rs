@@ -0,0 +1,100 @@
+mod common;
+
+use common::{TestClient, TestServer};
+use serde_json::Value;
+
+#[tokio::test]
+async fn test_healthz() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client.get("/healthz").await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["status"], "ok");
+}
+
+#[tokio::test]
+async fn test_readyz_reports_database_migrations_and_pool() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client.get("/readyz").await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["database"]["ok"], true);
+    assert!(body["database"]["latency_ms"].is_u64());
+    assert_eq!(body["migrations"]["ok"], true);
+    assert_eq!(body["migrations"]["pending"], Value::Array(vec![]));
+
+    let pool = &body["pool"];
+    assert!(pool["max"].as_u64().unwrap() >= 1);
+    assert!(pool["size"].as_u64().unwrap() >= pool["idle"].as_u64().unwrap());
+    assert!(pool["saturation"].is_f64());
+    assert_eq!(pool["saturated"], false);
+}
+
+#[tokio::test]
+async fn test_readyz_fails_when_database_is_unavailable() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    // 关闭连接池模拟数据库不可用：就绪探针失败，存活探针不受影响
+    server.state.database.close().await;
+
+    let resp = client.get("/readyz").await;
+    assert_eq!(resp.status(), 503);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["status"], "not_ready");
+    assert_eq!(body["database"]["ok"], false);
+    assert!(body["database"]["error"].is_string());
+    assert_eq!(body["migrations"]["ok"], false);
+
+    let resp = client.get("/healthz").await;
+    assert_eq!(resp.status(), 200);
+}
+
+#[tokio::test]
+async fn test_version_lists_build_info_and_migrations() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client.get("/version").await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+    assert!(!body["git_sha"].as_str().unwrap().is_empty());
+
+    let migrations = body["migrations"].as_array().unwrap();
+    let versions: Vec<i64> = migrations
+        .iter()
+        .map(|m| m["version"].as_i64().unwrap())
+        .collect();
+    assert_eq!(versions, [20231201000001, 20231201000002, 20231201000003]);
+    assert_eq!(migrations[0]["description"], "initial schema");
+    assert!(migrations[0]["installed_on"].is_string());
+}
+
+#[tokio::test]
+async fn test_probes_skip_api_middleware() {
+    let server = TestServer::start().await;
+
+    // 探针路由不经过 API 中间件栈，因此不会带 CORS 响应头
+    let resp = reqwest::Client::new()
+        .get(server.url("/healthz"))
+        .header("origin", "http://localhost:5173")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers().get("access-control-allow-origin").is_none());
+
+    let resp = reqwest::Client::new()
+        .get(server.url("/api/tags"))
+        .header("origin", "http://localhost:5173")
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.headers().get("access-control-allow-origin").is_some());
+}
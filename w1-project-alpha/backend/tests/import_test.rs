@@ -0,0 +1,209 @@
+mod common;
+
+use common::{TestClient, TestServer};
+use serde_json::{json, Value};
+
+const CSV_BODY: &str = "\
+title,description,completed,tags,created_at
+Migrate users,\"From the old, legacy tracker\",true,\"bug, legacy\",2024-01-15T08:30:00Z
+,Missing title,false,,
+Bad flag,,maybe,,
+Review backlog,,,legacy,
+";
+
+#[tokio::test]
+async fn test_import_csv_dry_run_then_commit() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    client
+        .post("/api/tags", json!({ "name": "bug", "color": "#FF0000" }))
+        .await;
+
+    // 1. dry run 只报告结果，不写入任何数据
+    let resp = client
+        .post_raw("/api/tickets/import?dry_run=true", "text/csv", CSV_BODY)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let report: Value = resp.json().await.unwrap();
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["total"], 4);
+    assert_eq!(report["imported"], 2);
+    assert_eq!(report["failed"], 2);
+    assert_eq!(report["created_tags"], json!(["legacy"]));
+    assert_eq!(report["ticket_ids"], json!([]));
+    assert_eq!(report["errors"][0]["row"], 2);
+    assert_eq!(
+        report["errors"][0]["errors"],
+        json!(["title must not be empty"])
+    );
+    assert_eq!(report["errors"][1]["row"], 3);
+
+    let tickets: Vec<Value> = client.get("/api/tickets").await.json().await.unwrap();
+    assert!(tickets.is_empty());
+    let tags: Vec<Value> = client.get("/api/tags").await.json().await.unwrap();
+    assert_eq!(tags.len(), 1);
+
+    // 2. 正式导入：有效行写入，缺失的标签自动创建
+    let resp = client
+        .post_raw("/api/tickets/import", "text/csv; charset=utf-8", CSV_BODY)
+        .await;
+    assert_eq!(resp.status(), 200);
+    let report: Value = resp.json().await.unwrap();
+    assert_eq!(report["dry_run"], false);
+    assert_eq!(report["imported"], 2);
+    assert_eq!(report["created_tags"], json!(["legacy"]));
+    let ids = report["ticket_ids"].as_array().unwrap();
+    assert_eq!(ids.len(), 2);
+
+    let resp = client
+        .get(&format!("/api/tickets/{}", ids[0].as_str().unwrap()))
+        .await;
+    let ticket: Value = resp.json().await.unwrap();
+    assert_eq!(ticket["title"], "Migrate users");
+    assert_eq!(ticket["description"], "From the old, legacy tracker");
+    assert_eq!(ticket["completed"], true);
+    assert_eq!(ticket["created_at"], "2024-01-15T08:30:00Z");
+    let tag_names: Vec<&str> = ticket["tags"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|t| t["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(tag_names, vec!["bug", "legacy"]);
+
+    let tags: Vec<Value> = client.get("/api/tags").await.json().await.unwrap();
+    assert_eq!(tags.len(), 2);
+
+    // 3. 再次导入时复用已存在的标签
+    let resp = client
+        .post_raw("/api/tickets/import", "text/csv", CSV_BODY)
+        .await;
+    let report: Value = resp.json().await.unwrap();
+    assert_eq!(report["created_tags"], json!([]));
+    let tickets: Vec<Value> = client.get("/api/tickets").await.json().await.unwrap();
+    assert_eq!(tickets.len(), 4);
+}
+
+#[tokio::test]
+async fn test_import_json() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let body = json!([
+        { "title": "From JSON", "tags": ["ops", "ops", "infra"] },
+        { "title": "Joined tags", "description": "d", "completed": true, "tags": "infra, ui" },
+        { "title": 42 },
+        { "title": "Bad tag", "tags": [""] },
+        { "title": "Backwards", "created_at": "2024-02-01T00:00:00Z", "updated_at": "2024-01-01T00:00:00Z" }
+    ]);
+
+    let resp = client.post("/api/tickets/import", body).await;
+    assert_eq!(resp.status(), 200);
+    let report: Value = resp.json().await.unwrap();
+    assert_eq!(report["total"], 5);
+    assert_eq!(report["imported"], 2);
+    assert_eq!(report["created_tags"], json!(["ops", "infra", "ui"]));
+    let rows: Vec<u64> = report["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["row"].as_u64().unwrap())
+        .collect();
+    assert_eq!(rows, vec![3, 4, 5]);
+
+    let ids = report["ticket_ids"].as_array().unwrap();
+    let resp = client
+        .get(&format!("/api/tickets/{}", ids[0].as_str().unwrap()))
+        .await;
+    let ticket: Value = resp.json().await.unwrap();
+    assert_eq!(ticket["tags"].as_array().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_import_round_trips_export() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    // 标签名可以包含分隔符与引号，导出时按 CSV 规则转义，重新导入后不被拆开
+    let mut tag_ids = Vec::new();
+    for name in ["bug", "ui, mobile", "say \"hi\""] {
+        let resp = client
+            .post("/api/tags", json!({ "name": name, "color": null }))
+            .await;
+        let tag: Value = resp.json().await.unwrap();
+        tag_ids.push(tag["id"].clone());
+    }
+    client
+        .post(
+            "/api/tickets",
+            json!({ "title": "Exported, \"quoted\"", "description": "multi\nline", "tag_ids": tag_ids }),
+        )
+        .await;
+
+    let csv = client
+        .get("/api/tickets/export?format=csv")
+        .await
+        .text()
+        .await
+        .unwrap();
+
+    let resp = client
+        .post_raw("/api/tickets/import", "text/csv", csv)
+        .await;
+    let report: Value = resp.json().await.unwrap();
+    assert_eq!(report["imported"], 1);
+    assert_eq!(report["failed"], 0);
+
+    let tickets: Vec<Value> = client.get("/api/tickets").await.json().await.unwrap();
+    assert_eq!(tickets.len(), 2);
+    assert_eq!(tickets[0]["title"], tickets[1]["title"]);
+    assert_eq!(tickets[0]["description"], "multi\nline");
+    assert_eq!(tickets[0]["tags"], tickets[1]["tags"]);
+    assert_eq!(tickets[0]["tags"].as_array().unwrap().len(), 3);
+
+    let tags: Vec<Value> = client.get("/api/tags").await.json().await.unwrap();
+    assert_eq!(tags.len(), 3);
+
+    // 手写的 "a, b" 形式仍按分隔符拆分
+    let resp = client
+        .post_raw(
+            "/api/tickets/import",
+            "text/csv",
+            "title,tags\nHand written,\"bug, ui, mobile\"\n",
+        )
+        .await;
+    let report: Value = resp.json().await.unwrap();
+    assert_eq!(report["created_tags"], json!(["ui", "mobile"]));
+}
+
+#[tokio::test]
+async fn test_import_rejects_unreadable_bodies() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client
+        .post_raw("/api/tickets/import", "text/plain", "title\nA")
+        .await;
+    assert_eq!(resp.status(), 400);
+
+    // 通过 format 参数覆盖 Content-Type
+    let resp = client
+        .post_raw("/api/tickets/import?format=csv", "text/plain", "title\nA")
+        .await;
+    assert_eq!(resp.status(), 200);
+
+    let resp = client
+        .post_raw(
+            "/api/tickets/import",
+            "application/json",
+            "{\"title\": \"A\"}",
+        )
+        .await;
+    assert_eq!(resp.status(), 400);
+
+    let resp = client
+        .post_raw("/api/tickets/import", "text/csv", "name,color\nA,#fff")
+        .await;
+    assert_eq!(resp.status(), 400);
+}
@@ -0,0 +1,96 @@
+mod common;
+
+use common::{TestClient, TestServer};
+use serde_json::json;
+
+/// 取出某个指标样本的值（`name` 需包含完整的标签部分）
+fn sample(text: &str, name: &str) -> Option<f64> {
+    text.lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(' ')?.parse().ok())
+}
+
+#[tokio::test]
+async fn test_metrics_cover_http_pool_queries_and_tickets() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client
+        .post("/api/tags", json!({ "name": "bug", "color": null }))
+        .await;
+    let tag: serde_json::Value = resp.json().await.unwrap();
+    let tag_id = tag["id"].as_str().unwrap();
+    client
+        .post(
+            "/api/tickets",
+            json!({ "title": "Open", "tag_ids": [tag_id] }),
+        )
+        .await;
+    let resp = client
+        .post("/api/tickets", json!({ "title": "Done" }))
+        .await;
+    let done: serde_json::Value = resp.json().await.unwrap();
+    client
+        .patch(&format!(
+            "/api/tickets/{}/toggle",
+            done["id"].as_str().unwrap()
+        ))
+        .await;
+    client
+        .get(&format!("/api/tickets/{}", uuid::Uuid::new_v4()))
+        .await;
+    client.get("/no/such/route").await;
+
+    let resp = client.get("/metrics").await;
+    assert_eq!(resp.status(), 200);
+    assert!(resp.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/plain; version=0.0.4"));
+    let text = resp.text().await.unwrap();
+
+    // HTTP 请求按路由模板而非具体路径计数
+    assert_eq!(
+        sample(
+            &text,
+            r#"http_requests_total{method="POST",route="/api/tickets",status="201"}"#
+        ),
+        Some(2.0)
+    );
+    assert_eq!(
+        sample(
+            &text,
+            r#"http_requests_total{method="GET",route="/api/tickets/{id}",status="404"}"#
+        ),
+        Some(1.0)
+    );
+    assert_eq!(
+        sample(
+            &text,
+            r#"http_requests_total{method="GET",route="unmatched",status="404"}"#
+        ),
+        Some(1.0)
+    );
+    assert!(text.contains(
+        r#"http_request_duration_seconds_bucket{method="PATCH",route="/api/tickets/{id}/toggle",status="200",le="#
+    ));
+    // 探针与抓取本身不计入
+    assert!(!text.contains(r#"route="/metrics""#));
+
+    // 连接池与仓库耗时
+    assert!(sample(&text, "db_pool_max_connections").unwrap() >= 1.0);
+    assert!(sample(&text, "db_pool_size").is_some());
+    assert!(sample(&text, "db_pool_idle").is_some());
+    assert!(sample(&text, "db_pool_waiters").is_some());
+    assert_eq!(
+        sample(
+            &text,
+            r#"db_query_duration_seconds_count{operation="create",repository="ticket"}"#
+        ),
+        Some(2.0)
+    );
+
+    // 业务指标
+    assert_eq!(sample(&text, r#"tickets{status="open"}"#), Some(1.0));
+    assert_eq!(sample(&text, r#"tickets{status="completed"}"#), Some(1.0));
+    assert_eq!(sample(&text, r#"tag_tickets{tag="bug"}"#), Some(1.0));
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use common::{TestClient, TestServer};
+use project_alpha_backend::graphql::GRAPHQL_PATH;
+use project_alpha_backend::{openapi, routes};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+const METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+/// 已注册但不属于 REST API、不写入 OpenAPI 文档的路由
+const UNDOCUMENTED: [(&str, &str); 2] = [("get", GRAPHQL_PATH), ("post", GRAPHQL_PATH)];
+
+/// create_routes 挂载的所有 (方法, 路径)，与路由器取自同一张路由表
+fn registered_routes() -> BTreeSet<(String, String)> {
+    routes::registered_routes()
+        .into_iter()
+        .map(|(method, path)| (method.as_str().to_lowercase(), path))
+        .collect()
+}
+
+fn documented_routes(spec: &Value) -> BTreeSet<(String, String)> {
+    spec["paths"]
+        .as_object()
+        .unwrap()
+        .iter()
+        .flat_map(|(path, item)| {
+            METHODS
+                .iter()
+                .filter(|method| item.get(**method).is_some())
+                .map(move |method| (method.to_string(), path.clone()))
+        })
+        .collect()
+}
+
+#[test]
+fn test_every_route_is_documented() {
+    let mut routes = registered_routes();
+    assert!(routes.contains(&("get".to_string(), "/api/v1/tickets".to_string())));
+    assert!(routes.contains(&("get".to_string(), "/api/v2/tags".to_string())));
+    assert!(routes.contains(&("delete".to_string(), "/api/v2/tickets/{id}".to_string())));
+
+    for (method, path) in UNDOCUMENTED {
+        assert!(
+            routes.remove(&(method.to_string(), path.to_string())),
+            "excluded route {} {} is not registered",
+            method,
+            path
+        );
+    }
+
+    let spec = serde_json::to_value(openapi::spec()).unwrap();
+    let documented = documented_routes(&spec);
+
+    let missing: Vec<_> = routes.difference(&documented).collect();
+    assert!(
+        missing.is_empty(),
+        "routes missing from the spec: {:?}",
+        missing
+    );
+    let stale: Vec<_> = documented.difference(&routes).collect();
+    assert!(
+        stale.is_empty(),
+        "documented routes that do not exist: {:?}",
+        stale
+    );
+}
+
+#[test]
+fn test_schemas_come_from_the_models() {
+    let spec = serde_json::to_value(openapi::spec()).unwrap();
+    let schemas = &spec["components"]["schemas"];
+
+    for name in [
+        "Ticket",
+        "TicketWithTags",
+        "Tag",
+        "Webhook",
+        "ImportReport",
+        "ErrorResponse",
+    ] {
+        assert!(schemas.get(name).is_some(), "schema {} missing", name);
+    }
+    // secret 只写不读，不应出现在响应模型中
+    assert!(schemas["Webhook"]["properties"].get("secret").is_none());
+    assert!(schemas["CreateWebhookRequest"]["properties"]
+        .get("secret")
+        .is_some());
+
+    let params = |path: &str| -> Vec<String> {
+        spec["paths"][path]["get"]["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap().to_string())
+            .collect()
+    };
+    assert_eq!(params("/api/v1/tickets"), ["tag", "search", "completed"]);
+    assert_eq!(
+        params("/api/v2/tickets"),
+        ["tag", "search", "completed", "page", "per_page"]
+    );
+    assert!(schemas.get("TicketV2").is_some());
+
+    // 各版本的 operationId 带版本前缀，且互不重复
+    let ids: Vec<_> = spec["paths"]
+        .as_object()
+        .unwrap()
+        .values()
+        .flat_map(|item| METHODS.iter().filter_map(move |m| item.get(*m)))
+        .map(|op| op["operationId"].as_str().unwrap())
+        .collect();
+    assert!(ids.contains(&"v1_get_tickets") && ids.contains(&"v2_get_tickets"));
+    assert_eq!(ids.len(), ids.iter().collect::<BTreeSet<_>>().len());
+}
+
+#[tokio::test]
+async fn test_spec_and_ui_are_served() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client.get("/api/openapi.json").await;
+    assert_eq!(resp.status(), 200);
+    let spec: Value = resp.json().await.unwrap();
+    assert!(spec["openapi"].as_str().unwrap().starts_with("3."));
+    assert_eq!(spec["info"]["title"], "Project Alpha API");
+    assert_eq!(
+        spec,
+        serde_json::to_value(openapi::spec()).unwrap(),
+        "served spec differs from the generated one"
+    );
+
+    let resp = client.get("/api/docs/").await;
+    assert_eq!(resp.status(), 200);
+    let html = resp.text().await.unwrap();
+    assert!(html.contains("swagger-ui"));
+
+    // UI 使用的配置指向 JSON 文档
+    let resp = client.get("/api/docs/swagger-initializer.js").await;
+    assert_eq!(resp.status(), 200);
+    assert!(resp.text().await.unwrap().contains("/api/openapi.json"));
+}
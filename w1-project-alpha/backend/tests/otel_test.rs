@@ -0,0 +1,202 @@
+//! OTLP 导出测试：用本地桩充当 collector，检查导出的 span 与 traceparent 传播
+#![cfg(feature = "otel")]
+
+mod common;
+
+use axum::{body::Bytes, extract::State, http::HeaderMap, routing::post, Json, Router};
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const PARENT_SPAN_ID: &str = "00f067aa0ba902b7";
+
+/// collector 桩收到的 span 与 webhook 桩收到的请求头
+#[derive(Clone, Default)]
+struct Collector {
+    spans: Arc<Mutex<Vec<Value>>>,
+    hooks: Arc<Mutex<Vec<HeaderMap>>>,
+}
+
+impl Collector {
+    async fn start() -> (Self, String) {
+        let collector = Collector::default();
+        let app = Router::new()
+            .route("/v1/traces", post(receive_traces))
+            .route("/hook", post(receive_hook))
+            .with_state(collector.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        (collector, url)
+    }
+
+    /// 等待满足条件的 span 出现
+    async fn wait_for_span(&self, what: &str, matches: impl Fn(&Value) -> bool) -> Value {
+        let deadline = Instant::now() + Duration::from_secs(15);
+        loop {
+            if let Some(span) = self.spans.lock().unwrap().iter().find(|s| matches(s)) {
+                return span.clone();
+            }
+            assert!(Instant::now() < deadline, "no {} span exported", what);
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+}
+
+/// OTLP/HTTP JSON：resourceSpans[].scopeSpans[].spans[]
+async fn receive_traces(State(collector): State<Collector>, body: Bytes) -> Json<Value> {
+    let request: Value = serde_json::from_slice(&body).expect("OTLP body is JSON");
+    let mut spans = collector.spans.lock().unwrap();
+    for resource in request["resourceSpans"].as_array().into_iter().flatten() {
+        for scope in resource["scopeSpans"].as_array().into_iter().flatten() {
+            spans.extend(scope["spans"].as_array().into_iter().flatten().cloned());
+        }
+    }
+    Json(json!({}))
+}
+
+async fn receive_hook(State(collector): State<Collector>, headers: HeaderMap) {
+    collector.hooks.lock().unwrap().push(headers);
+}
+
+fn attribute<'a>(span: &'a Value, key: &str) -> Option<&'a Value> {
+    span["attributes"]
+        .as_array()?
+        .iter()
+        .find(|a| a["key"] == key)
+        .map(|a| &a["value"])
+}
+
+/// 运行中的服务端进程，测试结束时结束进程
+struct Server(std::process::Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.0.kill().ok();
+        self.0.wait().ok();
+    }
+}
+
+#[tokio::test]
+async fn test_spans_are_exported_and_propagated() {
+    // 先于 server 声明，server 退出后才删除数据库
+    let database = common::TestDatabase::create().await;
+    let (collector, collector_url) = Collector::start().await;
+
+    // 导出与日志级别无关：即使 RUST_LOG=error 也应导出 span
+    let mut server = Server(
+        Command::new(env!("CARGO_BIN_EXE_project-alpha-backend"))
+            .current_dir(std::env::temp_dir())
+            .env_clear()
+            .env("APP_DATABASE_URL", &database.url)
+            .env("APP_SERVER_PORT", "0")
+            .env("APP_OTEL_ENDPOINT", &collector_url)
+            .env("APP_OTEL_SERVICE_NAME", "alpha-otel-test")
+            .env("OTEL_BSP_SCHEDULE_DELAY", "100")
+            .env("RUST_LOG", "error,project_alpha_backend=info")
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Failed to start server binary"),
+    );
+
+    let stdout = server.0.stdout.take().unwrap();
+    let (tx, lines) = mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(|line| line.ok()) {
+            if tx.send(line).is_err() {
+                break;
+            }
+        }
+    });
+    let addr = loop {
+        let line = lines
+            .recv_timeout(Duration::from_secs(10))
+            .expect("server did not start within 10s");
+        if let Some((_, addr)) = line.split_once("Listening on ") {
+            break addr.trim().to_string();
+        }
+    };
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .post(format!("http://{}/api/webhooks", addr))
+        .json(&json!({ "url": format!("{}/hook", collector_url), "secret": "s3cret" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+
+    // 1. 带 traceparent 的请求加入调用方的 trace
+    let resp = client
+        .post(format!("http://{}/api/tickets", addr))
+        .header(
+            "traceparent",
+            format!("00-{}-{}-01", TRACE_ID, PARENT_SPAN_ID),
+        )
+        .json(&json!({ "title": "Traced ticket" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 201);
+
+    let request = collector
+        .wait_for_span("request", |s| s["name"] == "POST /api/tickets")
+        .await;
+    assert_eq!(request["traceId"], TRACE_ID);
+    assert_eq!(request["parentSpanId"], PARENT_SPAN_ID);
+    assert_eq!(
+        attribute(&request, "http.route"),
+        Some(&json!({ "stringValue": "/api/tickets" }))
+    );
+    assert_eq!(
+        attribute(&request, "http.response.status_code"),
+        Some(&json!({ "intValue": "201" }))
+    );
+
+    // 2. 仓储调用是请求 span 的子 span
+    let query = collector
+        .wait_for_span("db.query", |s| s["name"] == "ticket.create")
+        .await;
+    assert_eq!(query["traceId"], TRACE_ID);
+    assert_eq!(query["parentSpanId"], request["spanId"]);
+
+    // 3. webhook 投递延续同一条 trace，并把 traceparent 传给接收方
+    let delivery = collector
+        .wait_for_span("webhook.deliver", |s| s["name"] == "webhook.deliver")
+        .await;
+    assert_eq!(delivery["traceId"], TRACE_ID);
+
+    let hooks = collector.hooks.lock().unwrap().clone();
+    assert_eq!(hooks.len(), 1);
+    let traceparent = hooks[0]["traceparent"].to_str().unwrap();
+    assert_eq!(
+        traceparent,
+        format!(
+            "00-{}-{}-01",
+            TRACE_ID,
+            delivery["spanId"].as_str().unwrap()
+        )
+    );
+
+    // 探针不进入 trace
+    client
+        .get(format!("http://{}/healthz", addr))
+        .send()
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert!(!collector
+        .spans
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|s| s["name"].as_str().unwrap_or_default().contains("healthz")));
+}
@@ -0,0 +1,145 @@
+mod common;
+
+use common::TestServer;
+use serde_json::Value;
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_request_id_is_generated_and_propagated() {
+    let server = TestServer::start().await;
+    let client = reqwest::Client::new();
+
+    // 未提供时由服务端生成
+    let resp = client.get(server.url("/api/tags")).send().await.unwrap();
+    assert_eq!(resp.status(), 200);
+    let generated = resp.headers()["x-request-id"].to_str().unwrap();
+    assert!(uuid::Uuid::parse_str(generated).is_ok());
+
+    // 上游提供的 ID 原样透传
+    let resp = client
+        .get(server.url("/api/tags"))
+        .header("x-request-id", "gateway-7f3a")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["x-request-id"], "gateway-7f3a");
+
+    // 不合法的 ID 被替换
+    let resp = client
+        .get(server.url("/api/tags"))
+        .header("x-request-id", "a".repeat(300))
+        .send()
+        .await
+        .unwrap();
+    let replaced = resp.headers()["x-request-id"].to_str().unwrap();
+    assert!(uuid::Uuid::parse_str(replaced).is_ok());
+}
+
+#[tokio::test]
+async fn test_error_body_carries_request_id() {
+    let server = TestServer::start().await;
+    let client = reqwest::Client::new();
+
+    let resp = client
+        .get(server.url(&format!("/api/tickets/{}", uuid::Uuid::new_v4())))
+        .header("x-request-id", "trace-me")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+    assert_eq!(resp.headers()["x-request-id"], "trace-me");
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["request_id"], "trace-me");
+    assert_eq!(body["status"], 404);
+
+    let resp = client
+        .post(server.url("/api/tags"))
+        .json(&serde_json::json!({ "name": "" }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+    let id = resp.headers()["x-request-id"].to_str().unwrap().to_string();
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["request_id"], id);
+}
+
+/// 运行中的服务端进程，测试结束时结束进程
+struct Server(std::process::Child);
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        self.0.kill().ok();
+        self.0.wait().ok();
+    }
+}
+
+#[tokio::test]
+async fn test_json_logs_carry_request_id() {
+    // 先于 server 声明，server 退出后才删除数据库
+    let database = common::TestDatabase::create().await;
+
+    let mut server = Server(
+        Command::new(env!("CARGO_BIN_EXE_project-alpha-backend"))
+            .current_dir(std::env::temp_dir())
+            .env_clear()
+            .env("APP_DATABASE_URL", &database.url)
+            .env("APP_SERVER_PORT", "0")
+            .env("LOG_FORMAT", "json")
+            .env("RUST_LOG", "project_alpha_backend=info,tower_http=debug")
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("Failed to start server binary"),
+    );
+
+    // 在后台线程逐行读取日志，每行都必须是 JSON
+    let stdout = server.0.stdout.take().unwrap();
+    let (lines_tx, lines) = mpsc::channel::<Value>();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines() {
+            let line = line.unwrap();
+            let event: Value = serde_json::from_str(&line)
+                .unwrap_or_else(|e| panic!("not a JSON log line ({}): {}", e, line));
+            if lines_tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+    let next_event = || {
+        lines
+            .recv_timeout(Duration::from_secs(10))
+            .expect("no log line within 10s")
+    };
+
+    let addr = loop {
+        let event = next_event();
+        if let Some(addr) = event["message"]
+            .as_str()
+            .and_then(|m| m.strip_prefix("Listening on "))
+        {
+            break addr.to_string();
+        }
+    };
+
+    let resp = reqwest::Client::new()
+        .get(format!("http://{}/api/tickets/{}", addr, uuid::Uuid::nil()))
+        .header("x-request-id", "json-log-test")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 404);
+
+    // 请求 span 的字段（包括 request_id）出现在该请求期间的每条日志中
+    let event = loop {
+        let event = next_event();
+        if event["span"]["request_id"] == "json-log-test" {
+            break event;
+        }
+    };
+    assert_eq!(event["span"]["name"], "request");
+    assert_eq!(event["span"]["method"], "GET");
+    assert!(event["level"].is_string());
+}
@@ -0,0 +1,108 @@
+//! 优雅停机测试：进行中的请求在排空时间内完成，超时后被丢弃
+mod common;
+
+use axum::{routing::get, Router};
+use project_alpha_backend::shutdown::{self, Shutdown};
+use project_alpha_backend::Database;
+use sqlx::PgPool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+
+struct SlowServer {
+    base_url: String,
+    shutdown: Shutdown,
+    /// 慢接口开始处理请求时发出通知
+    started: Arc<Notify>,
+    handle: JoinHandle<std::io::Result<()>>,
+}
+
+impl SlowServer {
+    /// 慢接口在处理期间占用一个数据库连接
+    async fn start(delay: Duration, drain_timeout: Duration, pool: PgPool) -> Self {
+        let started = Arc::new(Notify::new());
+        let app = Router::new().route(
+            "/slow",
+            get({
+                let started = started.clone();
+                move || async move {
+                    let _connection = pool.acquire().await.unwrap();
+                    started.notify_one();
+                    tokio::time::sleep(delay).await;
+                    "done"
+                }
+            }),
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let shutdown = Shutdown::new(drain_timeout);
+        let handle = tokio::spawn(shutdown::serve(listener, app, shutdown.clone()));
+
+        Self {
+            base_url,
+            shutdown,
+            started,
+            handle,
+        }
+    }
+
+    /// 发起一个慢请求，并等到服务端开始处理它
+    async fn in_flight_request(&self) -> JoinHandle<reqwest::Result<reqwest::Response>> {
+        let request = tokio::spawn(reqwest::get(format!("{}/slow", self.base_url)));
+        self.started.notified().await;
+        request
+    }
+}
+
+#[tokio::test]
+async fn test_in_flight_requests_finish_before_exit() {
+    let database = common::TestDatabase::create().await;
+    let server = SlowServer::start(
+        Duration::from_millis(300),
+        Duration::from_secs(5),
+        database.pool().await,
+    )
+    .await;
+
+    let request = server.in_flight_request().await;
+    server.shutdown.start();
+
+    // 请求在停机信号之后仍然正常完成
+    let response = request.await.unwrap().unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "done");
+
+    tokio::time::timeout(Duration::from_secs(2), server.handle)
+        .await
+        .expect("Server did not stop")
+        .unwrap()
+        .unwrap();
+
+    // 停机后不再接受新连接
+    assert!(reqwest::get(format!("{}/slow", server.base_url))
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_drain_timeout_stops_waiting_for_stuck_requests() {
+    let database = common::TestDatabase::create().await;
+    let pool = database.pool().await;
+    let drain_timeout = Duration::from_millis(500);
+    let server = SlowServer::start(Duration::from_secs(30), drain_timeout, pool.clone()).await;
+
+    let _request = server.in_flight_request().await;
+
+    // 与 main 相同的停机顺序：停止服务，再关闭连接池。
+    // 卡住的请求仍占用连接；两步共用一个截止时间，合计不超过一个排空时间
+    let started = Instant::now();
+    server.shutdown.start();
+    server.handle.await.unwrap().unwrap();
+    shutdown::close_database(&Database::Postgres(pool), &server.shutdown).await;
+    let elapsed = started.elapsed();
+    assert!(elapsed >= drain_timeout, "{:?}", elapsed);
+    assert!(elapsed < drain_timeout * 2, "{:?}", elapsed);
+}
@@ -0,0 +1,196 @@
+//! SQLite 后端的 HTTP 行为测试（需要 `--features sqlite`）
+#![cfg(feature = "sqlite")]
+
+mod common;
+
+use axum::{body::Bytes, extract::State, http::StatusCode, routing::post, Router};
+use common::{TestClient, TestServer};
+use project_alpha_backend::webhooks::{DispatcherConfig, WebhookDispatcher};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+
+#[tokio::test]
+async fn test_sqlite_ticket_flow() {
+    let server = TestServer::start_sqlite().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    // 1. 标签：创建、重名校验
+    let resp = client
+        .post("/api/tags", json!({ "name": "bug", "color": "#FF0000" }))
+        .await;
+    assert_eq!(resp.status(), 201);
+    let bug: Value = resp.json().await.unwrap();
+    let bug_id = bug["id"].as_str().unwrap().to_string();
+
+    let resp = client.post("/api/tags", json!({ "name": "bug" })).await;
+    assert_eq!(resp.status(), 400);
+
+    // 2. 创建带标签的 ticket
+    let resp = client
+        .post(
+            "/api/tickets",
+            json!({ "title": "Fix login page", "tag_ids": [bug_id] }),
+        )
+        .await;
+    assert_eq!(resp.status(), 201);
+    let ticket: Value = resp.json().await.unwrap();
+    let ticket_id = ticket["id"].as_str().unwrap().to_string();
+
+    client
+        .post("/api/tickets", json!({ "title": "Write docs" }))
+        .await;
+
+    let resp = client.get(&format!("/api/tickets/{}", ticket_id)).await;
+    assert_eq!(resp.status(), 200);
+    let found: Value = resp.json().await.unwrap();
+    assert_eq!(found["tags"][0]["name"], "bug");
+    assert_eq!(found["tags"][0]["id"], bug["id"]);
+
+    // 3. 搜索（FTS5 与短词回退）和筛选
+    for search in ["LOGIN", "lo"] {
+        let resp = client.get(&format!("/api/tickets?search={}", search)).await;
+        let tickets: Vec<Value> = resp.json().await.unwrap();
+        assert_eq!(tickets.len(), 1, "search={}", search);
+        assert_eq!(tickets[0]["title"], "Fix login page");
+    }
+
+    let resp = client.get(&format!("/api/tickets?tag={}", bug_id)).await;
+    let tickets: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(tickets.len(), 1);
+
+    // 4. 修改标题后搜索索引同步更新
+    let resp = client
+        .put(
+            &format!("/api/tickets/{}", ticket_id),
+            json!({ "title": "Fix signup page" }),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    let resp = client.get("/api/tickets?search=login").await;
+    let tickets: Vec<Value> = resp.json().await.unwrap();
+    assert!(tickets.is_empty());
+
+    // 5. 导出 CSV
+    let resp = client.get("/api/tickets/export?format=csv").await;
+    assert_eq!(resp.status(), 200);
+    let csv = resp.text().await.unwrap();
+    assert!(csv.contains("Fix signup page"));
+    assert!(csv.contains("bug"));
+
+    // 6. 删除标签后 ticket 上的关联随之删除
+    let resp = client.delete(&format!("/api/tags/{}", bug_id)).await;
+    assert_eq!(resp.status(), 204);
+    let resp = client.get(&format!("/api/tickets/{}", ticket_id)).await;
+    let found: Value = resp.json().await.unwrap();
+    assert_eq!(found["tags"], json!([]));
+
+    // 7. 错误处理
+    let resp = client
+        .delete(&format!("/api/tickets/{}", uuid::Uuid::new_v4()))
+        .await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[derive(Clone, Default)]
+struct Received(Arc<Mutex<Vec<Bytes>>>);
+
+async fn receive(State(received): State<Received>, body: Bytes) -> StatusCode {
+    received.0.lock().unwrap().push(body);
+    StatusCode::OK
+}
+
+#[tokio::test]
+async fn test_sqlite_webhook_outbox() {
+    let server = TestServer::start_sqlite().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let received = Received::default();
+    let app = Router::new()
+        .route("/hook", post(receive))
+        .with_state(received.clone());
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    let resp = client
+        .post(
+            "/api/webhooks",
+            json!({ "url": url, "secret": "s3cret", "events": ["ticket.created"] }),
+        )
+        .await;
+    assert_eq!(resp.status(), 201);
+    let webhook: Value = resp.json().await.unwrap();
+    assert_eq!(webhook["events"], json!(["ticket.created"]));
+    let webhook_id = webhook["id"].as_str().unwrap().to_string();
+
+    // 只有订阅的事件进入发件箱
+    let resp = client
+        .post("/api/tickets", json!({ "title": "Hooked" }))
+        .await;
+    let ticket: Value = resp.json().await.unwrap();
+    client
+        .delete(&format!("/api/tickets/{}", ticket["id"].as_str().unwrap()))
+        .await;
+    // 导入的工单同样入队，自动创建的标签不投递
+    client
+        .post_raw(
+            "/api/tickets/import",
+            "text/csv",
+            "title,tags\nImported,ops\n",
+        )
+        .await;
+
+    let dispatcher = WebhookDispatcher::new(
+        server.state.repositories.webhook.clone(),
+        DispatcherConfig::default(),
+    )
+    .unwrap();
+    assert_eq!(dispatcher.run_once().await.unwrap(), 2);
+    assert_eq!(dispatcher.run_once().await.unwrap(), 0);
+
+    let bodies = received.0.lock().unwrap().clone();
+    assert_eq!(bodies.len(), 2);
+    let mut titles = Vec::new();
+    for body in &bodies {
+        let payload: Value = serde_json::from_slice(body).unwrap();
+        assert_eq!(payload["event"], "ticket.created");
+        titles.push(
+            payload["data"]["ticket"]["title"]
+                .as_str()
+                .unwrap()
+                .to_string(),
+        );
+    }
+    titles.sort();
+    assert_eq!(titles, vec!["Hooked", "Imported"]);
+
+    let resp = client
+        .get(&format!("/api/webhooks/{}/deliveries", webhook_id))
+        .await;
+    let deliveries: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(deliveries.len(), 2);
+    assert_eq!(deliveries[0]["status"], "delivered");
+    assert_eq!(deliveries[0]["attempts"], 1);
+}
+
+#[tokio::test]
+async fn test_sqlite_readiness_and_version() {
+    let server = TestServer::start_sqlite().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client.get("/readyz").await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["status"], "ready");
+    assert_eq!(body["migrations"]["pending"], json!([]));
+
+    let resp = client.get("/version").await;
+    assert_eq!(resp.status(), 200);
+    let body: Value = resp.json().await.unwrap();
+    let migrations = body["migrations"].as_array().unwrap();
+    assert_eq!(migrations.len(), 3);
+    assert_eq!(migrations[1]["description"], "webhooks");
+}
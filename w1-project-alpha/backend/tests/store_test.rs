@@ -0,0 +1,609 @@
+//! 存储层一致性测试：同一组用例分别在内存、Postgres 和 SQLite 实现上运行
+
+mod common;
+
+use chrono::{DateTime, Duration, Utc};
+use futures_util::TryStreamExt;
+use project_alpha_backend::{
+    models::{
+        CreateTagRequest, CreateTicketRequest, ImportTicket, TicketFilter, UpdateTagRequest,
+        UpdateTicketRequest,
+    },
+    repositories::{MemoryStore, Repositories, TagStore, TicketStore},
+    utils::error::AppError,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// 被测的一对存储
+struct Stores {
+    tickets: Arc<dyn TicketStore>,
+    tags: Arc<dyn TagStore>,
+    /// Postgres 用例的独立数据库，随用例结束删除
+    _database: Option<common::TestDatabase>,
+}
+
+impl Stores {
+    async fn tag(&self, name: &str) -> Uuid {
+        self.tags
+            .create(CreateTagRequest {
+                name: name.to_string(),
+                color: None,
+            })
+            .await
+            .unwrap()
+            .id
+    }
+
+    async fn ticket(&self, title: &str, tag_ids: Vec<Uuid>) -> Uuid {
+        self.tickets
+            .create(CreateTicketRequest {
+                title: title.to_string(),
+                description: None,
+                tag_ids: Some(tag_ids),
+            })
+            .await
+            .unwrap()
+            .id
+    }
+
+    async fn titles(&self, filter: TicketFilter) -> Vec<String> {
+        self.tickets
+            .find_all(&filter)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|t| t.ticket.title)
+            .collect()
+    }
+}
+
+fn memory_stores() -> Stores {
+    let store = MemoryStore::new();
+    Stores {
+        tickets: Arc::new(store.clone()),
+        tags: Arc::new(store),
+        _database: None,
+    }
+}
+
+async fn postgres_stores() -> Stores {
+    let database = common::TestDatabase::create().await;
+    let repositories = Repositories::new(database.pool().await);
+    Stores {
+        tickets: repositories.ticket,
+        tags: repositories.tag,
+        _database: Some(database),
+    }
+}
+
+#[cfg(feature = "sqlite")]
+async fn sqlite_stores() -> Stores {
+    use project_alpha_backend::{config::DatabaseConfig, Database};
+
+    let database = Database::connect(&DatabaseConfig::new("sqlite::memory:"))
+        .await
+        .unwrap();
+    database.migrate().await.unwrap();
+    let repositories = database.repositories();
+    Stores {
+        tickets: repositories.ticket,
+        tags: repositories.tag,
+        _database: None,
+    }
+}
+
+/// 固定时间戳的导入行，用于得到确定的排序
+fn imported(title: &str, created_at: DateTime<Utc>, tags: &[&str]) -> ImportTicket {
+    ImportTicket {
+        title: title.to_string(),
+        description: None,
+        completed: false,
+        tags: tags.iter().map(|t| t.to_string()).collect(),
+        created_at: Some(created_at),
+        updated_at: None,
+    }
+}
+
+/// 为每个用例生成 `memory::<case>`、`postgres::<case>` 以及（启用 sqlite 特性时）
+/// `sqlite::<case>` 测试
+macro_rules! conformance {
+    ($($case:ident),* $(,)?) => {
+        mod memory {
+            $(
+                #[tokio::test]
+                async fn $case() {
+                    super::$case(super::memory_stores()).await;
+                }
+            )*
+        }
+
+        mod postgres {
+            $(
+                #[tokio::test]
+                async fn $case() {
+                    super::$case(super::postgres_stores().await).await;
+                }
+            )*
+        }
+
+        #[cfg(feature = "sqlite")]
+        mod sqlite {
+            $(
+                #[tokio::test]
+                async fn $case() {
+                    super::$case(super::sqlite_stores().await).await;
+                }
+            )*
+        }
+    };
+}
+
+conformance!(
+    tags_are_sorted_and_unique,
+    tag_update_is_partial,
+    tickets_are_newest_first_with_sorted_tags,
+    ticket_filters_combine,
+    ticket_update_is_partial,
+    toggle_flips_completed,
+    tag_links_report_missing_rows,
+    deletes_cascade_to_links,
+    import_creates_tags_and_keeps_timestamps,
+    import_reuses_tags_created_meanwhile,
+    stream_matches_find_all,
+    stats_count_by_status_and_tag,
+    pages_split_ties_by_id,
+    tags_are_found_for_many_tickets,
+);
+
+async fn tags_are_sorted_and_unique(stores: Stores) {
+    stores.tag("urgent").await;
+    stores.tag("bug").await;
+
+    let names: Vec<String> = stores
+        .tags
+        .find_all()
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|t| t.name)
+        .collect();
+    assert_eq!(names, vec!["bug", "urgent"]);
+
+    let err = stores
+        .tags
+        .create(CreateTagRequest {
+            name: "bug".to_string(),
+            color: None,
+        })
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)));
+
+    let found = stores.tags.find_by_name("bug").await.unwrap().unwrap();
+    assert_eq!(found.name, "bug");
+    assert!(stores.tags.find_by_name("BUG").await.unwrap().is_none());
+}
+
+async fn tag_update_is_partial(stores: Stores) {
+    let tag = stores
+        .tags
+        .create(CreateTagRequest {
+            name: "bug".to_string(),
+            color: Some("#FF0000".to_string()),
+        })
+        .await
+        .unwrap();
+    let other = stores.tag("feature").await;
+
+    let renamed = stores
+        .tags
+        .update(
+            tag.id,
+            UpdateTagRequest {
+                name: Some("defect".to_string()),
+                color: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(renamed.name, "defect");
+    assert_eq!(renamed.color.as_deref(), Some("#FF0000"));
+
+    let err = stores
+        .tags
+        .update(
+            other,
+            UpdateTagRequest {
+                name: Some("defect".to_string()),
+                color: None,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::Validation(_)));
+
+    let err = stores
+        .tags
+        .update(
+            Uuid::new_v4(),
+            UpdateTagRequest {
+                name: None,
+                color: None,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+}
+
+async fn tickets_are_newest_first_with_sorted_tags(stores: Stores) {
+    let now = Utc::now();
+    stores
+        .tickets
+        .import(
+            &[
+                imported("old", now - Duration::hours(2), &[]),
+                imported("new", now, &["zeta", "alpha"]),
+                imported("middle", now - Duration::hours(1), &[]),
+            ],
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+
+    let tickets = stores
+        .tickets
+        .find_all(&TicketFilter::default())
+        .await
+        .unwrap();
+    let titles: Vec<&str> = tickets.iter().map(|t| t.ticket.title.as_str()).collect();
+    assert_eq!(titles, vec!["new", "middle", "old"]);
+
+    let tag_names: Vec<&str> = tickets[0].tags.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(tag_names, vec!["alpha", "zeta"]);
+}
+
+async fn ticket_filters_combine(stores: Stores) {
+    let bug = stores.tag("bug").await;
+    let ui = stores.tag("ui").await;
+    let login = stores.ticket("Fix Login page", vec![bug, ui]).await;
+    stores.ticket("Login audit", vec![]).await;
+    stores.ticket("Dark mode", vec![ui]).await;
+    stores.tickets.toggle_completed(login).await.unwrap();
+
+    let mut titles = stores
+        .titles(TicketFilter {
+            search: Some("login".to_string()),
+            ..Default::default()
+        })
+        .await;
+    titles.sort();
+    assert_eq!(titles, vec!["Fix Login page", "Login audit"]);
+
+    // 短于三个字符的搜索词同样按子串匹配
+    let mut titles = stores
+        .titles(TicketFilter {
+            search: Some("OG".to_string()),
+            ..Default::default()
+        })
+        .await;
+    titles.sort();
+    assert_eq!(titles, vec!["Fix Login page", "Login audit"]);
+
+    let titles = stores
+        .titles(TicketFilter {
+            search: Some("\"login".to_string()),
+            ..Default::default()
+        })
+        .await;
+    assert!(titles.is_empty());
+
+    let titles = stores
+        .titles(TicketFilter {
+            tag: Some(bug),
+            ..Default::default()
+        })
+        .await;
+    assert_eq!(titles, vec!["Fix Login page"]);
+
+    let titles = stores
+        .titles(TicketFilter {
+            tag: Some(ui),
+            completed: Some(false),
+            ..Default::default()
+        })
+        .await;
+    assert_eq!(titles, vec!["Dark mode"]);
+
+    // 按标签过滤时仍返回该 ticket 的全部标签
+    let tickets = stores
+        .tickets
+        .find_all(&TicketFilter {
+            tag: Some(bug),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(tickets[0].tags.len(), 2);
+}
+
+async fn ticket_update_is_partial(stores: Stores) {
+    let id = stores.ticket("Original", vec![]).await;
+    let before = stores.tickets.find_by_id(id).await.unwrap().unwrap().ticket;
+
+    let unchanged = stores
+        .tickets
+        .update(
+            id,
+            UpdateTicketRequest {
+                title: None,
+                description: None,
+                completed: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(unchanged.updated_at, before.updated_at);
+
+    let updated = stores
+        .tickets
+        .update(
+            id,
+            UpdateTicketRequest {
+                title: None,
+                description: Some("details".to_string()),
+                completed: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(updated.title, "Original");
+    assert_eq!(updated.description.as_deref(), Some("details"));
+    assert!(updated.updated_at >= before.updated_at);
+
+    let err = stores
+        .tickets
+        .update(
+            Uuid::new_v4(),
+            UpdateTicketRequest {
+                title: Some("x".to_string()),
+                description: None,
+                completed: None,
+            },
+        )
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+}
+
+async fn toggle_flips_completed(stores: Stores) {
+    let id = stores.ticket("Toggle me", vec![]).await;
+
+    assert!(stores.tickets.toggle_completed(id).await.unwrap().completed);
+    assert!(!stores.tickets.toggle_completed(id).await.unwrap().completed);
+
+    let err = stores
+        .tickets
+        .toggle_completed(Uuid::new_v4())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+}
+
+async fn tag_links_report_missing_rows(stores: Stores) {
+    let tag = stores.tag("bug").await;
+    let ticket = stores.ticket("Linked", vec![]).await;
+
+    assert!(stores.tickets.add_tag(ticket, tag).await.unwrap());
+    // 重复添加是幂等的，并报告未做修改
+    assert!(!stores.tickets.add_tag(ticket, tag).await.unwrap());
+    let found = stores.tickets.find_by_id(ticket).await.unwrap().unwrap();
+    assert_eq!(found.tags.len(), 1);
+
+    let err = stores
+        .tickets
+        .add_tag(Uuid::new_v4(), tag)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+    let err = stores
+        .tickets
+        .add_tag(ticket, Uuid::new_v4())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+
+    stores.tickets.remove_tag(ticket, tag).await.unwrap();
+    let err = stores.tickets.remove_tag(ticket, tag).await.unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+}
+
+async fn deletes_cascade_to_links(stores: Stores) {
+    let bug = stores.tag("bug").await;
+    let ui = stores.tag("ui").await;
+    let first = stores.ticket("First", vec![bug, ui]).await;
+    let second = stores.ticket("Second", vec![bug]).await;
+
+    stores.tags.delete(bug).await.unwrap();
+    let found = stores.tickets.find_by_id(first).await.unwrap().unwrap();
+    assert_eq!(found.tags.len(), 1);
+    assert_eq!(found.tags[0].id, ui);
+
+    stores.tickets.delete(first).await.unwrap();
+    assert!(stores.tickets.find_by_id(first).await.unwrap().is_none());
+    let titles = stores
+        .titles(TicketFilter {
+            tag: Some(ui),
+            ..Default::default()
+        })
+        .await;
+    assert!(titles.is_empty());
+    assert!(stores.tickets.find_by_id(second).await.unwrap().is_some());
+
+    let err = stores.tickets.delete(first).await.unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+    let err = stores.tags.delete(bug).await.unwrap_err();
+    assert!(matches!(err, AppError::NotFound(_)));
+}
+
+async fn import_creates_tags_and_keeps_timestamps(stores: Stores) {
+    let bug = stores.tag("bug").await;
+    let created_at: DateTime<Utc> = "2024-01-02T03:04:05Z".parse().unwrap();
+
+    let (imported, created) = stores
+        .tickets
+        .import(
+            &[
+                imported("First", created_at, &["ops", "bug"]),
+                imported("Second", created_at, &["api", "ops"]),
+            ],
+            HashMap::from([("bug".to_string(), bug)]),
+        )
+        .await
+        .unwrap();
+
+    let created: Vec<String> = created.into_iter().map(|t| t.name).collect();
+    assert_eq!(created, vec!["ops", "api"]);
+    let ids: Vec<Uuid> = imported.iter().map(|t| t.id).collect();
+    assert_eq!(ids.len(), 2);
+    assert_eq!(imported[0].title, "First");
+    assert_eq!(imported[0].created_at, created_at);
+
+    let first = stores.tickets.find_by_id(ids[0]).await.unwrap().unwrap();
+    assert_eq!(first.ticket.title, "First");
+    assert_eq!(first.ticket.created_at, created_at);
+    assert_eq!(first.ticket.updated_at, created_at);
+    let tag_names: Vec<&str> = first.tags.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(tag_names, vec!["bug", "ops"]);
+}
+
+/// 调用方查找标签之后，其他请求创建了同名标签：复用该标签，而不是整个导入失败
+async fn import_reuses_tags_created_meanwhile(stores: Stores) {
+    let ops = stores.tag("ops").await;
+    let created_at: DateTime<Utc> = "2024-01-02T03:04:05Z".parse().unwrap();
+
+    let (imported, created) = stores
+        .tickets
+        .import(
+            &[imported("First", created_at, &["ops", "api"])],
+            HashMap::new(),
+        )
+        .await
+        .unwrap();
+
+    let created: Vec<String> = created.into_iter().map(|t| t.name).collect();
+    assert_eq!(created, vec!["api"]);
+    let first = stores
+        .tickets
+        .find_by_id(imported[0].id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(first.tags.iter().any(|tag| tag.id == ops));
+    assert_eq!(stores.tags.find_all().await.unwrap().len(), 2);
+}
+
+async fn stream_matches_find_all(stores: Stores) {
+    let bug = stores.tag("bug").await;
+    stores.ticket("One", vec![bug]).await;
+    stores.ticket("Two", vec![]).await;
+
+    let filter = TicketFilter {
+        tag: Some(bug),
+        ..Default::default()
+    };
+    let listed = stores.tickets.find_all(&filter).await.unwrap();
+    let streamed: Vec<_> = stores
+        .tickets
+        .stream_all(filter)
+        .try_collect()
+        .await
+        .unwrap();
+
+    assert_eq!(streamed.len(), listed.len());
+    for (a, b) in streamed.iter().zip(&listed) {
+        assert_eq!(a.ticket.id, b.ticket.id);
+        assert_eq!(a.tags.len(), b.tags.len());
+    }
+}
+
+async fn stats_count_by_status_and_tag(stores: Stores) {
+    let stats = stores.tickets.stats().await.unwrap();
+    assert_eq!((stats.open, stats.completed), (0, 0));
+    assert!(stats.by_tag.is_empty());
+
+    let ui = stores.tag("ui").await;
+    let bug = stores.tag("bug").await;
+    stores.tag("docs").await;
+    let first = stores.ticket("First", vec![bug, ui]).await;
+    stores.ticket("Second", vec![bug]).await;
+    stores.ticket("Third", vec![]).await;
+    stores.tickets.toggle_completed(first).await.unwrap();
+
+    let stats = stores.tickets.stats().await.unwrap();
+    assert_eq!((stats.open, stats.completed), (2, 1));
+    let by_tag: Vec<(&str, i64)> = stats
+        .by_tag
+        .iter()
+        .map(|t| (t.name.as_str(), t.tickets))
+        .collect();
+    assert_eq!(by_tag, vec![("bug", 2), ("docs", 0), ("ui", 1)]);
+    assert_eq!(stats.by_tag[0].tag_id, bug);
+}
+
+async fn pages_split_ties_by_id(stores: Stores) {
+    // 相同的创建时间下，按 id 排序保证分页不重叠
+    let created_at: DateTime<Utc> = "2024-01-02T03:04:05Z".parse().unwrap();
+    let batch: Vec<_> = (0..5)
+        .map(|i| imported(&format!("Ticket {}", i), created_at, &["ops"]))
+        .collect();
+    let (imported, _) = stores.tickets.import(&batch, HashMap::new()).await.unwrap();
+    let mut ids: Vec<Uuid> = imported.iter().map(|t| t.id).collect();
+    stores.ticket("Untagged", vec![]).await;
+    let ops = stores.tags.find_all().await.unwrap()[0].id;
+    ids.sort();
+
+    let filter = TicketFilter {
+        tag: Some(ops),
+        ..Default::default()
+    };
+    let mut paged = Vec::new();
+    for offset in [0, 2, 4] {
+        let (items, total) = stores.tickets.find_page(&filter, 2, offset).await.unwrap();
+        assert_eq!(total, 5);
+        assert!(items.iter().all(|t| t.tags.len() == 1));
+        paged.extend(items.into_iter().map(|t| t.ticket.id));
+    }
+    assert_eq!(paged, ids);
+
+    let (items, total) = stores
+        .tickets
+        .find_page(&TicketFilter::default(), 10, 10)
+        .await
+        .unwrap();
+    assert!(items.is_empty());
+    assert_eq!(total, 6);
+}
+
+async fn tags_are_found_for_many_tickets(stores: Stores) {
+    let ui = stores.tag("ui").await;
+    let bug = stores.tag("bug").await;
+    let first = stores.ticket("First", vec![ui, bug]).await;
+    let second = stores.ticket("Second", vec![bug]).await;
+    let untagged = stores.ticket("Untagged", vec![]).await;
+
+    let tags = stores
+        .tickets
+        .find_tags(&[first, second, untagged, Uuid::new_v4()])
+        .await
+        .unwrap();
+    let names = |id: Uuid| -> Vec<String> { tags[&id].iter().map(|t| t.name.clone()).collect() };
+    assert_eq!(tags.len(), 2);
+    assert_eq!(names(first), vec!["bug", "ui"]);
+    assert_eq!(names(second), vec!["bug"]);
+    assert_eq!(tags[&second][0].id, bug);
+
+    assert!(stores.tickets.find_tags(&[]).await.unwrap().is_empty());
+}
@@ -0,0 +1,232 @@
+//! API 版本测试：/api/v1、/api/v2、弃用的 /api 前缀与 Accept 协商
+mod common;
+
+use common::{TestClient, TestServer};
+use serde_json::{json, Value};
+
+const V2_ACCEPT: &str = "application/vnd.alpha.v2+json";
+
+fn header<'a>(resp: &'a reqwest::Response, name: &str) -> Option<&'a str> {
+    resp.headers().get(name).map(|v| v.to_str().unwrap())
+}
+
+#[tokio::test]
+async fn test_versioned_prefixes_are_not_deprecated() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client
+        .post("/api/v1/tickets", json!({ "title": "Versioned" }))
+        .await;
+    assert_eq!(resp.status(), 201);
+    let ticket: Value = resp.json().await.unwrap();
+    assert_eq!(ticket["completed"], false);
+
+    for path in ["/api/v1/tickets", "/api/v2/tickets", "/api/v1/tags"] {
+        let resp = client.get(path).await;
+        assert_eq!(resp.status(), 200, "{}", path);
+        assert_eq!(header(&resp, "deprecation"), None, "{}", path);
+        assert_eq!(header(&resp, "sunset"), None, "{}", path);
+        assert_eq!(
+            header(&resp, "content-type"),
+            Some("application/json"),
+            "{}",
+            path
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_unversioned_prefix_is_deprecated_v1() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let resp = client
+        .post("/api/tickets", json!({ "title": "Legacy" }))
+        .await;
+    assert_eq!(resp.status(), 201);
+    assert_eq!(header(&resp, "deprecation"), Some("@1793491200"));
+    assert_eq!(
+        header(&resp, "sunset"),
+        Some("Sat, 01 May 2027 00:00:00 GMT")
+    );
+    assert_eq!(
+        header(&resp, "link"),
+        Some("</api/v1/tickets>; rel=\"successor-version\"")
+    );
+    assert_eq!(header(&resp, "vary"), Some("accept"));
+    let ticket: Value = resp.json().await.unwrap();
+    let id = ticket["id"].as_str().unwrap();
+
+    // 响应仍是 v1 的形状
+    let resp = client.get(&format!("/api/tickets/{}/toggle", id)).await;
+    assert_eq!(resp.status(), 405);
+    let resp = client.patch(&format!("/api/tickets/{}/toggle", id)).await;
+    assert_eq!(
+        header(&resp, "link"),
+        Some(format!("</api/v1/tickets/{}/toggle>; rel=\"successor-version\"", id).as_str())
+    );
+    let ticket: Value = resp.json().await.unwrap();
+    assert_eq!(ticket["completed"], true);
+    assert!(ticket.get("status").is_none());
+
+    // 未知路径仍是 404
+    let resp = client.get("/api/nothing-here").await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[tokio::test]
+async fn test_unversioned_prefix_without_vendor_type_is_deprecated() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    let http = reqwest::Client::new();
+
+    // 不带 Accept、通配或普通 JSON 都不算选择了版本
+    for accept in [None, Some("*/*"), Some("application/json")] {
+        let mut request = http.get(client.url("/api/tags"));
+        if let Some(accept) = accept {
+            request = request.header("accept", accept);
+        }
+        let resp = request.send().await.unwrap();
+        assert_eq!(resp.status(), 200, "{:?}", accept);
+        assert_eq!(
+            header(&resp, "deprecation"),
+            Some("@1793491200"),
+            "{:?}",
+            accept
+        );
+        assert_eq!(
+            header(&resp, "sunset"),
+            Some("Sat, 01 May 2027 00:00:00 GMT"),
+            "{:?}",
+            accept
+        );
+        assert_eq!(
+            header(&resp, "content-type"),
+            Some("application/json"),
+            "{:?}",
+            accept
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_v2_pages_and_status() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    let mut ids = Vec::new();
+    for i in 0..5 {
+        let resp = client
+            .post(
+                "/api/v2/tickets",
+                json!({ "title": format!("Ticket {}", i) }),
+            )
+            .await;
+        assert_eq!(resp.status(), 201);
+        let ticket: Value = resp.json().await.unwrap();
+        assert_eq!(ticket["status"], "open");
+        assert_eq!(ticket["tags"], json!([]));
+        assert!(ticket.get("completed").is_none());
+        ids.push(ticket["id"].as_str().unwrap().to_string());
+    }
+
+    let resp = client.get("/api/v2/tickets?per_page=2&page=3").await;
+    assert_eq!(resp.status(), 200);
+    let page: Value = resp.json().await.unwrap();
+    assert_eq!(page["page"], 3);
+    assert_eq!(page["per_page"], 2);
+    assert_eq!(page["total"], 5);
+    assert_eq!(page["total_pages"], 3);
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+
+    // 按页遍历，不重复也不遗漏
+    let mut seen = Vec::new();
+    for n in 1..=3 {
+        let page: Value = client
+            .get(&format!("/api/v2/tickets?per_page=2&page={}", n))
+            .await
+            .json()
+            .await
+            .unwrap();
+        for item in page["items"].as_array().unwrap() {
+            seen.push(item["id"].as_str().unwrap().to_string());
+        }
+    }
+    seen.sort();
+    ids.sort();
+    assert_eq!(seen, ids);
+
+    // 状态取代 completed
+    let resp = client
+        .put(
+            &format!("/api/v2/tickets/{}", ids[0]),
+            json!({ "status": "completed" }),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    let ticket: Value = resp.json().await.unwrap();
+    assert_eq!(ticket["status"], "completed");
+
+    let page: Value = client
+        .get("/api/v2/tickets?completed=true")
+        .await
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(page["total"], 1);
+
+    for query in ["per_page=0", "per_page=101", "page=0"] {
+        let resp = client.get(&format!("/api/v2/tickets?{}", query)).await;
+        assert_eq!(resp.status(), 400, "{}", query);
+    }
+}
+
+#[tokio::test]
+async fn test_accept_header_selects_the_version() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    let http = reqwest::Client::new();
+
+    client
+        .post("/api/v1/tickets", json!({ "title": "Negotiated" }))
+        .await;
+
+    let resp = http
+        .get(client.url("/api/tickets"))
+        .header("accept", V2_ACCEPT)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 200);
+    assert_eq!(header(&resp, "content-type"), Some(V2_ACCEPT));
+    assert_eq!(header(&resp, "deprecation"), None);
+    assert_eq!(header(&resp, "vary"), Some("accept"));
+    let page: Value = resp.json().await.unwrap();
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["items"][0]["status"], "open");
+
+    // 显式请求 v1 也不再提示弃用
+    let resp = http
+        .get(client.url("/api/tickets"))
+        .header("accept", "application/vnd.alpha.v1+json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        header(&resp, "content-type"),
+        Some("application/vnd.alpha.v1+json")
+    );
+    assert_eq!(header(&resp, "deprecation"), None);
+    let tickets: Value = resp.json().await.unwrap();
+    assert_eq!(tickets[0]["completed"], false);
+
+    // 错误响应同样走 v2 路由
+    let resp = http
+        .get(client.url("/api/tickets?per_page=0"))
+        .header("accept", V2_ACCEPT)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+}
@@ -0,0 +1,397 @@
+mod common;
+
+use axum::{body::Bytes, extract::State, http::HeaderMap, http::StatusCode, routing::post, Router};
+use common::{TestClient, TestServer};
+use project_alpha_backend::webhooks::{verify, DispatcherConfig, WebhookDispatcher};
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+/// 本地 HTTP 桩：记录收到的请求，并按顺序返回预设状态码
+#[derive(Clone, Default)]
+struct Stub {
+    requests: Arc<Mutex<Vec<(HeaderMap, Bytes)>>>,
+    statuses: Arc<Mutex<Vec<StatusCode>>>,
+}
+
+impl Stub {
+    async fn start(statuses: Vec<StatusCode>) -> (Self, String) {
+        let stub = Stub {
+            statuses: Arc::new(Mutex::new(statuses)),
+            ..Default::default()
+        };
+        let app = Router::new()
+            .route("/hook", post(receive))
+            .with_state(stub.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        (stub, url)
+    }
+
+    fn requests(&self) -> Vec<(HeaderMap, Bytes)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn receive(State(stub): State<Stub>, headers: HeaderMap, body: Bytes) -> StatusCode {
+    stub.requests.lock().unwrap().push((headers, body));
+    let mut statuses = stub.statuses.lock().unwrap();
+    if statuses.is_empty() {
+        StatusCode::OK
+    } else {
+        statuses.remove(0)
+    }
+}
+
+/// 立即重试的投递任务，便于测试
+fn dispatcher(server: &TestServer) -> WebhookDispatcher {
+    WebhookDispatcher::new(
+        server.state.repositories.webhook.clone(),
+        DispatcherConfig {
+            base_backoff: Duration::ZERO,
+            max_attempts: 3,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_webhook_crud() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+
+    // 1. 创建 webhook，响应中不包含 secret
+    let resp = client
+        .post(
+            "/api/webhooks",
+            json!({
+                "url": "http://127.0.0.1:9/hook",
+                "secret": "s3cret",
+                "events": ["ticket.created"]
+            }),
+        )
+        .await;
+    assert_eq!(resp.status(), 201);
+    let webhook: Value = resp.json().await.unwrap();
+    let webhook_id = webhook["id"].as_str().unwrap();
+    assert_eq!(webhook["events"], json!(["ticket.created"]));
+    assert_eq!(webhook["active"], true);
+    assert!(webhook.get("secret").is_none());
+
+    // 2. 获取列表与单个 webhook
+    let resp = client.get("/api/webhooks").await;
+    let webhooks: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(webhooks.len(), 1);
+
+    let resp = client.get(&format!("/api/webhooks/{}", webhook_id)).await;
+    assert_eq!(resp.status(), 200);
+
+    // 3. 部分更新
+    let resp = client
+        .put(
+            &format!("/api/webhooks/{}", webhook_id),
+            json!({ "active": false, "events": [] }),
+        )
+        .await;
+    assert_eq!(resp.status(), 200);
+    let webhook: Value = resp.json().await.unwrap();
+    assert_eq!(webhook["active"], false);
+    assert_eq!(webhook["events"], json!([]));
+    assert_eq!(webhook["url"], "http://127.0.0.1:9/hook");
+
+    // 4. 校验错误
+    let resp = client
+        .post(
+            "/api/webhooks",
+            json!({ "url": "ftp://example.com", "secret": "s3cret" }),
+        )
+        .await;
+    assert_eq!(resp.status(), 400);
+    let resp = client
+        .post(
+            "/api/webhooks",
+            json!({ "url": "http://example.com", "secret": "s3cret", "events": ["ticket.exploded"] }),
+        )
+        .await;
+    assert_eq!(resp.status(), 400);
+    let resp = client
+        .post(
+            "/api/webhooks",
+            json!({ "url": "http://example.com", "secret": " " }),
+        )
+        .await;
+    assert_eq!(resp.status(), 400);
+
+    // 5. 删除
+    let resp = client
+        .delete(&format!("/api/webhooks/{}", webhook_id))
+        .await;
+    assert_eq!(resp.status(), 204);
+    let resp = client.get(&format!("/api/webhooks/{}", webhook_id)).await;
+    assert_eq!(resp.status(), 404);
+    let resp = client
+        .get(&format!("/api/webhooks/{}/deliveries", webhook_id))
+        .await;
+    assert_eq!(resp.status(), 404);
+}
+
+#[tokio::test]
+async fn test_webhook_delivery_is_signed_and_filtered() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    let (stub, url) = Stub::start(vec![]).await;
+
+    let resp = client
+        .post(
+            "/api/webhooks",
+            json!({ "url": url, "secret": "s3cret", "events": ["ticket.created"] }),
+        )
+        .await;
+    let webhook: Value = resp.json().await.unwrap();
+    let webhook_id = webhook["id"].as_str().unwrap();
+
+    // 未订阅的事件不会入队
+    let resp = client
+        .post("/api/tickets", json!({ "title": "Deploy pipeline" }))
+        .await;
+    let ticket: Value = resp.json().await.unwrap();
+    let ticket_id = ticket["id"].as_str().unwrap();
+    client
+        .patch(&format!("/api/tickets/{}/toggle", ticket_id))
+        .await;
+
+    let sent = dispatcher(&server).run_once().await.unwrap();
+    assert_eq!(sent, 1);
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 1);
+    let (headers, body) = &requests[0];
+    assert_eq!(headers["x-alpha-event"], "ticket.created");
+    let signature = headers["x-alpha-signature"].to_str().unwrap();
+    assert!(verify("s3cret", body, signature));
+
+    let payload: Value = serde_json::from_slice(body).unwrap();
+    assert_eq!(payload["event"], "ticket.created");
+    assert_eq!(payload["data"]["ticket"]["id"], ticket_id);
+    assert_eq!(payload["data"]["ticket"]["title"], "Deploy pipeline");
+
+    // 投递日志
+    let resp = client
+        .get(&format!("/api/webhooks/{}/deliveries", webhook_id))
+        .await;
+    assert_eq!(resp.status(), 200);
+    let deliveries: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(deliveries.len(), 1);
+    assert_eq!(deliveries[0]["status"], "delivered");
+    assert_eq!(deliveries[0]["attempts"], 1);
+    assert_eq!(deliveries[0]["last_status_code"], 200);
+    assert_eq!(
+        headers["x-alpha-delivery"].to_str().unwrap(),
+        deliveries[0]["id"].as_str().unwrap()
+    );
+
+    // 已投递的记录不会再次发送
+    assert_eq!(dispatcher(&server).run_once().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn test_webhook_delivery_retries_then_fails() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    let (stub, url) = Stub::start(vec![
+        StatusCode::INTERNAL_SERVER_ERROR,
+        StatusCode::SERVICE_UNAVAILABLE,
+    ])
+    .await;
+    let (failing_stub, failing_url) = Stub::start(vec![StatusCode::BAD_GATEWAY; 3]).await;
+
+    let resp = client
+        .post("/api/webhooks", json!({ "url": url, "secret": "s3cret" }))
+        .await;
+    let webhook: Value = resp.json().await.unwrap();
+    let webhook_id = webhook["id"].as_str().unwrap();
+    let resp = client
+        .post(
+            "/api/webhooks",
+            json!({ "url": failing_url, "secret": "s3cret" }),
+        )
+        .await;
+    let failing: Value = resp.json().await.unwrap();
+    let failing_id = failing["id"].as_str().unwrap();
+
+    let resp = client
+        .post("/api/tickets", json!({ "title": "Flaky receiver" }))
+        .await;
+    assert_eq!(resp.status(), 201);
+
+    let dispatcher = dispatcher(&server);
+
+    // 第一次失败后保持 pending，并记录状态码
+    assert_eq!(dispatcher.run_once().await.unwrap(), 2);
+    let resp = client
+        .get(&format!("/api/webhooks/{}/deliveries", webhook_id))
+        .await;
+    let deliveries: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(deliveries[0]["status"], "pending");
+    assert_eq!(deliveries[0]["attempts"], 1);
+    assert_eq!(deliveries[0]["last_status_code"], 500);
+
+    // 第二次失败，第三次成功
+    assert_eq!(dispatcher.run_once().await.unwrap(), 2);
+    assert_eq!(dispatcher.run_once().await.unwrap(), 2);
+    assert_eq!(dispatcher.run_once().await.unwrap(), 0);
+
+    let resp = client
+        .get(&format!("/api/webhooks/{}/deliveries", webhook_id))
+        .await;
+    let deliveries: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(deliveries[0]["status"], "delivered");
+    assert_eq!(deliveries[0]["attempts"], 3);
+    assert_eq!(stub.requests().len(), 3);
+
+    // 达到最大次数后标记为 failed
+    let resp = client
+        .get(&format!("/api/webhooks/{}/deliveries", failing_id))
+        .await;
+    let deliveries: Vec<Value> = resp.json().await.unwrap();
+    assert_eq!(deliveries[0]["status"], "failed");
+    assert_eq!(deliveries[0]["attempts"], 3);
+    assert_eq!(deliveries[0]["last_status_code"], 502);
+    assert_eq!(failing_stub.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn test_dispatcher_flushes_on_shutdown() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    let (stub, url) = Stub::start(vec![]).await;
+
+    client
+        .post("/api/webhooks", json!({ "url": url, "secret": "s3cret" }))
+        .await;
+
+    // 轮询间隔很长：停止前入队的投递只能靠退出时的 flush 送达
+    let dispatcher = WebhookDispatcher::new(
+        server.state.repositories.webhook.clone(),
+        DispatcherConfig {
+            poll_interval: Duration::from_secs(3600),
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let shutdown = CancellationToken::new();
+    let task = tokio::spawn(dispatcher.run(shutdown.clone()));
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    client
+        .post("/api/tickets", json!({ "title": "Before shutdown" }))
+        .await;
+    assert!(stub.requests().is_empty());
+
+    shutdown.cancel();
+    tokio::time::timeout(Duration::from_secs(5), task)
+        .await
+        .expect("Dispatcher did not stop")
+        .unwrap();
+
+    let requests = stub.requests();
+    assert_eq!(requests.len(), 1);
+    let payload: Value = serde_json::from_slice(&requests[0].1).unwrap();
+    assert_eq!(payload["data"]["ticket"]["title"], "Before shutdown");
+}
+
+#[tokio::test]
+async fn test_slow_receiver_does_not_hold_up_others() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    let (fast, fast_url) = Stub::start(vec![]).await;
+
+    // 慢速接收方：2 秒后才响应
+    let app = Router::new().route(
+        "/hook",
+        post(|| async {
+            tokio::time::sleep(Duration::from_secs(2)).await;
+            StatusCode::OK
+        }),
+    );
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let slow_url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    for url in [&slow_url, &fast_url] {
+        client
+            .post("/api/webhooks", json!({ "url": url, "secret": "s3cret" }))
+            .await;
+    }
+    client
+        .post("/api/tickets", json!({ "title": "Fan out" }))
+        .await;
+
+    let dispatcher = dispatcher(&server);
+    let round = tokio::spawn(async move { dispatcher.run_once().await.unwrap() });
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    assert_eq!(fast.requests().len(), 1);
+    assert!(!round.is_finished());
+    assert_eq!(round.await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn test_import_notifies_webhooks() {
+    let server = TestServer::start().await;
+    let client = TestClient::new(server.base_url.clone());
+    let (stub, url) = Stub::start(vec![]).await;
+
+    client
+        .post("/api/webhooks", json!({ "url": url, "secret": "s3cret" }))
+        .await;
+
+    // 试运行不写入任何数据，也不入队
+    let csv = "title,tags\nFirst import,ops\nSecond import,\n";
+    let resp = client
+        .post_raw("/api/tickets/import?dry_run=true", "text/csv", csv)
+        .await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(dispatcher(&server).run_once().await.unwrap(), 0);
+
+    let resp = client
+        .post_raw("/api/tickets/import", "text/csv", csv)
+        .await;
+    let report: Value = resp.json().await.unwrap();
+    assert_eq!(report["imported"], 2);
+
+    // 每个导入的工单一次投递；与通过 API 创建的标签一样，自动创建的标签不投递
+    assert_eq!(report["created_tags"], json!(["ops"]));
+    assert_eq!(dispatcher(&server).run_once().await.unwrap(), 2);
+    let mut events: Vec<(String, String)> = stub
+        .requests()
+        .iter()
+        .map(|(headers, body)| {
+            let payload: Value = serde_json::from_slice(body).unwrap();
+            (
+                headers["x-alpha-event"].to_str().unwrap().to_string(),
+                payload["data"]["ticket"]["title"]
+                    .as_str()
+                    .unwrap()
+                    .to_string(),
+            )
+        })
+        .collect();
+    events.sort();
+    assert_eq!(
+        events,
+        vec![
+            ("ticket.created".to_string(), "First import".to_string()),
+            ("ticket.created".to_string(), "Second import".to_string()),
+        ]
+    );
+}
@@ -76,7 +76,7 @@ export const ticketApi = {
     if (query?.completed !== undefined) params.append("completed", String(query.completed));
 
     const response = await apiClient.get<TicketWithTags[]>(
-      `/api/tickets${params.toString() ? `?${params.toString()}` : ""}`
+      `/api/v1/tickets${params.toString() ? `?${params.toString()}` : ""}`
     );
     return response.data;
   },
@@ -86,7 +86,7 @@ export const ticketApi = {
    * @param id - Ticket ID
    */
   getTicket: async (id: string): Promise<TicketWithTags> => {
-    const response = await apiClient.get<TicketWithTags>(`/api/tickets/${id}`);
+    const response = await apiClient.get<TicketWithTags>(`/api/v1/tickets/${id}`);
     return response.data;
   },
 
@@ -95,7 +95,7 @@ export const ticketApi = {
    * @param data - Ticket 数据
    */
   createTicket: async (data: CreateTicketRequest): Promise<TicketWithTags> => {
-    const response = await apiClient.post<TicketWithTags>("/api/tickets", data);
+    const response = await apiClient.post<TicketWithTags>("/api/v1/tickets", data);
     return response.data;
   },
 
@@ -105,7 +105,7 @@ export const ticketApi = {
    * @param data - 更新的数据
    */
   updateTicket: async (id: string, data: UpdateTicketRequest): Promise<TicketWithTags> => {
-    const response = await apiClient.put<TicketWithTags>(`/api/tickets/${id}`, data);
+    const response = await apiClient.put<TicketWithTags>(`/api/v1/tickets/${id}`, data);
     return response.data;
   },
 
@@ -114,7 +114,7 @@ export const ticketApi = {
    * @param id - Ticket ID
    */
   deleteTicket: async (id: string): Promise<void> => {
-    await apiClient.delete(`/api/tickets/${id}`);
+    await apiClient.delete(`/api/v1/tickets/${id}`);
   },
 
   /**
@@ -122,7 +122,7 @@ export const ticketApi = {
    * @param id - Ticket ID
    */
   toggleCompleted: async (id: string): Promise<TicketWithTags> => {
-    const response = await apiClient.patch<TicketWithTags>(`/api/tickets/${id}/toggle`);
+    const response = await apiClient.patch<TicketWithTags>(`/api/v1/tickets/${id}/toggle`);
     return response.data;
   },
 
@@ -132,7 +132,7 @@ export const ticketApi = {
    * @param tagId - Tag ID
    */
   addTag: async (ticketId: string, tagId: string): Promise<void> => {
-    await apiClient.post(`/api/tickets/${ticketId}/tags/${tagId}`);
+    await apiClient.post(`/api/v1/tickets/${ticketId}/tags/${tagId}`);
   },
 
   /**
@@ -141,7 +141,7 @@ export const ticketApi = {
    * @param tagId - Tag ID
    */
   removeTag: async (ticketId: string, tagId: string): Promise<void> => {
-    await apiClient.delete(`/api/tickets/${ticketId}/tags/${tagId}`);
+    await apiClient.delete(`/api/v1/tickets/${ticketId}/tags/${tagId}`);
   },
 };
 
@@ -153,7 +153,7 @@ export const tagApi = {
    * 获取所有标签
    */
   getTags: async (): Promise<Tag[]> => {
-    const response = await apiClient.get<Tag[]>("/api/tags");
+    const response = await apiClient.get<Tag[]>("/api/v1/tags");
     return response.data;
   },
 
@@ -162,7 +162,7 @@ export const tagApi = {
    * @param id - Tag ID
    */
   getTag: async (id: string): Promise<Tag> => {
-    const response = await apiClient.get<Tag>(`/api/tags/${id}`);
+    const response = await apiClient.get<Tag>(`/api/v1/tags/${id}`);
     return response.data;
   },
 
@@ -171,7 +171,7 @@ export const tagApi = {
    * @param data - Tag 数据
    */
   createTag: async (data: CreateTagRequest): Promise<Tag> => {
-    const response = await apiClient.post<Tag>("/api/tags", data);
+    const response = await apiClient.post<Tag>("/api/v1/tags", data);
     return response.data;
   },
 
@@ -181,7 +181,7 @@ export const tagApi = {
    * @param data - 更新的数据
    */
   updateTag: async (id: string, data: UpdateTagRequest): Promise<Tag> => {
-    const response = await apiClient.put<Tag>(`/api/tags/${id}`, data);
+    const response = await apiClient.put<Tag>(`/api/v1/tags/${id}`, data);
     return response.data;
   },
 
@@ -190,7 +190,7 @@ export const tagApi = {
    * @param id - Tag ID
    */
   deleteTag: async (id: string): Promise<void> => {
-    await apiClient.delete(`/api/tags/${id}`);
+    await apiClient.delete(`/api/v1/tags/${id}`);
   },
 };
 